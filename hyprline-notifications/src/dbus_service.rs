@@ -1,21 +1,48 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use tokio::sync::Mutex;
 use zbus::{interface, Connection, SignalContext};
-use async_channel::Sender;
+use async_channel::{Receiver, Sender};
 
-use crate::notification::{Notification, NotificationData, NotificationUrgency};
-use crate::repository::NotificationRepository;
+use crate::hints::{desktop_entry_icon, NotificationHints};
+use crate::notification::{Notification, NotificationUrgency};
+use crate::repository::{HistoryFilter, NotificationRepository};
+use crate::rules::NotificationRules;
+use crate::ui::popup::PopupEvent;
 use crate::UiEvent;
 
+/// Следующий id уведомления; при старте продолжает нумерацию из истории
 static NOTIFICATION_ID: AtomicU32 = AtomicU32::new(1);
 
+/// Выдаёт новый id (0 зарезервирован спецификацией)
+fn next_notification_id() -> u32 {
+    loop {
+        let id = NOTIFICATION_ID.fetch_add(1, Ordering::SeqCst);
+        if id != 0 {
+            return id;
+        }
+    }
+}
+
+const OBJECT_PATH: &str = "/org/freedesktop/Notifications";
+
+/// Причины закрытия для сигнала NotificationClosed
+const REASON_EXPIRED: u32 = 1;
+const REASON_DISMISSED: u32 = 2;
+const REASON_CLOSED: u32 = 3;
+
+/// Как часто проверять расписание DND
+const DND_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// D-Bus сервис уведомлений (org.freedesktop.Notifications)
 pub struct NotificationDbusService {
     repository: Arc<Mutex<NotificationRepository>>,
+    rules: Arc<Mutex<NotificationRules>>,
     notification_tx: Sender<Notification>,
     ui_tx: Sender<UiEvent>,
+    /// Id уведомлений, popup которых сейчас на экране
+    on_screen: Arc<Mutex<HashSet<u32>>>,
 }
 
 impl NotificationDbusService {
@@ -23,18 +50,28 @@ impl NotificationDbusService {
         repository: Arc<Mutex<NotificationRepository>>,
         notification_tx: Sender<Notification>,
         ui_tx: Sender<UiEvent>,
+        popup_event_rx: Receiver<PopupEvent>,
     ) -> Result<(), zbus::Error> {
         let connection = Connection::session().await?;
 
+        // Иначе после перезапуска новые уведомления получат id из старой истории
+        let max_id = repository.lock().await.max_id().unwrap_or(0);
+        NOTIFICATION_ID.store(max_id.wrapping_add(1), Ordering::SeqCst);
+
+        let rules = Arc::new(Mutex::new(NotificationRules::new()));
+        let on_screen = Arc::new(Mutex::new(HashSet::new()));
+
         let service = Self {
             repository,
+            rules: rules.clone(),
             notification_tx,
             ui_tx,
+            on_screen: on_screen.clone(),
         };
 
         connection
             .object_server()
-            .at("/org/freedesktop/Notifications", service)
+            .at(OBJECT_PATH, service)
             .await?;
 
         connection
@@ -45,12 +82,64 @@ impl NotificationDbusService {
         eprintln!("[NotificationService] ✓ Service: org.freedesktop.Notifications");
         eprintln!("[NotificationService] ✓ Ready to receive notifications");
 
-        // Ждём бесконечно
-        std::future::pending::<()>().await;
+        let ctxt = SignalContext::new(&connection, OBJECT_PATH)?;
+
+        // Расписание DND и правка файла правил меняют состояние без вызовов D-Bus
+        let dnd_ctxt = ctxt.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(DND_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let changed = rules.lock().await.check_dnd_changed();
+                if let Some(active) = changed {
+                    let _ = Self::dnd_changed(&dnd_ctxt, active).await;
+                }
+            }
+        });
+
+        // Превращаем события popup в сигналы D-Bus
+        while let Ok(event) = popup_event_rx.recv().await {
+            on_screen.lock().await.remove(&event.id());
+            if let Err(e) = Self::emit_popup_event(&ctxt, event).await {
+                eprintln!("[NotificationService] ✗ Failed to emit signal: {}", e);
+            }
+        }
 
         Ok(())
     }
 
+    /// Отправляет сигналы по событию от popup
+    ///
+    /// Для действия порядок как у других серверов: ActivationToken, ActionInvoked,
+    /// затем NotificationClosed - popup после действия закрывается.
+    async fn emit_popup_event(ctxt: &SignalContext<'_>, event: PopupEvent) -> zbus::Result<()> {
+        match event {
+            PopupEvent::ActionInvoked { id, action_key, activation_token } => {
+                eprintln!("[NotificationService] Action invoked: id={}, key={}", id, action_key);
+                if let Some(token) = activation_token {
+                    Self::activation_token(ctxt, id, token).await?;
+                }
+                Self::action_invoked(ctxt, id, action_key).await?;
+                Self::notification_closed(ctxt, id, REASON_DISMISSED).await
+            }
+            PopupEvent::Dismissed { id } => {
+                eprintln!("[NotificationService] Notification dismissed: id={}", id);
+                Self::notification_closed(ctxt, id, REASON_DISMISSED).await
+            }
+            PopupEvent::Expired { id } => {
+                Self::notification_closed(ctxt, id, REASON_EXPIRED).await
+            }
+        }
+    }
+
+    /// Уведомление с этим id на экране или в истории
+    async fn is_known(&self, id: u32) -> bool {
+        if self.on_screen.lock().await.contains(&id) {
+            return true;
+        }
+        self.repository.lock().await.exists(id).unwrap_or(false)
+    }
+
     /// Отправить сигнал об изменении количества уведомлений
     async fn emit_count_changed(&self, ctxt: &SignalContext<'_>) {
         let count = {
@@ -79,7 +168,9 @@ impl NotificationDbusService {
             "body".to_string(),
             "body-markup".to_string(),
             "actions".to_string(),
+            "icon-static".to_string(),
             "persistence".to_string(),
+            "sound".to_string(),
         ]
     }
 
@@ -96,23 +187,24 @@ impl NotificationDbusService {
         hints: HashMap<String, zbus::zvariant::OwnedValue>,
         expire_timeout: i32,
     ) -> u32 {
-        let id = if replaces_id > 0 {
+        // replaces_id обновляет уже показанное уведомление и строку истории;
+        // неизвестный id (закрыт и удалён или чужой) - новое уведомление с новым id
+        let replaces = replaces_id > 0 && self.is_known(replaces_id).await;
+        let id = if replaces {
             replaces_id
         } else {
-            NOTIFICATION_ID.fetch_add(1, Ordering::SeqCst)
+            next_notification_id()
         };
 
-        // Извлекаем urgency из hints
-        let urgency = hints
-            .get("urgency")
-            .and_then(|v| {
-                if let Ok(val) = v.downcast_ref::<u8>() {
-                    Some(val)
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(1);
+        let hints = NotificationHints::parse(&hints);
+
+        // image-path с именем иконки важнее app_icon, иконка из .desktop - запасной вариант
+        let icon = hints
+            .image_icon
+            .clone()
+            .or_else(|| Some(app_icon).filter(|icon| !icon.is_empty()))
+            .or_else(|| hints.desktop_entry.as_deref().and_then(desktop_entry_icon))
+            .unwrap_or_default();
 
         // Парсим actions (формат: [action_id, label, action_id, label, ...])
         let parsed_actions: Vec<(String, String)> = actions
@@ -126,27 +218,37 @@ impl NotificationDbusService {
             })
             .collect();
 
-        let notification = Notification::new(
+        let mut notification = Notification::new(
             id,
             app_name,
             summary,
             body,
-            app_icon,
-            NotificationUrgency::from(urgency),
+            icon,
+            NotificationUrgency::from(hints.urgency),
             parsed_actions,
             expire_timeout,
         );
+        notification.image = hints.image.clone();
+        notification.desktop_entry = hints.desktop_entry.clone();
+        notification.category = hints.category.clone();
+
+        // Правила приложений и режим DND
+        let decision = self.rules.lock().await.apply(&mut notification);
 
         // Сохраняем в БД
-        {
+        if decision.history {
             let mut repo = self.repository.lock().await;
-            if let Err(e) = repo.save(&notification) {
+            if let Err(e) = repo.save(&notification, replaces) {
                 eprintln!("[NotificationService] Failed to save notification: {}", e);
             }
         }
 
         // Отправляем в UI для показа popup
-        let _ = self.notification_tx.send(notification).await;
+        if decision.popup {
+            self.on_screen.lock().await.insert(id);
+            hints.play_sound();
+            let _ = self.notification_tx.send(notification).await;
+        }
 
         // Отправляем сигнал об изменении количества
         self.emit_count_changed(&ctxt).await;
@@ -166,8 +268,12 @@ impl NotificationDbusService {
             let _ = repo.delete(id);
         }
 
+        // Закрываем popup, если он ещё на экране
+        self.on_screen.lock().await.remove(&id);
+        let _ = self.ui_tx.send(UiEvent::ClosePopup(id)).await;
+
         // Отправляем сигнал о закрытии
-        Self::notification_closed(&ctxt, id, 3).await?; // 3 = closed by CloseNotification
+        Self::notification_closed(&ctxt, id, REASON_CLOSED).await?;
 
         // Отправляем сигнал об изменении количества
         self.emit_count_changed(&ctxt).await;
@@ -191,6 +297,14 @@ impl NotificationDbusService {
         action_key: String,
     ) -> zbus::Result<()>;
 
+    /// Сигнал: токен xdg-activation для действия (отправляется перед ActionInvoked)
+    #[zbus(signal)]
+    async fn activation_token(
+        ctxt: &SignalContext<'_>,
+        id: u32,
+        activation_token: String,
+    ) -> zbus::Result<()>;
+
     // === Кастомные методы для hyprline ===
 
     /// Получить количество уведомлений
@@ -202,11 +316,34 @@ impl NotificationDbusService {
     /// Получить историю уведомлений (JSON)
     async fn get_history(&self) -> String {
         let repo = self.repository.lock().await;
-        let notifications = repo.get_all().unwrap_or_default();
-        let data: Vec<NotificationData> = notifications.iter().map(|n| n.into()).collect();
+        let data = repo.get_all().unwrap_or_default();
         serde_json::to_string(&data).unwrap_or_else(|_| "[]".to_string())
     }
 
+    /// Поиск по истории (JSON)
+    ///
+    /// `urgency` -1 - любая, `since` 0 - за всё время (Unix timestamp).
+    async fn search_history(&self, query: String, urgency: i32, since: i64) -> String {
+        let filter = HistoryFilter {
+            query,
+            urgency: u8::try_from(urgency).ok().map(NotificationUrgency::from),
+            since: (since > 0).then_some(since),
+        };
+
+        let repo = self.repository.lock().await;
+        let data = repo.search(&filter).unwrap_or_else(|e| {
+            eprintln!("[NotificationService] ✗ Search failed: {}", e);
+            Vec::new()
+        });
+        serde_json::to_string(&data).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Получить картинку уведомления (PNG, пусто если её нет)
+    async fn get_image(&self, id: u32) -> Vec<u8> {
+        let repo = self.repository.lock().await;
+        repo.get_image(id).ok().flatten().unwrap_or_default()
+    }
+
     /// Удалить уведомление по ID
     async fn delete_notification(
         &self,
@@ -244,6 +381,54 @@ impl NotificationDbusService {
         deleted
     }
 
+    /// Включён ли DND вручную
+    async fn get_dnd_enabled(&self) -> bool {
+        self.rules.lock().await.dnd_enabled()
+    }
+
+    /// Действует ли DND сейчас (вручную или по расписанию)
+    async fn is_dnd_active(&self) -> bool {
+        self.rules.lock().await.is_dnd_active()
+    }
+
+    /// Включить/выключить DND
+    async fn set_dnd_enabled(
+        &self,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+        enabled: bool,
+    ) -> zbus::fdo::Result<()> {
+        let changed = {
+            let mut rules = self.rules.lock().await;
+            rules.set_dnd_enabled(enabled).map_err(zbus::fdo::Error::Failed)?;
+            rules.check_dnd_changed()
+        };
+
+        if let Some(active) = changed {
+            Self::dnd_changed(&ctxt, active).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Удалить историю приложения
+    async fn clear_app(
+        &self,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+        app_name: String,
+    ) -> u32 {
+        let deleted = {
+            let mut repo = self.repository.lock().await;
+            repo.clear_app(&app_name).unwrap_or(0) as u32
+        };
+
+        if deleted > 0 {
+            // Отправляем сигнал об изменении количества
+            self.emit_count_changed(&ctxt).await;
+        }
+
+        deleted
+    }
+
     /// Показать окно истории
     async fn show_history_window(&self) {
         let _ = self.ui_tx.send(UiEvent::ShowHistory).await;
@@ -260,5 +445,12 @@ impl NotificationDbusService {
         ctxt: &SignalContext<'_>,
         count: u32,
     ) -> zbus::Result<()>;
+
+    /// Сигнал: режим «Не беспокоить» включился или выключился
+    #[zbus(signal)]
+    async fn dnd_changed(
+        ctxt: &SignalContext<'_>,
+        active: bool,
+    ) -> zbus::Result<()>;
 }
 
@@ -0,0 +1,208 @@
+use gtk4::gdk_pixbuf::{Colorspace, InterpType, Pixbuf};
+use gtk4::glib;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use zbus::zvariant::OwnedValue;
+
+/// Максимальный размер стороны сохраняемой картинки (px)
+const THUMBNAIL_SIZE: i32 = 128;
+
+/// Подсказки (hints) уведомления, которые понимает сервер
+#[derive(Debug, Default)]
+pub struct NotificationHints {
+    pub urgency: u8,
+    /// Миниатюра картинки (PNG) из `image-data` или `image-path`
+    pub image: Option<Vec<u8>>,
+    /// Имя иконки из `image-path`, если там не путь к файлу
+    pub image_icon: Option<String>,
+    pub desktop_entry: Option<String>,
+    pub category: Option<String>,
+    pub sound_file: Option<String>,
+    pub sound_name: Option<String>,
+    pub suppress_sound: bool,
+}
+
+impl NotificationHints {
+    pub fn parse(hints: &HashMap<String, OwnedValue>) -> Self {
+        let string = |key: &str| {
+            hints
+                .get(key)
+                .and_then(|v| v.downcast_ref::<&str>().ok())
+                .map(|s| s.to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        // Старые версии спецификации называли поля иначе
+        let image_data = ["image-data", "image_data", "icon_data"]
+            .iter()
+            .find_map(|key| hints.get(*key));
+        let image_path = string("image-path").or_else(|| string("image_path"));
+
+        let mut image = image_data.and_then(decode_image_data);
+        let mut image_icon = None;
+        if image.is_none() {
+            if let Some(path) = image_path {
+                match path.strip_prefix("file://") {
+                    Some(path) => image = load_image_file(path),
+                    None if path.starts_with('/') => image = load_image_file(&path),
+                    None => image_icon = Some(path),
+                }
+            }
+        }
+
+        Self {
+            urgency: hints
+                .get("urgency")
+                .and_then(|v| v.downcast_ref::<u8>().ok())
+                .unwrap_or(1),
+            image,
+            image_icon,
+            desktop_entry: string("desktop-entry"),
+            category: string("category"),
+            sound_file: string("sound-file"),
+            sound_name: string("sound-name"),
+            suppress_sound: hints
+                .get("suppress-sound")
+                .and_then(|v| v.downcast_ref::<bool>().ok())
+                .unwrap_or(false),
+        }
+    }
+
+    /// Проигрывает звук уведомления, если приложение его передало
+    pub fn play_sound(&self) {
+        if self.suppress_sound {
+            return;
+        }
+
+        // Пробуем плееры по очереди, пока какой-нибудь не запустится
+        let players: Vec<(&str, Vec<String>)> = match (&self.sound_file, &self.sound_name) {
+            (Some(file), _) => {
+                let file = file.strip_prefix("file://").unwrap_or(file).to_string();
+                vec![("pw-play", vec![file.clone()]), ("paplay", vec![file])]
+            }
+            (None, Some(name)) => vec![("canberra-gtk-play", vec!["-i".to_string(), name.clone()])],
+            (None, None) => return,
+        };
+
+        std::thread::spawn(move || {
+            for (program, args) in players {
+                if let Ok(mut child) = std::process::Command::new(program).args(&args).spawn() {
+                    let _ = child.wait();
+                    return;
+                }
+            }
+            eprintln!("[NotificationService] ✗ No sound player found (pw-play, paplay or canberra-gtk-play)");
+        });
+    }
+}
+
+/// Разбирает `image-data` (iiibiiay) и сохраняет миниатюру в PNG
+fn decode_image_data(value: &OwnedValue) -> Option<Vec<u8>> {
+    let (width, height, rowstride, has_alpha, bits_per_sample, channels, data): (
+        i32,
+        i32,
+        i32,
+        bool,
+        i32,
+        i32,
+        Vec<u8>,
+    ) = value.try_clone().ok()?.try_into().ok()?;
+
+    if width <= 0 || height <= 0 || bits_per_sample != 8 || channels != if has_alpha { 4 } else { 3 } {
+        eprintln!("[NotificationService] ✗ Unsupported image-data format");
+        return None;
+    }
+
+    if rowstride <= 0 {
+        eprintln!("[NotificationService] ✗ Invalid image-data rowstride {}", rowstride);
+        return None;
+    }
+
+    // Последняя строка может быть без выравнивания до rowstride;
+    // размеры приходят от клиента, поэтому переполнение - тоже ошибка
+    let row_bytes = width.checked_mul(channels);
+    let required = row_bytes.and_then(|row_bytes| {
+        (rowstride as usize)
+            .checked_mul(height as usize - 1)?
+            .checked_add(row_bytes as usize)
+    });
+    let (Some(row_bytes), Some(required)) = (row_bytes, required) else {
+        eprintln!("[NotificationService] ✗ image-data size overflows");
+        return None;
+    };
+    if rowstride < row_bytes || data.len() < required {
+        eprintln!("[NotificationService] ✗ Truncated image-data");
+        return None;
+    }
+
+    let pixbuf = Pixbuf::from_bytes(
+        &glib::Bytes::from_owned(data),
+        Colorspace::Rgb,
+        has_alpha,
+        bits_per_sample,
+        width,
+        height,
+        rowstride,
+    );
+    encode_thumbnail(&pixbuf)
+}
+
+/// Загружает картинку из файла (`image-path`)
+fn load_image_file(path: &str) -> Option<Vec<u8>> {
+    match Pixbuf::from_file(path) {
+        Ok(pixbuf) => encode_thumbnail(&pixbuf),
+        Err(e) => {
+            eprintln!("[NotificationService] ✗ Failed to load image {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Уменьшает картинку до THUMBNAIL_SIZE и кодирует в PNG
+fn encode_thumbnail(pixbuf: &Pixbuf) -> Option<Vec<u8>> {
+    let (width, height) = (pixbuf.width(), pixbuf.height());
+    let scale = THUMBNAIL_SIZE as f64 / width.max(height) as f64;
+
+    let thumbnail = if scale < 1.0 {
+        let width = ((width as f64 * scale).round() as i32).max(1);
+        let height = ((height as f64 * scale).round() as i32).max(1);
+        pixbuf.scale_simple(width, height, InterpType::Bilinear)?
+    } else {
+        pixbuf.clone()
+    };
+
+    thumbnail
+        .save_to_bufferv("png", &[])
+        .inspect_err(|e| eprintln!("[NotificationService] ✗ Failed to encode image: {}", e))
+        .ok()
+}
+
+/// Иконка приложения из его .desktop файла (`desktop-entry`)
+pub fn desktop_entry_icon(desktop_entry: &str) -> Option<String> {
+    let file_name = format!("{}.desktop", desktop_entry.trim_end_matches(".desktop"));
+
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".local/share")
+        });
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+
+    std::iter::once(data_home)
+        .chain(data_dirs.split(':').filter(|dir| !dir.is_empty()).map(PathBuf::from))
+        .map(|dir| dir.join("applications").join(&file_name))
+        .find_map(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            // Берём Icon= только из основной секции
+            content
+                .lines()
+                .skip_while(|line| line.trim() != "[Desktop Entry]")
+                .skip(1)
+                .take_while(|line| !line.starts_with('['))
+                .find_map(|line| line.strip_prefix("Icon="))
+                .map(|icon| icon.trim().to_string())
+                .filter(|icon| !icon.is_empty())
+        })
+}
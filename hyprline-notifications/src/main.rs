@@ -1,6 +1,8 @@
 mod dbus_service;
+mod hints;
 mod notification;
 mod repository;
+mod rules;
 mod ui;
 
 use gtk4::glib;
@@ -66,36 +68,19 @@ fn setup_service(app: &gtk4::Application) {
     // Запускаем D-Bus сервис в отдельном потоке
     let repo_for_dbus = repository.clone();
     let ui_tx_for_dbus = ui_tx.clone();
-    let popup_event_rx_clone = popup_event_rx.clone();
 
     std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async {
-            // Запускаем обработчик popup событий для отправки D-Bus сигналов
-            let popup_handler = tokio::spawn(async move {
-                while let Ok(event) = popup_event_rx_clone.recv().await {
-                    match event {
-                        PopupEvent::ActionInvoked { id, action_key } => {
-                            eprintln!("[NotificationService] Action invoked: id={}, key={}", id, action_key);
-                            // Отправляем D-Bus сигнал ActionInvoked
-                            // (это будет обработано в dbus_service)
-                        }
-                        PopupEvent::Dismissed { id } => {
-                            eprintln!("[NotificationService] Notification dismissed: id={}", id);
-                        }
-                    }
-                }
-            });
-
+            // Сервис сам отправляет D-Bus сигналы по событиям popup
             if let Err(e) = NotificationDbusService::start(
                 repo_for_dbus,
                 notification_tx,
                 ui_tx_for_dbus,
+                popup_event_rx,
             ).await {
                 eprintln!("[NotificationService] Failed to start D-Bus service: {}", e);
             }
-
-            popup_handler.abort();
         });
     });
 
@@ -123,6 +108,9 @@ fn setup_service(app: &gtk4::Application) {
                 UiEvent::HideHistory => {
                     eprintln!("[UI] Hide history requested");
                 }
+                UiEvent::ClosePopup(id) => {
+                    ui::popup::close_notification_popup(id);
+                }
             }
         }
         glib::ControlFlow::Continue
@@ -137,5 +125,7 @@ fn setup_service(app: &gtk4::Application) {
 pub enum UiEvent {
     ShowHistory,
     HideHistory,
+    /// Закрыть popup уведомления (CloseNotification)
+    ClosePopup(u32),
 }
 
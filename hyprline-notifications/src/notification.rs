@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 
 /// Уровень важности уведомления
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum NotificationUrgency {
     Low = 0,
     Normal = 1,
@@ -31,6 +32,10 @@ pub struct Notification {
     pub timestamp: DateTime<Utc>,
     pub actions: Vec<(String, String)>, // (action_id, label)
     pub expire_timeout: i32, // -1 = default, 0 = never expire, >0 = milliseconds
+    /// Миниатюра картинки уведомления (PNG)
+    pub image: Option<Vec<u8>>,
+    pub desktop_entry: Option<String>,
+    pub category: Option<String>,
 }
 
 impl Notification {
@@ -54,6 +59,9 @@ impl Notification {
             timestamp: Utc::now(),
             actions,
             expire_timeout,
+            image: None,
+            desktop_entry: None,
+            category: None,
         }
     }
 }
@@ -68,6 +76,10 @@ pub struct NotificationData {
     pub icon: String,
     pub urgency: u8,
     pub timestamp: i64, // Unix timestamp
+    /// Картинку можно получить через GetImage
+    pub has_image: bool,
+    pub desktop_entry: Option<String>,
+    pub category: Option<String>,
 }
 
 impl From<&Notification> for NotificationData {
@@ -80,6 +92,9 @@ impl From<&Notification> for NotificationData {
             icon: n.icon.clone(),
             urgency: n.urgency as u8,
             timestamp: n.timestamp.timestamp(),
+            has_image: n.image.is_some(),
+            desktop_entry: n.desktop_entry.clone(),
+            category: n.category.clone(),
         }
     }
 }
@@ -1,7 +1,68 @@
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params, params_from_iter};
+use rusqlite::types::Value;
 use std::path::PathBuf;
-use crate::notification::{Notification, NotificationUrgency};
-use chrono::{DateTime, Utc, TimeZone};
+use crate::notification::{Notification, NotificationData, NotificationUrgency};
+
+/// Схема таблицы истории
+///
+/// `row_id` - ключ строки истории, `id` - id уведомления в D-Bus. Замена
+/// уведомления (replaces_id) обновляет последнюю строку с этим id.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS notifications (
+        row_id INTEGER PRIMARY KEY AUTOINCREMENT,
+        id INTEGER NOT NULL,
+        app_name TEXT NOT NULL,
+        summary TEXT NOT NULL,
+        body TEXT NOT NULL,
+        icon TEXT NOT NULL,
+        urgency INTEGER NOT NULL,
+        timestamp INTEGER NOT NULL,
+        actions TEXT NOT NULL,
+        image BLOB,
+        desktop_entry TEXT,
+        category TEXT
+    );
+    CREATE INDEX IF NOT EXISTS notifications_id ON notifications (id);
+";
+
+/// Полнотекстовый индекс по заголовку и тексту (FTS5), обновляется триггерами
+const SEARCH_SCHEMA: &str = "
+    CREATE VIRTUAL TABLE notifications_fts USING fts5(
+        summary, body, content = 'notifications', content_rowid = 'row_id'
+    );
+    CREATE TRIGGER notifications_fts_insert AFTER INSERT ON notifications BEGIN
+        INSERT INTO notifications_fts (rowid, summary, body) VALUES (new.row_id, new.summary, new.body);
+    END;
+    CREATE TRIGGER notifications_fts_delete AFTER DELETE ON notifications BEGIN
+        INSERT INTO notifications_fts (notifications_fts, rowid, summary, body)
+            VALUES ('delete', old.row_id, old.summary, old.body);
+    END;
+    CREATE TRIGGER notifications_fts_update AFTER UPDATE ON notifications BEGIN
+        INSERT INTO notifications_fts (notifications_fts, rowid, summary, body)
+            VALUES ('delete', old.row_id, old.summary, old.body);
+        INSERT INTO notifications_fts (rowid, summary, body) VALUES (new.row_id, new.summary, new.body);
+    END;
+    INSERT INTO notifications_fts (notifications_fts) VALUES ('rebuild');
+";
+
+/// Колонки, общие для старой и новой схемы
+const COLUMNS: &str =
+    "id, app_name, summary, body, icon, urgency, timestamp, actions, image, desktop_entry, category";
+
+/// Колонки для списка истории: вместо картинки только признак её наличия
+/// (сама картинка загружается отдельно через `get_image`)
+const LIST_COLUMNS: &str =
+    "id, app_name, summary, body, icon, urgency, timestamp, image IS NOT NULL, desktop_entry, category";
+
+/// Фильтр истории; пустые поля не ограничивают выборку
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Поиск по заголовку и тексту
+    pub query: String,
+    pub urgency: Option<NotificationUrgency>,
+    /// Unix timestamp, не раньше которого пришло уведомление
+    pub since: Option<i64>,
+}
 
 pub struct NotificationRepository {
     conn: Connection,
@@ -19,25 +80,84 @@ impl NotificationRepository {
         let conn = Connection::open(&db_path)?;
 
         // Создаём таблицу
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS notifications (
-                id INTEGER PRIMARY KEY,
-                app_name TEXT NOT NULL,
-                summary TEXT NOT NULL,
-                body TEXT NOT NULL,
-                icon TEXT NOT NULL,
-                urgency INTEGER NOT NULL,
-                timestamp INTEGER NOT NULL,
-                actions TEXT NOT NULL
-            )",
-            [],
-        )?;
+        conn.execute_batch(SCHEMA)?;
+        Self::migrate(&conn)?;
+        Self::init_search(&conn)?;
 
         eprintln!("[NotificationRepository] ✓ Database initialized at {:?}", db_path);
 
         Ok(Self { conn })
     }
 
+    /// Приводит старые базы к текущей схеме
+    fn migrate(conn: &Connection) -> Result<(), rusqlite::Error> {
+        let mut stmt = conn.prepare("PRAGMA table_info(notifications)")?;
+        let columns = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (name, kind) in [("image", "BLOB"), ("desktop_entry", "TEXT"), ("category", "TEXT")] {
+            if !columns.iter().any(|column| column == name) {
+                conn.execute(&format!("ALTER TABLE notifications ADD COLUMN {} {}", name, kind), [])?;
+                eprintln!("[NotificationRepository] ✓ Added column {}", name);
+            }
+        }
+
+        // Раньше ключом был id уведомления - пересоздаём таблицу с row_id
+        if !columns.iter().any(|column| column == "row_id") {
+            conn.execute_batch(&format!(
+                "BEGIN;
+                 DROP INDEX IF EXISTS notifications_id;
+                 ALTER TABLE notifications RENAME TO notifications_old;
+                 {SCHEMA}
+                 INSERT INTO notifications ({COLUMNS})
+                     SELECT {COLUMNS} FROM notifications_old ORDER BY timestamp;
+                 DROP TABLE notifications_old;
+                 COMMIT;"
+            ))?;
+            eprintln!("[NotificationRepository] ✓ Migrated history to row_id keys");
+        }
+
+        Ok(())
+    }
+
+    /// Создаёт индекс поиска и заполняет его существующей историей
+    fn init_search(conn: &Connection) -> Result<(), rusqlite::Error> {
+        let exists = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE name = 'notifications_fts'",
+                [],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+
+        if !exists {
+            conn.execute_batch(&format!("BEGIN; {SEARCH_SCHEMA} COMMIT;"))?;
+            eprintln!("[NotificationRepository] ✓ Built full-text search index");
+        }
+
+        Ok(())
+    }
+
+    /// Наибольший id в истории, чтобы новые id не совпадали со старыми
+    pub fn max_id(&self) -> Result<u32, rusqlite::Error> {
+        self.conn.query_row(
+            "SELECT COALESCE(MAX(id), 0) FROM notifications",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    /// Есть ли в истории уведомление с этим id
+    pub fn exists(&self, id: u32) -> Result<bool, rusqlite::Error> {
+        self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM notifications WHERE id = ?1)",
+            params![id],
+            |row| row.get(0),
+        )
+    }
+
     fn get_db_path() -> PathBuf {
         let data_dir = std::env::var("XDG_DATA_HOME")
             .map(PathBuf::from)
@@ -49,56 +169,113 @@ impl NotificationRepository {
         data_dir.join("hyprline-notifications/notifications.db")
     }
 
-    pub fn save(&mut self, notification: &Notification) -> Result<(), rusqlite::Error> {
+    /// Сохраняет уведомление
+    ///
+    /// При `replaces` (замена через replaces_id) обновляется последняя строка с тем же id,
+    /// иначе всегда добавляется новая строка.
+    pub fn save(&mut self, notification: &Notification, replaces: bool) -> Result<(), rusqlite::Error> {
         let actions_json = serde_json::to_string(&notification.actions).unwrap_or_default();
+        let urgency = notification.urgency as u8;
+        let timestamp = notification.timestamp.timestamp();
+        let values = params![
+            notification.id,
+            notification.app_name,
+            notification.summary,
+            notification.body,
+            notification.icon,
+            urgency,
+            timestamp,
+            actions_json,
+            notification.image,
+            notification.desktop_entry,
+            notification.category,
+        ];
+
+        if replaces {
+            let updated = self.conn.execute(
+                "UPDATE notifications
+                 SET app_name = ?2, summary = ?3, body = ?4, icon = ?5, urgency = ?6, timestamp = ?7,
+                     actions = ?8, image = ?9, desktop_entry = ?10, category = ?11
+                 WHERE row_id = (SELECT MAX(row_id) FROM notifications WHERE id = ?1)",
+                values,
+            )?;
+            if updated > 0 {
+                return Ok(());
+            }
+            // Заменяемое уведомление было только на экране, в истории его нет
+        }
 
         self.conn.execute(
-            "INSERT OR REPLACE INTO notifications (id, app_name, summary, body, icon, urgency, timestamp, actions)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-            params![
-                notification.id,
-                notification.app_name,
-                notification.summary,
-                notification.body,
-                notification.icon,
-                notification.urgency as u8,
-                notification.timestamp.timestamp(),
-                actions_json,
-            ],
+            &format!("INSERT INTO notifications ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"),
+            values,
         )?;
 
         Ok(())
     }
 
-    pub fn get_all(&self) -> Result<Vec<Notification>, rusqlite::Error> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, app_name, summary, body, icon, urgency, timestamp, actions
-             FROM notifications
-             ORDER BY timestamp DESC"
-        )?;
+    pub fn get_all(&self) -> Result<Vec<NotificationData>, rusqlite::Error> {
+        self.search(&HistoryFilter::default())
+    }
+
+    /// История с фильтрами, новые сверху
+    pub fn search(&self, filter: &HistoryFilter) -> Result<Vec<NotificationData>, rusqlite::Error> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        let query = fts_query(&filter.query);
+        if !query.is_empty() {
+            conditions.push("row_id IN (SELECT rowid FROM notifications_fts WHERE notifications_fts MATCH ?)");
+            values.push(Value::Text(query));
+        }
+        if let Some(urgency) = filter.urgency {
+            conditions.push("urgency = ?");
+            values.push(Value::Integer(urgency as i64));
+        }
+        if let Some(since) = filter.since {
+            conditions.push("timestamp >= ?");
+            values.push(Value::Integer(since));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
 
-        let notifications = stmt.query_map([], |row| {
-            let actions_json: String = row.get(7)?;
-            let actions: Vec<(String, String)> = serde_json::from_str(&actions_json).unwrap_or_default();
-            let timestamp_secs: i64 = row.get(6)?;
-            let urgency_val: u8 = row.get(5)?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {LIST_COLUMNS} FROM notifications {where_clause} ORDER BY timestamp DESC, row_id DESC"
+        ))?;
 
-            Ok(Notification {
+        let notifications = stmt.query_map(params_from_iter(values), |row| {
+            Ok(NotificationData {
                 id: row.get(0)?,
                 app_name: row.get(1)?,
                 summary: row.get(2)?,
                 body: row.get(3)?,
                 icon: row.get(4)?,
-                urgency: NotificationUrgency::from(urgency_val),
-                timestamp: Utc.timestamp_opt(timestamp_secs, 0).unwrap(),
-                actions,
-                expire_timeout: -1,
+                urgency: row.get(5)?,
+                timestamp: row.get(6)?,
+                has_image: row.get(7)?,
+                desktop_entry: row.get(8)?,
+                category: row.get(9)?,
             })
         })?;
 
         notifications.collect()
     }
 
+    /// Картинка уведомления (PNG)
+    pub fn get_image(&self, id: u32) -> Result<Option<Vec<u8>>, rusqlite::Error> {
+        self.conn
+            .query_row(
+                "SELECT image FROM notifications WHERE id = ?1 ORDER BY row_id DESC LIMIT 1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map(Option::flatten)
+    }
+
     pub fn get_count(&self) -> Result<usize, rusqlite::Error> {
         let count: i64 = self.conn.query_row(
             "SELECT COUNT(*) FROM notifications",
@@ -120,5 +297,23 @@ impl NotificationRepository {
         let affected = self.conn.execute("DELETE FROM notifications", [])?;
         Ok(affected)
     }
+
+    /// Удаляет всю историю приложения
+    pub fn clear_app(&mut self, app_name: &str) -> Result<usize, rusqlite::Error> {
+        let affected = self.conn.execute(
+            "DELETE FROM notifications WHERE app_name = ?1",
+            params![app_name],
+        )?;
+        Ok(affected)
+    }
+}
+
+/// Превращает ввод пользователя в запрос FTS5: каждое слово ищется как префикс
+fn fts_query(input: &str) -> String {
+    input
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
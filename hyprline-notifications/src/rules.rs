@@ -0,0 +1,252 @@
+use chrono::{DateTime, Datelike, Local, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::notification::{Notification, NotificationUrgency};
+
+/// Интервал «Не беспокоить» по времени
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DndSchedule {
+    /// Начало, "HH:MM"
+    pub start: String,
+    /// Конец, "HH:MM"; если раньше начала - интервал через полночь
+    pub end: String,
+    /// Дни начала интервала ("mon", "tue", ...); пусто - каждый день
+    #[serde(default)]
+    pub days: Vec<String>,
+}
+
+impl DndSchedule {
+    fn contains(&self, now: DateTime<Local>) -> bool {
+        let (Ok(start), Ok(end)) = (
+            NaiveTime::parse_from_str(&self.start, "%H:%M"),
+            NaiveTime::parse_from_str(&self.end, "%H:%M"),
+        ) else {
+            return false;
+        };
+
+        let time = now.time();
+        let today = now.weekday();
+        if start <= end {
+            start <= time && time < end && self.on_day(today)
+        } else if time >= start {
+            self.on_day(today)
+        } else {
+            // Утренняя часть интервала, начавшегося вчера
+            time < end && self.on_day(today.pred())
+        }
+    }
+
+    fn on_day(&self, day: Weekday) -> bool {
+        self.days.is_empty()
+            || self
+                .days
+                .iter()
+                .any(|d| d.parse::<Weekday>().is_ok_and(|d| d == day))
+    }
+}
+
+/// Настройки режима «Не беспокоить»
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DndConfig {
+    /// Включён вручную (из панели или по D-Bus)
+    pub enabled: bool,
+    /// Показывать критические уведомления даже в режиме DND
+    pub allow_critical: bool,
+    pub schedules: Vec<DndSchedule>,
+}
+
+impl Default for DndConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allow_critical: true,
+            schedules: Vec::new(),
+        }
+    }
+}
+
+/// Правило для уведомлений приложения
+///
+/// Условия сравниваются без учёта регистра; пустое условие подходит всем.
+/// Подходящие правила применяются по порядку, последующие переопределяют предыдущие.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppRule {
+    pub app_name: Option<String>,
+    pub desktop_entry: Option<String>,
+    pub category: Option<String>,
+    /// `false` - не показывать popup
+    pub popup: Option<bool>,
+    /// `false` - не сохранять в историю
+    pub history: Option<bool>,
+    pub urgency: Option<NotificationUrgency>,
+    /// Время показа popup, мс (0 - не закрывать)
+    pub timeout: Option<i32>,
+}
+
+impl AppRule {
+    fn matches(&self, notification: &Notification) -> bool {
+        let check = |pattern: &Option<String>, value: Option<&str>| match pattern {
+            Some(pattern) => value.is_some_and(|value| value.eq_ignore_ascii_case(pattern)),
+            None => true,
+        };
+
+        check(&self.app_name, Some(&notification.app_name))
+            && check(&self.desktop_entry, notification.desktop_entry.as_deref())
+            && check(&self.category, notification.category.as_deref())
+    }
+}
+
+/// Файл правил (`~/.config/hyprline/notifications.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RulesFile {
+    pub dnd: DndConfig,
+    pub rules: Vec<AppRule>,
+}
+
+/// Что делать с уведомлением после применения правил
+#[derive(Debug, Clone, Copy)]
+pub struct RuleDecision {
+    pub popup: bool,
+    pub history: bool,
+}
+
+/// Правила и режим DND; файл перечитывается, если изменился
+pub struct NotificationRules {
+    file: RulesFile,
+    modified: Option<SystemTime>,
+    /// Последнее известное состояние DND, чтобы сигналить только об изменениях
+    dnd_active: bool,
+}
+
+impl NotificationRules {
+    pub fn new() -> Self {
+        let mut rules = Self {
+            file: RulesFile::default(),
+            modified: None,
+            dnd_active: false,
+        };
+        rules.reload_if_changed();
+        rules.dnd_active = rules.is_dnd_active();
+        rules
+    }
+
+    pub fn rules_path() -> PathBuf {
+        let config_dir = std::env::var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+                PathBuf::from(home).join(".config")
+            });
+
+        config_dir.join("hyprline/notifications.json")
+    }
+
+    fn reload_if_changed(&mut self) {
+        let path = Self::rules_path();
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        if modified.is_none() {
+            self.file = RulesFile::default();
+            return;
+        }
+
+        match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<RulesFile>(&content).map_err(|e| e.to_string()))
+        {
+            Ok(file) => {
+                eprintln!(
+                    "[NotificationRules] ✓ Loaded {} rule(s), {} DND schedule(s)",
+                    file.rules.len(),
+                    file.dnd.schedules.len()
+                );
+                self.file = file;
+            }
+            // Оставляем прежние правила, пока файл не исправят
+            Err(e) => eprintln!("[NotificationRules] ✗ Failed to load {:?}: {}", path, e),
+        }
+    }
+
+    /// DND включён вручную
+    pub fn dnd_enabled(&self) -> bool {
+        self.file.dnd.enabled
+    }
+
+    /// DND действует сейчас: включён вручную или по расписанию
+    pub fn is_dnd_active(&self) -> bool {
+        let now = Local::now();
+        self.file.dnd.enabled || self.file.dnd.schedules.iter().any(|s| s.contains(now))
+    }
+
+    /// Включает/выключает DND и сохраняет в файл правил
+    pub fn set_dnd_enabled(&mut self, enabled: bool) -> Result<(), String> {
+        self.reload_if_changed();
+        self.file.dnd.enabled = enabled;
+
+        let path = Self::rules_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        let json = serde_json::to_string_pretty(&self.file)
+            .map_err(|e| format!("Failed to serialize rules: {}", e))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        self.modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+
+        eprintln!("[NotificationRules] ✓ Do not disturb {}", if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    /// Перечитывает файл и возвращает новое состояние DND, если оно изменилось
+    pub fn check_dnd_changed(&mut self) -> Option<bool> {
+        self.reload_if_changed();
+        let active = self.is_dnd_active();
+        if active == self.dnd_active {
+            return None;
+        }
+        self.dnd_active = active;
+        Some(active)
+    }
+
+    /// Применяет правила к уведомлению и решает, показывать ли popup
+    pub fn apply(&mut self, notification: &mut Notification) -> RuleDecision {
+        self.reload_if_changed();
+
+        let mut decision = RuleDecision {
+            popup: true,
+            history: true,
+        };
+
+        for rule in self.file.rules.iter().filter(|rule| rule.matches(notification)) {
+            if let Some(popup) = rule.popup {
+                decision.popup = popup;
+            }
+            if let Some(history) = rule.history {
+                decision.history = history;
+            }
+            if let Some(urgency) = rule.urgency {
+                notification.urgency = urgency;
+            }
+            if let Some(timeout) = rule.timeout {
+                notification.expire_timeout = timeout;
+            }
+        }
+
+        // В режиме DND popup только у критических (если разрешено)
+        let critical_allowed =
+            self.file.dnd.allow_critical && notification.urgency == NotificationUrgency::Critical;
+        if self.is_dnd_active() && !critical_allowed {
+            decision.popup = false;
+        }
+
+        decision
+    }
+}
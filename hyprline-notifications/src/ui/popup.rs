@@ -10,8 +10,26 @@ use crate::notification::{Notification, NotificationUrgency};
 /// События от popup
 #[derive(Debug, Clone)]
 pub enum PopupEvent {
-    ActionInvoked { id: u32, action_key: String },
+    /// Нажата кнопка действия или само уведомление (`default`)
+    ActionInvoked {
+        id: u32,
+        action_key: String,
+        /// Токен xdg-activation, чтобы приложение могло получить фокус
+        activation_token: Option<String>,
+    },
+    /// Закрыто пользователем
     Dismissed { id: u32 },
+    /// Закрыто по таймауту
+    Expired { id: u32 },
+}
+
+impl PopupEvent {
+    /// Id уведомления, к которому относится событие
+    pub fn id(&self) -> u32 {
+        match self {
+            PopupEvent::ActionInvoked { id, .. } | PopupEvent::Dismissed { id } | PopupEvent::Expired { id } => *id,
+        }
+    }
 }
 
 /// Структура для отслеживания активных popup-уведомлений
@@ -53,6 +71,13 @@ impl PopupState {
         self.active_popups.push_back((popup, notification_id));
     }
 
+    fn find_popup(&self, notification_id: u32) -> Option<gtk4::Window> {
+        self.active_popups
+            .iter()
+            .find(|(_, id)| *id == notification_id)
+            .map(|(window, _)| window.clone())
+    }
+
     fn remove_popup(&mut self, popup: &gtk4::Window) {
         self.active_popups.retain(|(p, _)| p != popup);
 
@@ -68,12 +93,42 @@ thread_local! {
     static POPUP_STATE: RefCell<PopupState> = RefCell::new(PopupState::new());
 }
 
-/// Показывает popup-уведомление с поддержкой actions
-pub fn show_notification_popup(
-    app: &Application,
-    notification: Notification,
-    event_tx: Option<Sender<PopupEvent>>,
-) {
+/// Закрывает popup уведомления (вызван CloseNotification)
+pub fn close_notification_popup(notification_id: u32) {
+    let window = POPUP_STATE.with(|state| state.borrow().find_popup(notification_id));
+
+    if let Some(window) = window {
+        POPUP_STATE.with(|state| {
+            state.borrow_mut().remove_popup(&window);
+        });
+        window.close();
+    }
+}
+
+/// Закрывает popup и сообщает сервису причину
+fn close_popup(window: &gtk4::Window, event_tx: &Option<Sender<PopupEvent>>, event: PopupEvent) {
+    POPUP_STATE.with(|state| {
+        state.borrow_mut().remove_popup(window);
+    });
+    if let Some(tx) = event_tx {
+        let _ = tx.try_send(event);
+    }
+    window.close();
+}
+
+/// Запрашивает токен xdg-activation у композитора
+///
+/// Приложение передаёт его в `xdg_activation_v1.activate`, иначе композитор
+/// может не дать окну фокус после нажатия на действие.
+fn activation_token(window: &gtk4::Window) -> Option<String> {
+    let context = gtk4::prelude::WidgetExt::display(window).app_launch_context();
+    context
+        .startup_notify_id(None::<&gtk4::gio::AppInfo>, &[])
+        .map(|token| token.to_string())
+}
+
+/// Создаёт окно popup (layer shell, справа вверху)
+fn create_popup_window(app: &Application) -> gtk4::Window {
     let window = gtk4::Window::new();
     window.set_application(Some(app));
 
@@ -88,8 +143,25 @@ pub fn show_notification_popup(
     window.set_margin(Edge::Right, 10);
 
     window.add_css_class("notification-popup");
+    window
+}
+
+/// Показывает popup-уведомление с поддержкой actions
+///
+/// Если popup с таким id уже на экране (replaces_id), содержимое
+/// обновляется на месте, а таймаут начинается заново.
+pub fn show_notification_popup(
+    app: &Application,
+    notification: Notification,
+    event_tx: Option<Sender<PopupEvent>>,
+) {
+    let existing = POPUP_STATE.with(|state| state.borrow().find_popup(notification.id));
+    let is_new = existing.is_none();
+    let window = existing.unwrap_or_else(|| create_popup_window(app));
 
     // Добавляем класс в зависимости от urgency
+    window.remove_css_class("notification-popup-critical");
+    window.remove_css_class("notification-popup-low");
     match notification.urgency {
         NotificationUrgency::Critical => {
             window.add_css_class("notification-popup-critical");
@@ -118,7 +190,7 @@ pub fn show_notification_popup(
     icon_box.set_margin_top(12);
     icon_box.set_margin_bottom(12);
 
-    let icon_widget = create_icon_widget(&notification.icon, &notification.app_name);
+    let icon_widget = create_icon_widget(&notification);
     icon_box.append(&icon_widget);
     main_container.append(&icon_box);
 
@@ -176,19 +248,32 @@ pub fn show_notification_popup(
     let event_tx_clone = event_tx.clone();
     close_button.connect_clicked(move |_| {
         if let Some(win) = window_weak.upgrade() {
-            POPUP_STATE.with(|state| {
-                state.borrow_mut().remove_popup(&win);
-            });
-            // Отправляем событие о dismiss
-            if let Some(ref tx) = event_tx_clone {
-                let _ = tx.try_send(PopupEvent::Dismissed { id: notification_id });
-            }
-            win.close();
+            close_popup(&win, &event_tx_clone, PopupEvent::Dismissed { id: notification_id });
         }
     });
     close_box.append(&close_button);
     main_container.append(&close_box);
 
+    // Клик по уведомлению - действие "default", если приложение его передало
+    if notification.actions.iter().any(|(key, _)| key == "default") {
+        main_container.set_cursor_from_name(Some("pointer"));
+
+        let gesture = gtk4::GestureClick::new();
+        let window_weak = window.downgrade();
+        let event_tx_clone = event_tx.clone();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Some(win) = window_weak.upgrade() {
+                let event = PopupEvent::ActionInvoked {
+                    id: notification_id,
+                    action_key: "default".to_string(),
+                    activation_token: activation_token(&win),
+                };
+                close_popup(&win, &event_tx_clone, event);
+            }
+        });
+        main_container.add_controller(gesture);
+    }
+
     outer_box.append(&main_container);
 
     // === Кнопки действий (actions) ===
@@ -201,7 +286,7 @@ pub fn show_notification_popup(
         actions_box.set_margin_bottom(12);
 
         for (action_key, action_label) in &notification.actions {
-            // Пропускаем "default" action - он обычно для клика по уведомлению
+            // "default" вызывается кликом по уведомлению
             if action_key == "default" {
                 continue;
             }
@@ -214,17 +299,12 @@ pub fn show_notification_popup(
             let action_key_clone = action_key.clone();
             button.connect_clicked(move |_| {
                 if let Some(win) = window_weak.upgrade() {
-                    // Отправляем событие о action
-                    if let Some(ref tx) = event_tx_clone {
-                        let _ = tx.try_send(PopupEvent::ActionInvoked {
-                            id: notification_id,
-                            action_key: action_key_clone.clone()
-                        });
-                    }
-                    POPUP_STATE.with(|state| {
-                        state.borrow_mut().remove_popup(&win);
-                    });
-                    win.close();
+                    let event = PopupEvent::ActionInvoked {
+                        id: notification_id,
+                        action_key: action_key_clone.clone(),
+                        activation_token: activation_token(&win),
+                    };
+                    close_popup(&win, &event_tx_clone, event);
                 }
             });
             actions_box.append(&button);
@@ -239,9 +319,11 @@ pub fn show_notification_popup(
     window.set_child(Some(&outer_box));
 
     // Добавляем в состояние
-    POPUP_STATE.with(|state| {
-        state.borrow_mut().add_popup(window.clone(), notification_id);
-    });
+    if is_new {
+        POPUP_STATE.with(|state| {
+            state.borrow_mut().add_popup(window.clone(), notification_id);
+        });
+    }
 
     window.present();
 
@@ -261,17 +343,12 @@ pub fn show_notification_popup(
 
     if timeout > 0 {
         let window_weak = window.downgrade();
+        // Если popup обновили, старое содержимое удалено и таймаут не срабатывает
+        let content_weak = outer_box.downgrade();
         let event_tx_clone = event_tx;
         glib::timeout_add_local_once(std::time::Duration::from_millis(timeout), move || {
-            if let Some(win) = window_weak.upgrade() {
-                POPUP_STATE.with(|state| {
-                    state.borrow_mut().remove_popup(&win);
-                });
-                // Отправляем событие о dismiss по таймауту
-                if let Some(ref tx) = event_tx_clone {
-                    let _ = tx.try_send(PopupEvent::Dismissed { id: notification_id });
-                }
-                win.close();
+            if let (Some(win), Some(_)) = (window_weak.upgrade(), content_weak.upgrade()) {
+                close_popup(&win, &event_tx_clone, PopupEvent::Expired { id: notification_id });
             }
         });
     }
@@ -343,7 +420,18 @@ fn html_to_pango(input: &str) -> String {
 }
 
 /// Создаёт виджет иконки для уведомления
-fn create_icon_widget(icon_name: &str, app_name: &str) -> gtk4::Widget {
+fn create_icon_widget(notification: &Notification) -> gtk4::Widget {
+    // Картинка из hints (image-data / image-path) важнее иконки приложения
+    if let Some(texture) = notification.image.as_deref().and_then(texture_from_png) {
+        let image = Image::from_paintable(Some(&texture));
+        image.set_pixel_size(48);
+        image.add_css_class("notification-popup-icon-image");
+        return image.upcast();
+    }
+
+    let icon_name = notification.icon.as_str();
+    let app_name = notification.app_name.as_str();
+
     // Если иконка указана, пробуем её загрузить
     if !icon_name.is_empty() {
         // Проверяем, это путь к файлу или имя иконки
@@ -372,6 +460,14 @@ fn create_icon_widget(icon_name: &str, app_name: &str) -> gtk4::Widget {
     label.upcast()
 }
 
+/// Загружает PNG, сохранённый сервисом
+fn texture_from_png(data: &[u8]) -> Option<gtk4::gdk::Texture> {
+    let loader = gtk4::gdk_pixbuf::PixbufLoader::new();
+    loader.write(data).ok()?;
+    loader.close().ok()?;
+    Some(gtk4::gdk::Texture::for_pixbuf(&loader.pixbuf()?))
+}
+
 /// Возвращает иконку по умолчанию для известных приложений
 fn get_default_icon_for_app(app_name: &str) -> &'static str {
     match app_name.to_lowercase().as_str() {
@@ -0,0 +1,122 @@
+use super::widget_config::{WidgetPosition, WidgetType};
+use serde_json::{json, Map, Value};
+
+/// Текущая версия схемы config.json
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Версия схемы файла
+///
+/// - 0: файлы без поля `version` - плоский список `widgets` без профилей
+///   или старый формат `BarConfig` (`widgets` - объект `тип -> { zone, order }`)
+/// - 1: профили без поля `version`
+/// - 2: текущая схема с полем `version`
+pub fn detect_version(value: &Value) -> Result<u32, String> {
+    match value.get("version") {
+        Some(version) => version
+            .as_u64()
+            .map(|v| v as u32)
+            .ok_or_else(|| format!("`version` must be a number, got {}", version)),
+        None if value.get("profiles").is_some() => Ok(1),
+        None => Ok(0),
+    }
+}
+
+/// Приводит JSON конфигурации любой поддерживаемой версии к текущей схеме
+pub fn migrate(mut value: Value) -> Result<Value, String> {
+    let mut version = detect_version(&value)?;
+
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "config version {} is newer than supported {}",
+            version, CONFIG_VERSION
+        ));
+    }
+
+    if !value.is_object() {
+        return Err("config must be a JSON object".to_string());
+    }
+
+    while version < CONFIG_VERSION {
+        value = match version {
+            0 => migrate_v0(value)?,
+            1 => migrate_v1(value),
+            _ => unreachable!(),
+        };
+        version += 1;
+        eprintln!("[Config] Migrated config to version {}", version);
+    }
+
+    Ok(value)
+}
+
+/// v0 -> v1: плоский список виджетов или `BarConfig` -> профиль "Default"
+fn migrate_v0(value: Value) -> Result<Value, String> {
+    let widgets = match value.get("widgets") {
+        Some(Value::Array(widgets)) => Value::Array(widgets.clone()),
+        Some(Value::Object(bar_widgets)) => migrate_bar_config_widgets(bar_widgets)?,
+        Some(other) => return Err(format!("legacy `widgets` has unexpected type: {}", other)),
+        None => return Err("config has neither `profiles` nor `widgets`".to_string()),
+    };
+
+    Ok(json!({
+        "profiles": [{ "name": "Default", "widgets": widgets }],
+        "active_profile": "Default",
+        "monitors": value.get("monitors").cloned().unwrap_or_else(|| json!({})),
+    }))
+}
+
+/// Виджеты старого `BarConfig`: ключ - тип в нижнем регистре, значение - `{ zone, order }`
+fn migrate_bar_config_widgets(bar_widgets: &Map<String, Value>) -> Result<Value, String> {
+    let mut widgets = Vec::new();
+
+    for (key, entry) in bar_widgets {
+        let widget_type = WidgetType::all()
+            .into_iter()
+            .find(|t| format!("{:?}", t).to_lowercase() == *key)
+            .ok_or_else(|| format!("unknown legacy widget type `{}`", key))?;
+
+        let position = match entry.get("zone").and_then(Value::as_str) {
+            Some("left") => WidgetPosition::Left,
+            Some("center") => WidgetPosition::Center,
+            Some("right") => WidgetPosition::Right,
+            other => return Err(format!("invalid zone {:?} for legacy widget `{}`", other, key)),
+        };
+
+        let order = entry.get("order").and_then(Value::as_i64).unwrap_or(0);
+
+        widgets.push(json!({
+            "widget_type": widget_type,
+            "enabled": true,
+            "position": position,
+            "order": order,
+        }));
+    }
+
+    Ok(Value::Array(widgets))
+}
+
+/// v1 -> v2: устаревший `widgets` переносится в профиль "Default" (если профилей нет)
+/// или удаляется, добавляется `version`
+fn migrate_v1(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        let legacy_widgets = object.remove("widgets").unwrap_or(Value::Null);
+        let has_legacy_widgets = legacy_widgets.as_array().is_some_and(|w| !w.is_empty());
+        let has_profiles = object
+            .get("profiles")
+            .and_then(Value::as_array)
+            .is_some_and(|p| !p.is_empty());
+
+        if has_legacy_widgets && !has_profiles {
+            object.insert(
+                "profiles".to_string(),
+                json!([{ "name": "Default", "widgets": legacy_widgets }]),
+            );
+            object.insert("active_profile".to_string(), Value::from("Default"));
+        } else if has_legacy_widgets {
+            eprintln!("[Config] Dropping legacy `widgets` list: profiles take precedence");
+        }
+
+        object.insert("version".to_string(), Value::from(2));
+    }
+    value
+}
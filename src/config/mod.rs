@@ -1,10 +1,21 @@
-pub mod bar_config;
+pub mod migration;
+pub mod night_light_config;
+pub mod osd_config;
+pub mod power_config;
 pub mod widget_config;
+pub mod widget_options;
 
 pub use widget_config::{
     HyprlineConfig, WidgetConfig, WidgetType, WidgetPosition, 
     WidgetProfile, MonitorConfig,
-    get_config, save_config, subscribe_config_changes, notify_config_changed
+    get_config, save_config, reload_config, subscribe_config_changes, notify_config_changed
+};
+pub use night_light_config::{NightLightConfig, NightLightSchedule};
+pub use osd_config::{OsdAnchor, OsdConfig};
+pub use power_config::{CriticalAction, PowerConfig};
+pub use widget_options::{
+    ActiveWindowOptions, BatteryOptions, DateTimeOptions, OptionKind, SystemResourcesOptions,
+    TaskbarOptions, TaskbarScope, WorkspacesOptions, options_schema,
 };
 
 use std::collections::HashMap;
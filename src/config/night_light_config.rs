@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Когда ночной режим включается автоматически
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NightLightSchedule {
+    /// Только вручную
+    Manual,
+    /// С `start` до `end` каждый день
+    Fixed,
+    /// От заката до восхода по координатам `latitude`/`longitude`
+    Sun,
+}
+
+/// Настройки ночного режима (цветовая температура через hyprsunset)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NightLightConfig {
+    /// Цветовая температура в кельвинах, когда режим включён
+    pub temperature: u32,
+    pub schedule: NightLightSchedule,
+    /// Начало для `Fixed`, "HH:MM"
+    pub start: String,
+    /// Конец для `Fixed`, "HH:MM"
+    pub end: String,
+    /// Широта для `Sun` (градусы, север положительный)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    /// Долгота для `Sun` (градусы, восток положительный)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+}
+
+impl Default for NightLightConfig {
+    fn default() -> Self {
+        Self {
+            temperature: 4000,
+            schedule: NightLightSchedule::Manual,
+            start: "20:00".to_string(),
+            end: "07:00".to_string(),
+            latitude: None,
+            longitude: None,
+        }
+    }
+}
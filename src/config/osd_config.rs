@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Положение OSD на экране
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OsdAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl OsdAnchor {
+    /// Привязка к краям экрана: (top, bottom, left, right)
+    pub fn edges(&self) -> (bool, bool, bool, bool) {
+        match self {
+            OsdAnchor::TopLeft => (true, false, true, false),
+            OsdAnchor::Top => (true, false, false, false),
+            OsdAnchor::TopRight => (true, false, false, true),
+            OsdAnchor::Left => (false, false, true, false),
+            OsdAnchor::Center => (false, false, false, false),
+            OsdAnchor::Right => (false, false, false, true),
+            OsdAnchor::BottomLeft => (false, true, true, false),
+            OsdAnchor::Bottom => (false, true, false, false),
+            OsdAnchor::BottomRight => (false, true, false, true),
+        }
+    }
+}
+
+/// Настройки всплывающих индикаторов (OSD)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OsdConfig {
+    pub anchor: OsdAnchor,
+    /// Отступ от вертикального края (под панелью при привязке к верху)
+    pub margin_vertical: i32,
+    /// Отступ от горизонтального края
+    pub margin_horizontal: i32,
+    /// Через сколько миллисекунд OSD скрывается
+    pub timeout_ms: u32,
+    pub volume: bool,
+    pub brightness: bool,
+    pub microphone: bool,
+    pub keyboard_layout: bool,
+    pub caps_lock: bool,
+}
+
+impl Default for OsdConfig {
+    fn default() -> Self {
+        Self {
+            anchor: OsdAnchor::TopRight,
+            margin_vertical: 60,
+            margin_horizontal: 20,
+            timeout_ms: 3000,
+            volume: true,
+            brightness: true,
+            microphone: true,
+            keyboard_layout: true,
+            caps_lock: true,
+        }
+    }
+}
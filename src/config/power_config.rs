@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Действие при критическом разряде батареи (через logind)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CriticalAction {
+    None,
+    Suspend,
+    Hibernate,
+    HybridSleep,
+    PowerOff,
+}
+
+/// Предупреждения о разряде батареи
+///
+/// Пороги low/critical берутся из настроек виджета Battery, здесь - только
+/// действие при критическом разряде. Срабатывают только при разрядке и один раз
+/// на каждый порог; подключение зарядки сбрасывает состояние.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PowerConfig {
+    /// Отправлять уведомления о низком заряде
+    pub notify: bool,
+    /// Заряд (%), при котором выполняется `critical_action`
+    pub action_threshold: u8,
+    pub critical_action: CriticalAction,
+}
+
+impl Default for PowerConfig {
+    fn default() -> Self {
+        Self {
+            notify: true,
+            action_threshold: 5,
+            critical_action: CriticalAction::None,
+        }
+    }
+}
@@ -1,3 +1,8 @@
+use super::migration::{detect_version, migrate, CONFIG_VERSION};
+use super::night_light_config::NightLightConfig;
+use super::osd_config::OsdConfig;
+use super::power_config::PowerConfig;
+use super::widget_options::{load_options, options_schema, store_options, BatteryOptions, SystemResourcesOptions, WidgetOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -27,6 +32,10 @@ pub enum WidgetType {
     Notifications,
     DateTime,
     Submap,
+    Taskbar,
+    Microphone,
+    Media,
+    NightLight,
 }
 
 impl WidgetType {
@@ -45,6 +54,10 @@ impl WidgetType {
             WidgetType::Notifications => "Notifications",
             WidgetType::DateTime => "Date & Time",
             WidgetType::Submap => "Submap",
+            WidgetType::Taskbar => "Taskbar",
+            WidgetType::Microphone => "Microphone",
+            WidgetType::Media => "Media Player",
+            WidgetType::NightLight => "Night Light",
         }
     }
 
@@ -63,6 +76,10 @@ impl WidgetType {
             WidgetType::Notifications => "󰂚",
             WidgetType::DateTime => "󰥔",
             WidgetType::Submap => "󰌌",
+            WidgetType::Taskbar => "󰖲",
+            WidgetType::Microphone => "󰍬",
+            WidgetType::Media => "󰎆",
+            WidgetType::NightLight => "󰖔",
         }
     }
 
@@ -81,21 +98,57 @@ impl WidgetType {
             WidgetType::Notifications,
             WidgetType::DateTime,
             WidgetType::Submap,
+            WidgetType::Taskbar,
+            WidgetType::Microphone,
+            WidgetType::Media,
+            WidgetType::NightLight,
         ]
     }
 }
 
 /// Конфигурация одного виджета
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct WidgetConfig {
     pub widget_type: WidgetType,
     pub enabled: bool,
     pub position: WidgetPosition,
     pub order: i32,
+    /// Версионированные настройки, специфичные для типа виджета (см. `widget_options`)
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub options: serde_json::Value,
+}
+
+impl WidgetConfig {
+    pub fn new(widget_type: WidgetType, enabled: bool, position: WidgetPosition, order: i32) -> Self {
+        Self {
+            widget_type,
+            enabled,
+            position,
+            order,
+            options: serde_json::Value::Null,
+        }
+    }
+
+    /// Получить типизированные настройки виджета
+    /// Отсутствующие поля берутся из `Default`, при ошибке разбора или валидации - настройки по умолчанию
+    pub fn options<T: WidgetOptions>(&self) -> T {
+        load_options(&self.options).unwrap_or_else(|e| {
+            eprintln!("[Config] ✗ Invalid options for {:?}: {}", self.widget_type, e);
+            T::default()
+        })
+    }
+
+    /// Сохранить типизированные настройки виджета
+    #[allow(dead_code)]
+    pub fn set_options<T: WidgetOptions>(&mut self, options: &T) {
+        self.options = store_options(options);
+    }
 }
 
 /// Профиль виджетов - набор виджетов с определённой конфигурацией
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct WidgetProfile {
     pub name: String,
     pub widgets: Vec<WidgetConfig>,
@@ -107,28 +160,29 @@ impl Default for WidgetProfile {
             name: "Default".to_string(),
             widgets: vec![
                 // Left zone
-                WidgetConfig { widget_type: WidgetType::Menu, enabled: true, position: WidgetPosition::Left, order: 0 },
-                WidgetConfig { widget_type: WidgetType::Workspaces, enabled: true, position: WidgetPosition::Left, order: 1 },
-                WidgetConfig { widget_type: WidgetType::ActiveWindow, enabled: true, position: WidgetPosition::Left, order: 2 },
-                WidgetConfig { widget_type: WidgetType::Submap, enabled: true, position: WidgetPosition::Left, order: 3 },
+                WidgetConfig::new(WidgetType::Menu, true, WidgetPosition::Left, 0),
+                WidgetConfig::new(WidgetType::Workspaces, true, WidgetPosition::Left, 1),
+                WidgetConfig::new(WidgetType::ActiveWindow, true, WidgetPosition::Left, 2),
+                WidgetConfig::new(WidgetType::Submap, true, WidgetPosition::Left, 3),
 
                 // Right zone
-                WidgetConfig { widget_type: WidgetType::SystemTray, enabled: true, position: WidgetPosition::Right, order: 0 },
-                WidgetConfig { widget_type: WidgetType::SystemResources, enabled: true, position: WidgetPosition::Right, order: 1 },
-                WidgetConfig { widget_type: WidgetType::Network, enabled: true, position: WidgetPosition::Right, order: 2 },
-                WidgetConfig { widget_type: WidgetType::Volume, enabled: true, position: WidgetPosition::Right, order: 3 },
-                WidgetConfig { widget_type: WidgetType::Brightness, enabled: true, position: WidgetPosition::Right, order: 4 },
-                WidgetConfig { widget_type: WidgetType::Battery, enabled: true, position: WidgetPosition::Right, order: 5 },
-                WidgetConfig { widget_type: WidgetType::KeyboardLayout, enabled: true, position: WidgetPosition::Right, order: 6 },
-                WidgetConfig { widget_type: WidgetType::Notifications, enabled: true, position: WidgetPosition::Right, order: 7 },
-                WidgetConfig { widget_type: WidgetType::DateTime, enabled: true, position: WidgetPosition::Right, order: 8 },
+                WidgetConfig::new(WidgetType::SystemTray, true, WidgetPosition::Right, 0),
+                WidgetConfig::new(WidgetType::SystemResources, true, WidgetPosition::Right, 1),
+                WidgetConfig::new(WidgetType::Network, true, WidgetPosition::Right, 2),
+                WidgetConfig::new(WidgetType::Volume, true, WidgetPosition::Right, 3),
+                WidgetConfig::new(WidgetType::Brightness, true, WidgetPosition::Right, 4),
+                WidgetConfig::new(WidgetType::Battery, true, WidgetPosition::Right, 5),
+                WidgetConfig::new(WidgetType::KeyboardLayout, true, WidgetPosition::Right, 6),
+                WidgetConfig::new(WidgetType::Notifications, true, WidgetPosition::Right, 7),
+                WidgetConfig::new(WidgetType::DateTime, true, WidgetPosition::Right, 8),
             ],
         }
     }
 }
 
 /// Конфигурация для конкретного монитора
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct MonitorConfig {
     /// Имя профиля, используемого для этого монитора
     /// Если None - используется активный профиль
@@ -144,46 +198,61 @@ impl Default for MonitorConfig {
 }
 
 /// Главная конфигурация панели
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct HyprlineConfig {
+    /// Версия схемы файла (см. `migration`)
+    pub version: u32,
     /// Список профилей виджетов
     pub profiles: Vec<WidgetProfile>,
     /// Имя активного профиля (по умолчанию)
     pub active_profile: String,
     /// Настройки для конкретных мониторов (ключ - имя монитора)
     pub monitors: HashMap<String, MonitorConfig>,
-
-    /// Обратная совместимость - старое поле widgets
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub widgets: Vec<WidgetConfig>,
+    /// Имя темы из `themes/` (файл `<имя>.css`), None - только встроенные стили
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Всплывающие индикаторы громкости, яркости, раскладки и т.д.
+    #[serde(default)]
+    pub osd: OsdConfig,
+    /// Ночной режим: температура и расписание
+    #[serde(default)]
+    pub night_light: NightLightConfig,
+    /// Уведомления и действия при разряде батареи
+    #[serde(default)]
+    pub power: PowerConfig,
 }
 
 impl Default for HyprlineConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             profiles: vec![
                 WidgetProfile::default(),
                 WidgetProfile {
                     name: "Minimal".to_string(),
                     widgets: vec![
-                        WidgetConfig { widget_type: WidgetType::Workspaces, enabled: true, position: WidgetPosition::Left, order: 0 },
-                        WidgetConfig { widget_type: WidgetType::ActiveWindow, enabled: true, position: WidgetPosition::Center, order: 0 },
-                        WidgetConfig { widget_type: WidgetType::DateTime, enabled: true, position: WidgetPosition::Right, order: 0 },
+                        WidgetConfig::new(WidgetType::Workspaces, true, WidgetPosition::Left, 0),
+                        WidgetConfig::new(WidgetType::ActiveWindow, true, WidgetPosition::Center, 0),
+                        WidgetConfig::new(WidgetType::DateTime, true, WidgetPosition::Right, 0),
                     ],
                 },
                 WidgetProfile {
                     name: "Secondary Monitor".to_string(),
                     widgets: vec![
-                        WidgetConfig { widget_type: WidgetType::Workspaces, enabled: true, position: WidgetPosition::Left, order: 0 },
-                        WidgetConfig { widget_type: WidgetType::ActiveWindow, enabled: true, position: WidgetPosition::Center, order: 0 },
-                        WidgetConfig { widget_type: WidgetType::SystemResources, enabled: true, position: WidgetPosition::Right, order: 0 },
-                        WidgetConfig { widget_type: WidgetType::DateTime, enabled: true, position: WidgetPosition::Right, order: 1 },
+                        WidgetConfig::new(WidgetType::Workspaces, true, WidgetPosition::Left, 0),
+                        WidgetConfig::new(WidgetType::ActiveWindow, true, WidgetPosition::Center, 0),
+                        WidgetConfig::new(WidgetType::SystemResources, true, WidgetPosition::Right, 0),
+                        WidgetConfig::new(WidgetType::DateTime, true, WidgetPosition::Right, 1),
                     ],
                 },
             ],
             active_profile: "Default".to_string(),
             monitors: HashMap::new(),
-            widgets: Vec::new(),
+            theme: None,
+            osd: OsdConfig::default(),
+            night_light: NightLightConfig::default(),
+            power: PowerConfig::default(),
         }
     }
 }
@@ -202,51 +271,116 @@ impl HyprlineConfig {
     }
 
     /// Загрузить конфигурацию из файла
-    pub fn load() -> Self {
+    ///
+    /// Отсутствующий файл - конфигурация по умолчанию. Файлы старых версий мигрируются
+    /// и перезаписываются в текущей схеме, исходный файл сохраняется в `config.json.bak`.
+    /// Ошибки синтаксиса, неизвестные ключи и некорректные options возвращаются как Err.
+    pub fn load() -> Result<Self, String> {
         let path = Self::config_path();
 
-        if path.exists() {
-            match fs::read_to_string(&path) {
-                Ok(content) => {
-                    match serde_json::from_str::<HyprlineConfig>(&content) {
-                        Ok(mut config) => {
-                            // Миграция старого формата
-                            if !config.widgets.is_empty() && config.profiles.is_empty() {
-                                eprintln!("[Config] Migrating old config format...");
-                                config.profiles = vec![WidgetProfile {
-                                    name: "Default".to_string(),
-                                    widgets: config.widgets.clone(),
-                                }];
-                                config.active_profile = "Default".to_string();
-                                config.widgets.clear();
-                                // Сохраняем мигрированный конфиг
-                                let _ = config.save();
-                            }
-
-                            // Если профили пустые - добавляем дефолтный
-                            if config.profiles.is_empty() {
-                                config.profiles = vec![WidgetProfile::default()];
-                                config.active_profile = "Default".to_string();
-                            }
-
-                            eprintln!("[Config] ✓ Loaded from {:?}", path);
-                            eprintln!("[Config] Active profile: {}", config.active_profile);
-                            eprintln!("[Config] Profiles: {:?}", config.profiles.iter().map(|p| &p.name).collect::<Vec<_>>());
-                            return config;
-                        }
-                        Err(e) => {
-                            eprintln!("[Config] ✗ Failed to parse config: {}", e);
+        if !path.exists() {
+            eprintln!("[Config] No config file, using default configuration");
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read config: {}", e))?;
+
+        let (mut config, migrated) = Self::parse(&content)?;
+
+        // Если профили пустые - добавляем дефолтный
+        if config.profiles.is_empty() {
+            config.profiles = vec![WidgetProfile::default()];
+            config.active_profile = "Default".to_string();
+        }
+
+        if migrated {
+            let backup = path.with_extension("json.bak");
+            match fs::copy(&path, &backup) {
+                Ok(_) => eprintln!("[Config] ✓ Legacy config backed up to {:?}", backup),
+                Err(e) => eprintln!("[Config] ✗ Failed to back up legacy config: {}", e),
+            }
+            if let Err(e) = config.save() {
+                eprintln!("[Config] ✗ Failed to save migrated config: {}", e);
+            }
+        }
+
+        eprintln!("[Config] ✓ Loaded from {:?}", path);
+        eprintln!("[Config] Active profile: {}", config.active_profile);
+        eprintln!("[Config] Profiles: {:?}", config.profiles.iter().map(|p| &p.name).collect::<Vec<_>>());
+        Ok(config)
+    }
+
+    /// Разобрать содержимое config.json; второе значение - была ли выполнена миграция
+    pub fn parse(content: &str) -> Result<(Self, bool), String> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| format!("Invalid JSON: {}", e))?;
+        let version = detect_version(&value)?;
+
+        let config: Self = if version == CONFIG_VERSION {
+            // Разбираем из текста, чтобы в ошибке были строка и столбец
+            serde_json::from_str(content).map_err(|e| format!("Invalid config: {}", e))?
+        } else {
+            serde_json::from_value(migrate(value)?)
+                .map_err(|e| format!("Invalid config (version {}): {}", version, e))?
+        };
+
+        config.validate()?;
+        Ok((config, version != CONFIG_VERSION))
+    }
+
+    /// Проверка options всех виджетов по их схемам, имени темы и порогов батареи
+    fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+
+        // Тема - имя файла внутри themes/, а не путь
+        if let Some(theme) = &self.theme {
+            if theme.is_empty() || theme.contains('/') || theme.contains("..") {
+                errors.push(format!("theme '{}': expected a file name from themes/", theme));
+            }
+        }
+
+        for profile in &self.profiles {
+            for widget in &profile.widgets {
+                match options_schema(widget.widget_type) {
+                    Some(schema) => {
+                        if let Err(e) = (schema.normalize)(&widget.options) {
+                            errors.push(format!("profile '{}', {:?}: {}", profile.name, widget.widget_type, e));
                         }
                     }
-                }
-                Err(e) => {
-                    eprintln!("[Config] ✗ Failed to read config: {}", e);
+                    None if !widget.options.is_null() => {
+                        errors.push(format!(
+                            "profile '{}', {:?}: widget has no options",
+                            profile.name, widget.widget_type
+                        ));
+                    }
+                    None => {}
                 }
             }
         }
 
-        eprintln!("[Config] Using default configuration");
-        Self::default()
+        // Действие должно срабатывать не раньше критического уведомления;
+        // при некорректных options виджета ошибка уже записана выше
+        if errors.is_empty() {
+            let battery = self.battery_options();
+            let action = self.power.action_threshold;
+            if !(action <= battery.critical_threshold
+                && battery.critical_threshold < battery.low_threshold
+                && battery.low_threshold <= 100)
+            {
+                errors.push(format!(
+                    "power: thresholds must satisfy action_threshold <= critical_threshold < low_threshold <= 100 \
+                     (got {} / {} / {})",
+                    action, battery.critical_threshold, battery.low_threshold
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
     }
 
     /// Сохранить конфигурацию в файл
@@ -285,6 +419,29 @@ impl HyprlineConfig {
             .expect("At least one profile must exist")
     }
 
+    /// Интервал опроса системных ресурсов в секундах:
+    /// минимальный среди включённых виджетов SystemResources во всех профилях
+    pub fn system_resources_interval(&self) -> u32 {
+        self.profiles
+            .iter()
+            .flat_map(|p| p.widgets.iter())
+            .filter(|w| w.enabled && w.widget_type == WidgetType::SystemResources)
+            .map(|w| w.options::<SystemResourcesOptions>().update_interval)
+            .min()
+            .unwrap_or_else(|| SystemResourcesOptions::default().update_interval)
+    }
+
+    /// Пороги заряда батареи: из первого включённого виджета Battery
+    /// активного профиля, иначе значения по умолчанию
+    pub fn battery_options(&self) -> BatteryOptions {
+        self.get_active_profile()
+            .widgets
+            .iter()
+            .find(|w| w.enabled && w.widget_type == WidgetType::Battery)
+            .map(|w| w.options::<BatteryOptions>())
+            .unwrap_or_default()
+    }
+
     /// Получить профиль для конкретного монитора
     pub fn get_profile_for_monitor(&self, monitor_name: &str) -> &WidgetProfile {
         // Проверяем, есть ли специфичная настройка для монитора
@@ -430,12 +587,7 @@ impl HyprlineConfig {
                 widget.position = position;
                 widget.order = order;
             } else {
-                profile.widgets.push(WidgetConfig {
-                    widget_type,
-                    enabled,
-                    position,
-                    order,
-                });
+                profile.widgets.push(WidgetConfig::new(widget_type, enabled, position, order));
             }
         }
     }
@@ -449,7 +601,12 @@ static CONFIG_CHANGE_CALLBACKS: OnceLock<Mutex<Vec<Box<dyn Fn() + Send + Sync>>>
 
 pub fn get_config() -> &'static RwLock<HyprlineConfig> {
     CONFIG.get_or_init(|| {
-        RwLock::new(HyprlineConfig::load())
+        let config = HyprlineConfig::load().unwrap_or_else(|e| {
+            eprintln!("[Config] ✗ Failed to load {:?}: {}", HyprlineConfig::config_path(), e);
+            eprintln!("[Config] Using default configuration, the file is left untouched");
+            HyprlineConfig::default()
+        });
+        RwLock::new(config)
     })
 }
 
@@ -462,6 +619,31 @@ pub fn save_config() -> Result<(), String> {
     Ok(())
 }
 
+/// Перечитать конфигурацию с диска после внешнего редактирования файла
+///
+/// Возвращает true, если конфигурация изменилась и подписчики уведомлены.
+/// При ошибке разбора текущая конфигурация остаётся без изменений.
+pub fn reload_config() -> Result<bool, String> {
+    // Файл удалён или ещё не создан - оставляем текущую конфигурацию
+    if !HyprlineConfig::config_path().exists() {
+        return Ok(false);
+    }
+
+    let config = HyprlineConfig::load()?;
+
+    {
+        let mut current = get_config().write().unwrap();
+        // Собственное сохранение из окна настроек - ничего не изменилось
+        if *current == config {
+            return Ok(false);
+        }
+        *current = config;
+    }
+
+    notify_config_changed();
+    Ok(true)
+}
+
 /// Подписаться на изменения конфигурации
 pub fn subscribe_config_changes<F>(callback: F)
 where
@@ -0,0 +1,441 @@
+use super::widget_config::WidgetType;
+use crate::domain::models::{DateTimeConfig, DateTimeFormat};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Ключ версии схемы внутри блока options
+const VERSION_KEY: &str = "version";
+
+/// Тип поля настроек (по нему строится редактор в окне настроек)
+#[derive(Debug, Clone)]
+pub enum OptionKind {
+    Bool,
+    Int { min: i64, max: i64 },
+    Text,
+    /// Выбор из списка: (значение в JSON, подпись)
+    Choice(&'static [(&'static str, &'static str)]),
+    /// Список объектов из двух строковых полей, редактируется построчно как `first => second`
+    Pairs { first: &'static str, second: &'static str },
+}
+
+/// Описание одного поля настроек виджета
+#[derive(Debug, Clone)]
+pub struct OptionField {
+    /// Имя поля в JSON
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: OptionKind,
+}
+
+impl OptionField {
+    const fn new(key: &'static str, label: &'static str, kind: OptionKind) -> Self {
+        Self { key, label, kind }
+    }
+}
+
+/// Типизированные настройки виджета
+///
+/// В конфиге хранятся как JSON-объект с полем `version`; отсутствующие поля
+/// берутся из `Default`, неизвестные поля - ошибка, старые версии приводятся
+/// к текущей через `migrate`.
+pub trait WidgetOptions: Serialize + DeserializeOwned + Default {
+    /// Текущая версия схемы
+    const VERSION: u32 = 1;
+
+    /// Поля для редактора в окне настроек
+    fn fields() -> Vec<OptionField>;
+
+    /// Приведение JSON старой версии к текущей
+    fn migrate(_from_version: u32, value: Value) -> Value {
+        value
+    }
+
+    /// Проверка значений после разбора
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Разобрать блок options: миграция, значения по умолчанию, валидация
+pub fn load_options<T: WidgetOptions>(value: &Value) -> Result<T, String> {
+    if value.is_null() {
+        return Ok(T::default());
+    }
+
+    let mut value = value.clone();
+    let object = value.as_object_mut().ok_or("options must be an object")?;
+
+    // Блоки без версии - из первой версии схемы
+    let version = match object.remove(VERSION_KEY) {
+        Some(v) => v.as_u64().ok_or("version must be a number")? as u32,
+        None => 1,
+    };
+
+    if version > T::VERSION {
+        return Err(format!("options version {} is newer than supported {}", version, T::VERSION));
+    }
+    if version < T::VERSION {
+        value = T::migrate(version, value);
+    }
+
+    let options: T = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    options.validate()?;
+    Ok(options)
+}
+
+/// Сериализовать настройки вместе с версией схемы
+pub fn store_options<T: WidgetOptions>(options: &T) -> Value {
+    let mut value = serde_json::to_value(options).unwrap_or(Value::Null);
+    if let Some(object) = value.as_object_mut() {
+        object.insert(VERSION_KEY.to_string(), Value::from(T::VERSION));
+    }
+    value
+}
+
+/// Нормализация блока options: разбор и обратная сериализация с текущей версией
+fn normalize<T: WidgetOptions>(value: &Value) -> Result<Value, String> {
+    load_options::<T>(value).map(|options| store_options(&options))
+}
+
+/// Схема настроек типа виджета, не зависящая от конкретного Rust-типа
+pub struct OptionsSchema {
+    pub fields: Vec<OptionField>,
+    /// Проверяет блок options и возвращает его в нормализованном виде
+    pub normalize: fn(&Value) -> Result<Value, String>,
+}
+
+impl OptionsSchema {
+    fn of<T: WidgetOptions>() -> Self {
+        Self {
+            fields: T::fields(),
+            normalize: normalize::<T>,
+        }
+    }
+
+    /// Настройки по умолчанию в нормализованном виде
+    pub fn defaults(&self) -> Value {
+        (self.normalize)(&Value::Null).unwrap_or(Value::Null)
+    }
+}
+
+/// Схема настроек для типа виджета (None - у виджета нет настроек)
+pub fn options_schema(widget_type: WidgetType) -> Option<OptionsSchema> {
+    match widget_type {
+        WidgetType::Workspaces => Some(OptionsSchema::of::<WorkspacesOptions>()),
+        WidgetType::ActiveWindow => Some(OptionsSchema::of::<ActiveWindowOptions>()),
+        WidgetType::Taskbar => Some(OptionsSchema::of::<TaskbarOptions>()),
+        WidgetType::DateTime => Some(OptionsSchema::of::<DateTimeOptions>()),
+        WidgetType::SystemResources => Some(OptionsSchema::of::<SystemResourcesOptions>()),
+        WidgetType::Battery => Some(OptionsSchema::of::<BatteryOptions>()),
+        _ => None,
+    }
+}
+
+/// Настройки виджета Workspaces
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkspacesOptions {
+    /// Показывать только workspaces монитора, на котором находится панель
+    pub monitor_only: bool,
+    /// Показывать special (scratchpad) workspaces
+    pub show_special: bool,
+    /// Показывать иконки приложений открытых на workspace окон
+    pub show_icons: bool,
+}
+
+impl Default for WorkspacesOptions {
+    fn default() -> Self {
+        Self {
+            monitor_only: true,
+            show_special: true,
+            show_icons: true,
+        }
+    }
+}
+
+impl WidgetOptions for WorkspacesOptions {
+    fn fields() -> Vec<OptionField> {
+        vec![
+            OptionField::new("monitor_only", "Only this monitor's workspaces", OptionKind::Bool),
+            OptionField::new("show_special", "Show special workspaces", OptionKind::Bool),
+            OptionField::new("show_icons", "Show app icons", OptionKind::Bool),
+        ]
+    }
+}
+
+/// Какие окна показывает Taskbar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskbarScope {
+    /// Окна активного workspace монитора панели
+    Workspace,
+    /// Все окна монитора панели
+    Monitor,
+}
+
+/// Настройки виджета Taskbar
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TaskbarOptions {
+    pub scope: TaskbarScope,
+    /// Одна кнопка на класс приложения
+    pub group_by_class: bool,
+    /// Показывать заголовки окон рядом с иконками
+    pub show_titles: bool,
+}
+
+impl Default for TaskbarOptions {
+    fn default() -> Self {
+        Self {
+            scope: TaskbarScope::Workspace,
+            group_by_class: false,
+            show_titles: true,
+        }
+    }
+}
+
+impl WidgetOptions for TaskbarOptions {
+    fn fields() -> Vec<OptionField> {
+        vec![
+            OptionField::new(
+                "scope",
+                "Show windows from",
+                OptionKind::Choice(&[("Workspace", "Active workspace"), ("Monitor", "Whole monitor")]),
+            ),
+            OptionField::new("group_by_class", "Group windows by app", OptionKind::Bool),
+            OptionField::new("show_titles", "Show window titles", OptionKind::Bool),
+        ]
+    }
+}
+
+/// Правило переписывания заголовка окна: regex и строка замены (`$1`, `${name}`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TitleRewriteRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub replacement: String,
+}
+
+/// Настройки виджета ActiveWindow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ActiveWindowOptions {
+    /// Показывать иконку приложения
+    pub show_icon: bool,
+    /// Показывать класс окна перед заголовком
+    pub show_class: bool,
+    /// Показывать индикатор floating/fullscreen/pinned
+    pub show_state: bool,
+    /// Максимальная длина заголовка в символах (0 - без ограничения)
+    pub max_length: u32,
+    /// Правила переписывания заголовка, применяются по порядку
+    pub rewrite_rules: Vec<TitleRewriteRule>,
+}
+
+impl Default for ActiveWindowOptions {
+    fn default() -> Self {
+        Self {
+            show_icon: true,
+            show_class: false,
+            show_state: true,
+            max_length: 50,
+            rewrite_rules: Vec::new(),
+        }
+    }
+}
+
+impl WidgetOptions for ActiveWindowOptions {
+    fn fields() -> Vec<OptionField> {
+        vec![
+            OptionField::new("show_icon", "Show app icon", OptionKind::Bool),
+            OptionField::new("show_class", "Show window class", OptionKind::Bool),
+            OptionField::new("show_state", "Show floating/fullscreen/pinned state", OptionKind::Bool),
+            OptionField::new("max_length", "Max title length (0 = unlimited)", OptionKind::Int { min: 0, max: 500 }),
+            OptionField::new(
+                "rewrite_rules",
+                "Title rewrite rules (regex => replacement)",
+                OptionKind::Pairs { first: "pattern", second: "replacement" },
+            ),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for rule in &self.rewrite_rules {
+            regex::Regex::new(&rule.pattern)
+                .map_err(|e| format!("invalid rewrite rule '{}': {}", rule.pattern, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Формат даты и времени
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DateTimeStyle {
+    /// Согласно локали системы
+    SystemLocale,
+    /// Только время
+    TimeOnly,
+    /// Только дата
+    DateOnly,
+    /// Формат из `custom_format`
+    Custom,
+}
+
+/// Настройки виджета DateTime
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DateTimeOptions {
+    pub style: DateTimeStyle,
+    /// strftime-формат для `DateTimeStyle::Custom`, например `%Y-%m-%d %H:%M`
+    pub custom_format: String,
+    pub show_seconds: bool,
+    pub show_date: bool,
+}
+
+impl Default for DateTimeOptions {
+    fn default() -> Self {
+        Self {
+            style: DateTimeStyle::SystemLocale,
+            custom_format: "%Y-%m-%d %H:%M:%S".to_string(),
+            show_seconds: true,
+            show_date: true,
+        }
+    }
+}
+
+impl DateTimeOptions {
+    /// Конфигурация для DateTimeService
+    pub fn to_datetime_config(&self) -> DateTimeConfig {
+        let format = match self.style {
+            DateTimeStyle::SystemLocale => DateTimeFormat::SystemLocale,
+            DateTimeStyle::TimeOnly => DateTimeFormat::TimeOnly,
+            DateTimeStyle::DateOnly => DateTimeFormat::DateOnly,
+            DateTimeStyle::Custom => DateTimeFormat::Custom(self.custom_format.clone()),
+        };
+
+        DateTimeConfig {
+            format,
+            show_seconds: self.show_seconds,
+            show_date: self.show_date,
+        }
+    }
+}
+
+impl WidgetOptions for DateTimeOptions {
+    fn fields() -> Vec<OptionField> {
+        vec![
+            OptionField::new(
+                "style",
+                "Format",
+                OptionKind::Choice(&[
+                    ("SystemLocale", "System locale"),
+                    ("TimeOnly", "Time only"),
+                    ("DateOnly", "Date only"),
+                    ("Custom", "Custom"),
+                ]),
+            ),
+            OptionField::new("custom_format", "Custom format (strftime)", OptionKind::Text),
+            OptionField::new("show_seconds", "Show seconds", OptionKind::Bool),
+            OptionField::new("show_date", "Show date", OptionKind::Bool),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.style == DateTimeStyle::Custom {
+            use chrono::format::{Item, StrftimeItems};
+            if self.custom_format.is_empty() {
+                return Err("custom format is empty".to_string());
+            }
+            if StrftimeItems::new(&self.custom_format).any(|item| item == Item::Error) {
+                return Err(format!("invalid strftime format '{}'", self.custom_format));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Настройки виджета SystemResources
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SystemResourcesOptions {
+    /// Интервал обновления в секундах
+    pub update_interval: u32,
+    /// Показывать использование памяти
+    pub show_memory: bool,
+    /// Порог нагрузки (%) для класса `*-medium`
+    pub medium_threshold: u32,
+    /// Порог нагрузки (%) для класса `*-high`
+    pub high_threshold: u32,
+}
+
+impl Default for SystemResourcesOptions {
+    fn default() -> Self {
+        Self {
+            update_interval: 2,
+            show_memory: true,
+            medium_threshold: 50,
+            high_threshold: 80,
+        }
+    }
+}
+
+impl WidgetOptions for SystemResourcesOptions {
+    fn fields() -> Vec<OptionField> {
+        vec![
+            OptionField::new("update_interval", "Update interval (seconds)", OptionKind::Int { min: 1, max: 60 }),
+            OptionField::new("show_memory", "Show memory usage", OptionKind::Bool),
+            OptionField::new("medium_threshold", "Medium load threshold (%)", OptionKind::Int { min: 0, max: 100 }),
+            OptionField::new("high_threshold", "High load threshold (%)", OptionKind::Int { min: 0, max: 100 }),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.update_interval == 0 {
+            return Err("update interval must be at least 1 second".to_string());
+        }
+        if self.medium_threshold >= self.high_threshold || self.high_threshold > 100 {
+            return Err("thresholds must satisfy medium < high <= 100".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Настройки виджета Battery
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BatteryOptions {
+    /// Показывать процент заряда рядом с иконкой
+    pub show_percentage: bool,
+    /// Заряд (%), ниже которого применяется класс `battery-low`
+    pub low_threshold: u8,
+    /// Заряд (%), ниже которого применяется класс `battery-critical`
+    pub critical_threshold: u8,
+}
+
+impl Default for BatteryOptions {
+    fn default() -> Self {
+        Self {
+            show_percentage: true,
+            low_threshold: 30,
+            critical_threshold: 10,
+        }
+    }
+}
+
+impl WidgetOptions for BatteryOptions {
+    fn fields() -> Vec<OptionField> {
+        vec![
+            OptionField::new("show_percentage", "Show percentage", OptionKind::Bool),
+            OptionField::new("low_threshold", "Low level (%)", OptionKind::Int { min: 0, max: 100 }),
+            OptionField::new("critical_threshold", "Critical level (%)", OptionKind::Int { min: 0, max: 100 }),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.critical_threshold >= self.low_threshold || self.low_threshold > 100 {
+            return Err("thresholds must satisfy critical < low <= 100".to_string());
+        }
+        Ok(())
+    }
+}
@@ -3,6 +3,10 @@ use crate::domain::models::BatteryInfo;
 /// Trait для получения информации о состоянии батареи
 pub trait BatteryService {
     /// Возвращает текущее состояние батареи
+    ///
+    /// Несколько батарей ноутбука объединяются в одну.
     fn get_battery_info(&self) -> Option<BatteryInfo>;
-}
 
+    /// Периферийные устройства с батареей (мыши, клавиатуры, гарнитуры, телефоны)
+    fn get_peripherals(&self) -> Vec<BatteryInfo>;
+}
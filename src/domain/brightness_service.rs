@@ -1,7 +1,17 @@
+use crate::domain::models::BrightnessDisplay;
 use std::sync::Arc;
 
 /// Сервис для управления яркостью экрана
 pub trait BrightnessService: Send + Sync {
+    /// Все дисплеи с регулируемой яркостью
+    ///
+    /// Методы ниже без id работают с основным дисплеем (встроенная подсветка,
+    /// а если её нет - первый внешний монитор).
+    fn list_displays(&self) -> Vec<BrightnessDisplay>;
+
+    /// Установить яркость конкретного дисплея (0-100)
+    fn set_display_brightness(&self, id: &str, value: u32) -> Result<(), String>;
+
     /// Получить текущую яркость (0-100)
     fn get_brightness(&self) -> Result<u32, String>;
     
@@ -14,6 +24,11 @@ pub trait BrightnessService: Send + Sync {
     /// Уменьшить яркость на процент
     fn decrease_brightness(&self, percent: u32) -> Result<(), String>;
     
+    /// Поддерживает ли бэкенд автоматическую регулировку
+    fn supports_auto_adjustment(&self) -> bool {
+        true
+    }
+
     /// Включить автоматическую регулировку
     fn enable_auto_adjustment(&self) -> Result<(), String>;
     
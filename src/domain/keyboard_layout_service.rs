@@ -4,5 +4,8 @@ use crate::domain::models::KeyboardLayout;
 pub trait KeyboardLayoutService: Send + Sync {
     /// Получает текущую активную раскладку
     fn get_current_layout(&self) -> Option<KeyboardLayout>;
+
+    /// Состояние Caps Lock основной клавиатуры
+    fn get_caps_lock(&self) -> Option<bool>;
 }
 
@@ -11,4 +11,7 @@ pub mod system_resources_service;
 pub mod network_service;
 pub mod brightness_service;
 pub mod submap_service;
+pub mod mpris_service;
+pub mod night_light_service;
+pub mod power_profile_service;
 
@@ -3,16 +3,37 @@ use serde::Deserialize;
 #[derive(Debug, Deserialize, Clone)]
 pub struct Workspace {
     pub id: i32,
-    #[allow(dead_code)]
     pub name: String,
     pub windows: i32,
     pub monitor: String,
 }
 
+impl Workspace {
+    /// Special (scratchpad) workspace: имя `special` или `special:<name>`, отрицательный id
+    pub fn is_special(&self) -> bool {
+        self.id < 0 && (self.name == "special" || self.name.starts_with("special:"))
+    }
+
+    /// Имя special workspace без префикса `special:`
+    pub fn special_name(&self) -> &str {
+        self.name.strip_prefix("special:").unwrap_or("")
+    }
+
+    /// Идентификатор workspace для аргументов dispatcher'ов (`3`, `name:web`, `special:scratch`)
+    pub fn dispatch_target(&self) -> String {
+        if self.is_special() {
+            self.name.clone()
+        } else if self.id > 0 {
+            self.id.to_string()
+        } else {
+            format!("name:{}", self.name)
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Monitor {
     pub name: String,
-    #[allow(dead_code)]
     pub id: i32,
 }
 
@@ -21,19 +42,17 @@ pub struct ActiveWorkspace {
     pub id: i32,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct MonitorInfo {
-    pub name: String,
-    pub focused: bool,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct MonitorWithWorkspace {
     pub name: String,
-    #[allow(dead_code)]
     pub id: i32,
+    #[serde(default)]
+    pub focused: bool,
     #[serde(rename = "activeWorkspace")]
     pub active_workspace: WorkspaceInfo,
+    /// Открытый special workspace (id = 0, если закрыт)
+    #[serde(rename = "specialWorkspace", default)]
+    pub special_workspace: WorkspaceRef,
 }
 
 #[derive(Debug, Deserialize)]
@@ -41,6 +60,246 @@ pub struct WorkspaceInfo {
     pub id: i32,
 }
 
+/// Состояние workspace'ов и окон для одного монитора (один batch-запрос к Hyprland)
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceSnapshot {
+    pub workspaces: Vec<Workspace>,
+    /// Активный workspace на мониторе с фокусом
+    pub active_workspace: i32,
+    /// Монитор с фокусом
+    pub active_monitor: String,
+    /// Id монитора в Hyprland (`WindowInfo::monitor`)
+    pub monitor_id: Option<i32>,
+    /// Активный workspace на этом мониторе
+    pub monitor_active_workspace: Option<i32>,
+    /// Открытый special workspace на этом мониторе
+    pub active_special: Option<String>,
+    pub clients: Vec<WindowInfo>,
+}
+
+// Hyprland IPC models (ответы на j/ запросы)
+
+/// Ссылка на workspace внутри других объектов (`{"id": 1, "name": "1"}`)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct WorkspaceRef {
+    pub id: i32,
+    pub name: String,
+}
+
+/// Окно (`j/clients`, `j/activewindow`)
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowInfo {
+    pub address: String,
+    #[serde(default)]
+    pub mapped: bool,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub at: (i32, i32),
+    #[serde(default)]
+    pub size: (i32, i32),
+    pub workspace: WorkspaceRef,
+    #[serde(default)]
+    pub floating: bool,
+    #[serde(default)]
+    pub pseudo: bool,
+    #[serde(default)]
+    pub monitor: i32,
+    #[serde(default)]
+    pub class: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub initial_class: String,
+    #[serde(default)]
+    pub initial_title: String,
+    #[serde(default)]
+    pub pid: i32,
+    #[serde(default)]
+    pub xwayland: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    /// Режим fullscreen: 0 - нет, 1 - maximized, 2 - fullscreen
+    /// (старые версии Hyprland присылают bool)
+    #[serde(default, deserialize_with = "deserialize_bool_or_int")]
+    pub fullscreen: u8,
+    #[serde(default, rename = "focusHistoryID")]
+    pub focus_history_id: i32,
+    #[serde(default)]
+    pub grouped: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn deserialize_bool_or_int<'de, D>(deserializer: D) -> Result<u8, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrInt {
+        Bool(bool),
+        Int(u8),
+    }
+
+    Ok(match BoolOrInt::deserialize(deserializer)? {
+        BoolOrInt::Bool(value) => value as u8,
+        BoolOrInt::Int(value) => value,
+    })
+}
+
+/// Устройства ввода (`j/devices`)
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DevicesInfo {
+    #[serde(default)]
+    pub keyboards: Vec<KeyboardDevice>,
+}
+
+/// Клавиатура из `j/devices`
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct KeyboardDevice {
+    #[serde(default)]
+    pub address: String,
+    pub name: String,
+    #[serde(default)]
+    pub layout: String,
+    #[serde(default)]
+    pub variant: String,
+    #[serde(default)]
+    pub active_keymap: String,
+    /// Основная клавиатура (есть в новых версиях Hyprland)
+    #[serde(default)]
+    pub main: bool,
+    #[serde(default, rename = "capsLock")]
+    pub caps_lock: bool,
+}
+
+/// Биндинг (`j/binds`)
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct BindInfo {
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub mouse: bool,
+    #[serde(default)]
+    pub release: bool,
+    #[serde(default)]
+    pub repeat: bool,
+    #[serde(default)]
+    pub modmask: u32,
+    #[serde(default)]
+    pub submap: Option<String>,
+    pub key: String,
+    #[serde(default)]
+    pub keycode: i32,
+    #[serde(default)]
+    pub description: String,
+    pub dispatcher: String,
+    #[serde(default)]
+    pub arg: String,
+}
+
+impl BindInfo {
+    /// Модификаторы в виде строки `SUPER+CTRL+ALT+SHIFT`
+    pub fn modmask_to_string(&self) -> String {
+        let mut mods = Vec::new();
+
+        // Hyprland modmask bits:
+        // 1 = Shift, 4 = Ctrl, 8 = Alt, 64 = Super
+        if self.modmask & 64 != 0 {
+            mods.push("SUPER");
+        }
+        if self.modmask & 4 != 0 {
+            mods.push("CTRL");
+        }
+        if self.modmask & 8 != 0 {
+            mods.push("ALT");
+        }
+        if self.modmask & 1 != 0 {
+            mods.push("SHIFT");
+        }
+
+        mods.join("+")
+    }
+}
+
+/// Layer-surface'ы одного монитора (`j/layers`), ключ `levels` - уровень слоя (0..3)
+#[derive(Debug, Deserialize, Clone, Default)]
+#[allow(dead_code)]
+pub struct LayersInfo {
+    #[serde(default)]
+    pub levels: std::collections::HashMap<String, Vec<LayerInfo>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct LayerInfo {
+    pub address: String,
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default)]
+    pub w: i32,
+    #[serde(default)]
+    pub h: i32,
+    #[serde(default)]
+    pub namespace: String,
+    #[serde(default)]
+    pub pid: i32,
+}
+
+/// Правило workspace'а (`j/workspacerules`); отсутствующие поля - не заданы в конфиге
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceRule {
+    pub workspace_string: String,
+    #[serde(default)]
+    pub monitor: Option<String>,
+    #[serde(default)]
+    pub default: Option<bool>,
+    #[serde(default)]
+    pub persistent: Option<bool>,
+    #[serde(default)]
+    pub default_name: Option<String>,
+    #[serde(default)]
+    pub on_created_empty: Option<String>,
+}
+
+/// Описание монитора (`j/monitors`)
+#[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(not(feature = "ddc"), allow(dead_code))]
+pub struct MonitorDetails {
+    /// Имя коннектора (DP-1, eDP-1)
+    pub name: String,
+    #[serde(default)]
+    pub make: String,
+    #[serde(default)]
+    pub model: String,
+}
+
+/// Значение опции конфига (`j/getoption`); заполнено только поле соответствующего типа
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct OptionInfo {
+    pub option: String,
+    #[serde(default)]
+    pub int: Option<i64>,
+    #[serde(default)]
+    pub float: Option<f64>,
+    #[serde(default)]
+    pub str: Option<String>,
+    #[serde(default)]
+    pub custom: Option<String>,
+    #[serde(default)]
+    pub set: bool,
+}
+
 // System Tray models
 #[derive(Debug, Clone)]
 pub struct TrayItem {
@@ -90,13 +349,10 @@ pub enum DateTimeFormat {
     SystemLocale,
     /// Кастомный формат (strftime синтаксис)
     /// Например: "%Y-%m-%d %H:%M:%S"
-    #[allow(dead_code)]
     Custom(String),
     /// Только время в 24-часовом формате
-    #[allow(dead_code)]
     TimeOnly,
     /// Только дата в формате YYYY-MM-DD
-    #[allow(dead_code)]
     DateOnly,
 }
 
@@ -117,6 +373,66 @@ pub struct BatteryInfo {
     pub status: BatteryStatus,
     pub time_to_empty: Option<u32>, // minutes
     pub time_to_full: Option<u32>,  // minutes
+    pub kind: BatteryKind,
+    /// Модель устройства; для нескольких батарей ноутбука - через " + "
+    pub model: String,
+    /// Мощность заряда/разряда, Вт
+    pub energy_rate: Option<f64>,
+    /// Текущая ёмкость относительно паспортной, %
+    pub health: Option<u8>,
+}
+
+/// Тип устройства с батареей (UPower `Type`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryKind {
+    Battery,
+    Ups,
+    Mouse,
+    Keyboard,
+    Headset,
+    Headphones,
+    Phone,
+    Tablet,
+    GamingInput,
+    Pen,
+    Touchpad,
+    Other,
+}
+
+impl BatteryKind {
+    pub fn from_upower(kind: u32) -> Self {
+        match kind {
+            2 => BatteryKind::Battery,
+            3 => BatteryKind::Ups,
+            5 => BatteryKind::Mouse,
+            6 => BatteryKind::Keyboard,
+            8 => BatteryKind::Phone,
+            10 => BatteryKind::Tablet,
+            12 => BatteryKind::GamingInput,
+            13 => BatteryKind::Pen,
+            14 => BatteryKind::Touchpad,
+            17 => BatteryKind::Headset,
+            19 => BatteryKind::Headphones,
+            _ => BatteryKind::Other,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatteryKind::Battery => "Battery",
+            BatteryKind::Ups => "UPS",
+            BatteryKind::Mouse => "Mouse",
+            BatteryKind::Keyboard => "Keyboard",
+            BatteryKind::Headset => "Headset",
+            BatteryKind::Headphones => "Headphones",
+            BatteryKind::Phone => "Phone",
+            BatteryKind::Tablet => "Tablet",
+            BatteryKind::GamingInput => "Gamepad",
+            BatteryKind::Pen => "Pen",
+            BatteryKind::Touchpad => "Touchpad",
+            BatteryKind::Other => "Device",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -128,6 +444,49 @@ pub enum BatteryStatus {
     Unknown,
 }
 
+/// Профиль питания power-profiles-daemon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerProfile {
+    PowerSaver,
+    Balanced,
+    Performance,
+}
+
+impl PowerProfile {
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "power-saver" => Some(PowerProfile::PowerSaver),
+            "balanced" => Some(PowerProfile::Balanced),
+            "performance" => Some(PowerProfile::Performance),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            PowerProfile::PowerSaver => "power-saver",
+            PowerProfile::Balanced => "balanced",
+            PowerProfile::Performance => "performance",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PowerProfile::PowerSaver => "Power Saver",
+            PowerProfile::Balanced => "Balanced",
+            PowerProfile::Performance => "Performance",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            PowerProfile::PowerSaver => "󰌪",
+            PowerProfile::Balanced => "󰾅",
+            PowerProfile::Performance => "󰓅",
+        }
+    }
+}
+
 // Volume models
 #[derive(Debug, Clone, PartialEq)]
 pub struct VolumeInfo {
@@ -135,6 +494,80 @@ pub struct VolumeInfo {
     pub muted: bool,
 }
 
+/// Тип аудиоустройства
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioDeviceKind {
+    /// Устройство вывода (колонки, наушники)
+    Sink,
+    /// Устройство ввода (микрофон)
+    Source,
+}
+
+/// Аудиоустройство PipeWire
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioDevice {
+    pub id: u32,
+    /// Имя узла (`node.name`), используется для выбора устройства по умолчанию
+    pub name: String,
+    /// Человекочитаемое описание (`node.description`)
+    pub description: String,
+    pub kind: AudioDeviceKind,
+    pub is_default: bool,
+}
+
+/// Поток воспроизведения приложения
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioStream {
+    pub id: u32,
+    /// Имя приложения (`application.name`)
+    pub app_name: String,
+    /// Иконка или имя бинарника приложения
+    pub icon_name: Option<String>,
+    /// Что играет (`media.name`)
+    pub media_title: Option<String>,
+    pub volume: u8,      // 0-100
+    pub muted: bool,
+}
+
+/// Состояние микрофона (source по умолчанию)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicrophoneInfo {
+    pub volume: u8,      // 0-100
+    pub muted: bool,
+    /// Микрофон захватывает хотя бы одно приложение
+    pub in_use: bool,
+}
+
+// Media (MPRIS) models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+/// Медиаплеер, найденный через MPRIS
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaPlayer {
+    /// Имя на шине (`org.mpris.MediaPlayer2.*`)
+    pub bus_name: String,
+    /// Человекочитаемое имя плеера (`Identity`)
+    pub identity: String,
+    pub status: PlaybackStatus,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    /// `mpris:artUrl` (обычно file:// или https://)
+    pub art_url: Option<String>,
+    /// Длительность трека в микросекундах
+    pub length_us: Option<i64>,
+    /// Текущая позиция в микросекундах
+    pub position_us: i64,
+    pub can_go_next: bool,
+    pub can_go_previous: bool,
+    pub can_seek: bool,
+}
+
 // Notification models
 #[derive(Debug, Clone)]
 pub struct Notification {
@@ -146,6 +579,10 @@ pub struct Notification {
     pub urgency: NotificationUrgency,
     pub timestamp: std::time::SystemTime,
     pub actions: Vec<String>,
+    /// У уведомления есть картинка (загружается через NotificationService::get_image)
+    pub has_image: bool,
+    pub desktop_entry: Option<String>,
+    pub category: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -155,6 +592,16 @@ pub enum NotificationUrgency {
     Critical,
 }
 
+/// Фильтр истории уведомлений; пустые поля не ограничивают выборку
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFilter {
+    /// Полнотекстовый поиск по заголовку и тексту
+    pub query: String,
+    pub urgency: Option<NotificationUrgency>,
+    /// Только уведомления, пришедшие не раньше этого момента
+    pub since: Option<std::time::SystemTime>,
+}
+
 // Network models
 #[derive(Debug, Clone)]
 pub struct NetworkConnection {
@@ -200,6 +647,32 @@ pub struct SystemResources {
     pub memory_total_gb: f32,
 }
 
+// Brightness models
+/// Дисплей с регулируемой яркостью
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrightnessDisplay {
+    /// Идентификатор для `set_display_brightness` (например "backlight", "ddc:/dev/i2c-5")
+    pub id: String,
+    /// Название для интерфейса
+    pub name: String,
+    /// Имя коннектора, совпадающее с именем монитора Hyprland (eDP-1, DP-2)
+    pub connector: Option<String>,
+    /// Встроенная подсветка (изменения приходят через subscribe_brightness_changed)
+    pub internal: bool,
+    /// Яркость 0-100
+    pub brightness: u32,
+}
+
+// Night light models
+#[derive(Debug, Clone, PartialEq)]
+pub struct NightLightState {
+    pub enabled: bool,
+    /// Температура в кельвинах, применяемая при включении
+    pub temperature: u32,
+    /// Когда расписание переключит режим в следующий раз
+    pub next_change: Option<chrono::DateTime<chrono::Local>>,
+}
+
 // Keyboard layout models
 #[derive(Debug, Clone, PartialEq)]
 pub struct KeyboardLayout {
@@ -0,0 +1,25 @@
+use crate::domain::models::MediaPlayer;
+
+/// Trait для управления медиаплеерами (MPRIS)
+pub trait MprisService {
+    /// Возвращает все найденные плееры
+    fn list_players(&self) -> Vec<MediaPlayer>;
+
+    /// Возвращает активный плеер: выбранный пользователем или играющий сейчас
+    fn get_active_player(&self) -> Option<MediaPlayer>;
+
+    /// Выбирает активный плеер по имени на шине
+    fn set_active_player(&self, bus_name: &str);
+
+    /// Переключает воспроизведение/паузу активного плеера
+    fn play_pause(&self) -> Result<(), String>;
+
+    /// Следующий трек
+    fn next(&self) -> Result<(), String>;
+
+    /// Предыдущий трек
+    fn previous(&self) -> Result<(), String>;
+
+    /// Перематывает активный плеер на позицию (микросекунды)
+    fn set_position(&self, position_us: i64) -> Result<(), String>;
+}
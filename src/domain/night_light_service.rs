@@ -0,0 +1,13 @@
+use crate::domain::models::NightLightState;
+
+/// Сервис ночного режима (цветовая температура экрана)
+pub trait NightLightService: Send + Sync {
+    /// Текущее состояние
+    fn get_state(&self) -> NightLightState;
+
+    /// Включить/выключить ночной режим на всех мониторах
+    fn set_enabled(&self, enabled: bool) -> Result<(), String>;
+
+    /// Установить температуру в кельвинах (применяется сразу, если режим включён)
+    fn set_temperature(&self, kelvin: u32) -> Result<(), String>;
+}
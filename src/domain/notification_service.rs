@@ -1,4 +1,4 @@
-use crate::domain::models::Notification;
+use crate::domain::models::{Notification, NotificationFilter};
 
 /// Trait для работы с уведомлениями
 pub trait NotificationService: Send + Sync {
@@ -11,13 +11,28 @@ pub trait NotificationService: Send + Sync {
     /// Получает историю уведомлений
     fn get_history(&self) -> Vec<Notification>;
 
+    /// Ищет в истории с учётом фильтра
+    fn search_history(&self, filter: &NotificationFilter) -> Vec<Notification>;
+
+    /// Картинка уведомления (PNG)
+    fn get_image(&self, id: u32) -> Option<Vec<u8>>;
+
     /// Очищает историю уведомлений
     fn clear_history(&self);
 
     /// Удаляет конкретное уведомление из истории
     fn remove_notification(&self, id: u32);
 
+    /// Удаляет всю историю приложения
+    fn clear_app(&self, app_name: &str);
+
     /// Показать окно истории (через D-Bus)
     fn show_history_window(&self);
+
+    /// Включён ли режим «Не беспокоить» вручную
+    fn is_dnd_enabled(&self) -> bool;
+
+    /// Включает/выключает режим «Не беспокоить»
+    fn set_dnd_enabled(&self, enabled: bool);
 }
 
@@ -0,0 +1,13 @@
+use crate::domain::models::PowerProfile;
+
+/// Сервис профилей питания
+pub trait PowerProfileService: Send + Sync {
+    /// Профили, которые поддерживает система (пусто, если демон не запущен)
+    fn available_profiles(&self) -> Vec<PowerProfile>;
+
+    /// Текущий профиль
+    fn active_profile(&self) -> Option<PowerProfile>;
+
+    /// Переключить профиль
+    fn set_profile(&self, profile: PowerProfile) -> Result<(), String>;
+}
@@ -1,4 +1,4 @@
-use crate::domain::models::VolumeInfo;
+use crate::domain::models::{AudioDevice, AudioDeviceKind, AudioStream, MicrophoneInfo, VolumeInfo};
 
 /// Trait для управления громкостью системы
 pub trait VolumeService {
@@ -13,5 +13,31 @@ pub trait VolumeService {
 
     /// Устанавливает состояние mute
     fn set_mute(&self, muted: bool) -> Result<(), String>;
-}
 
+    /// Возвращает список устройств вывода или ввода
+    fn list_devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice>;
+
+    /// Делает устройство устройством по умолчанию (по `node.name`)
+    fn set_default_device(&self, kind: AudioDeviceKind, name: &str) -> Result<(), String>;
+
+    /// Возвращает состояние микрофона по умолчанию
+    fn get_microphone_info(&self) -> Option<MicrophoneInfo>;
+
+    /// Устанавливает уровень громкости микрофона (0-100)
+    fn set_microphone_volume(&self, volume: u8) -> Result<(), String>;
+
+    /// Переключает mute микрофона
+    fn toggle_microphone_mute(&self) -> Result<(), String>;
+
+    /// Устанавливает mute микрофона
+    fn set_microphone_mute(&self, muted: bool) -> Result<(), String>;
+
+    /// Возвращает активные потоки воспроизведения приложений
+    fn list_streams(&self) -> Vec<AudioStream>;
+
+    /// Устанавливает громкость потока (0-100)
+    fn set_stream_volume(&self, id: u32, volume: u8) -> Result<(), String>;
+
+    /// Устанавливает mute потока
+    fn set_stream_mute(&self, id: u32, muted: bool) -> Result<(), String>;
+}
@@ -1,13 +1,52 @@
-use crate::domain::models::{Monitor, Workspace};
+use crate::domain::models::{Monitor, WindowInfo, WorkspaceSnapshot};
+use crate::infrastructure::hyprland_client::HyprlandError;
 
 /// Trait для работы с workspace сервисом
+///
+/// Ошибки IPC возвращаются вызывающему: виджет сам решает, что показать.
 pub trait WorkspaceService {
-    fn get_monitors(&self) -> Vec<Monitor>;
-    fn get_workspaces(&self) -> Vec<Workspace>;
-    fn get_active_workspace(&self) -> i32;
-    fn get_active_monitor(&self) -> String;
-    fn get_active_workspace_for_monitor(&self, monitor_name: &str) -> Option<i32>;
-    fn get_active_window_title(&self) -> String;
-    fn switch_workspace(&self, id: i32);
-}
+    fn get_monitors(&self) -> Result<Vec<Monitor>, HyprlandError>;
+    /// Workspace'ы, мониторы и окна одним запросом - для перерисовки виджетов
+    fn get_snapshot(&self, monitor_name: &str) -> Result<WorkspaceSnapshot, HyprlandError>;
+    /// Активное окно (`j/activewindow`), None если фокуса нет
+    fn get_active_window(&self) -> Result<Option<WindowInfo>, HyprlandError>;
+    fn switch_workspace(&self, id: i32) -> Result<(), HyprlandError> {
+        self.dispatch("workspace", &id.to_string())
+    }
+    /// Переключение на именованный workspace (`name:<name>`)
+    fn switch_workspace_by_name(&self, name: &str) -> Result<(), HyprlandError> {
+        self.dispatch("workspace", &format!("name:{}", name))
+    }
+    /// Показать/скрыть special workspace (имя без префикса `special:`)
+    fn toggle_special_workspace(&self, name: &str) -> Result<(), HyprlandError> {
+        self.dispatch("togglespecialworkspace", name)
+    }
+
+    /// Выполнить произвольный dispatcher Hyprland
+    fn dispatch(&self, dispatcher: &str, args: &str) -> Result<(), HyprlandError>;
+
+    /// Переключиться на следующий/предыдущий открытый workspace (`e+N` / `e-N`)
+    fn cycle_workspace(&self, delta: i32) -> Result<(), HyprlandError> {
+        self.dispatch("workspace", &format!("e{:+}", delta))
+    }
+
+    /// Переместить активное окно на workspace, не переключаясь на него
+    fn move_focused_window_to_workspace(&self, target: &str) -> Result<(), HyprlandError> {
+        self.dispatch("movetoworkspacesilent", target)
+    }
 
+    /// Передать фокус окну (адрес в формате `0x...`)
+    fn focus_window(&self, address: &str) -> Result<(), HyprlandError> {
+        self.dispatch("focuswindow", &format!("address:{}", address))
+    }
+
+    /// Закрыть окно (адрес в формате `0x...`)
+    fn close_window(&self, address: &str) -> Result<(), HyprlandError> {
+        self.dispatch("closewindow", &format!("address:{}", address))
+    }
+
+    /// Переместить workspace на другой монитор
+    fn move_workspace_to_monitor(&self, target: &str, monitor_name: &str) -> Result<(), HyprlandError> {
+        self.dispatch("moveworkspacetomonitor", &format!("{} {}", target, monitor_name))
+    }
+}
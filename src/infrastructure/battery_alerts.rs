@@ -0,0 +1,154 @@
+use crate::config::{self, BatteryOptions, CriticalAction, PowerConfig};
+use crate::domain::models::{BatteryInfo, BatteryStatus};
+use crate::infrastructure::notification_client::NotificationClient;
+use zbus::{blocking::Connection, proxy};
+
+#[proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+trait LoginManager {
+    fn suspend(&self, interactive: bool) -> zbus::Result<()>;
+    fn hibernate(&self, interactive: bool) -> zbus::Result<()>;
+    fn hybrid_sleep(&self, interactive: bool) -> zbus::Result<()>;
+    fn power_off(&self, interactive: bool) -> zbus::Result<()>;
+}
+
+/// Уровень разряда, о котором уже предупредили
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AlertLevel {
+    Normal,
+    Low,
+    Critical,
+    Action,
+}
+
+/// Уведомления о низком заряде и действие при критическом разряде
+///
+/// Каждый порог срабатывает один раз за разрядку. Пороги low/critical - те же,
+/// что у виджета Battery, действие - из секции `power`; читаются при каждой проверке.
+pub struct BatteryAlerts {
+    level: AlertLevel,
+    /// id последнего уведомления, чтобы заменять его, а не плодить новые
+    notification_id: u32,
+}
+
+impl BatteryAlerts {
+    pub fn new() -> Self {
+        Self {
+            level: AlertLevel::Normal,
+            notification_id: 0,
+        }
+    }
+
+    /// Проверяет заряд после очередного изменения батареи
+    pub fn check(&mut self, info: Option<&BatteryInfo>) {
+        let Some(info) = info else {
+            return;
+        };
+
+        if info.status != BatteryStatus::Discharging {
+            self.level = AlertLevel::Normal;
+            return;
+        }
+
+        let (config, battery) = {
+            let guard = config::get_config().read().unwrap();
+            (guard.power.clone(), guard.battery_options())
+        };
+        let level = Self::level_for(&config, &battery, info.percentage);
+        if level <= self.level {
+            // Заряд вырос без зарядки (калибровка) - позволяем сработать снова
+            self.level = level;
+            return;
+        }
+        self.level = level;
+
+        match level {
+            AlertLevel::Normal => {}
+            AlertLevel::Low => {
+                self.notify(&config, "Battery low", &remaining_text(info), false);
+            }
+            AlertLevel::Critical => {
+                self.notify(&config, "Battery critically low", &remaining_text(info), true);
+            }
+            AlertLevel::Action => {
+                let Some(action) = action_name(config.critical_action) else {
+                    self.notify(&config, "Battery critically low", &remaining_text(info), true);
+                    return;
+                };
+                self.notify(
+                    &config,
+                    "Battery critically low",
+                    &format!("{}% left, {} now", info.percentage, action),
+                    true,
+                );
+                run_action(config.critical_action);
+            }
+        }
+    }
+
+    fn level_for(config: &PowerConfig, battery: &BatteryOptions, percentage: u8) -> AlertLevel {
+        if config.critical_action != CriticalAction::None && percentage <= config.action_threshold {
+            AlertLevel::Action
+        } else if percentage <= battery.critical_threshold {
+            AlertLevel::Critical
+        } else if percentage <= battery.low_threshold {
+            AlertLevel::Low
+        } else {
+            AlertLevel::Normal
+        }
+    }
+
+    fn notify(&mut self, config: &PowerConfig, summary: &str, body: &str, critical: bool) {
+        eprintln!("[Battery] {}: {}", summary, body);
+        if !config.notify {
+            return;
+        }
+
+        match NotificationClient::new().and_then(|client| client.send(summary, body, critical, self.notification_id)) {
+            Ok(id) => self.notification_id = id,
+            Err(e) => eprintln!("[Battery] ✗ {}", e),
+        }
+    }
+}
+
+fn action_name(action: CriticalAction) -> Option<&'static str> {
+    match action {
+        CriticalAction::None => None,
+        CriticalAction::Suspend => Some("suspending"),
+        CriticalAction::Hibernate => Some("hibernating"),
+        CriticalAction::HybridSleep => Some("suspending"),
+        CriticalAction::PowerOff => Some("powering off"),
+    }
+}
+
+/// Выполняет действие через logind в отдельном потоке
+fn run_action(action: CriticalAction) {
+    std::thread::spawn(move || {
+        let result = Connection::system()
+            .and_then(|conn| {
+                let manager = LoginManagerProxyBlocking::new(&conn)?;
+                match action {
+                    CriticalAction::None => Ok(()),
+                    CriticalAction::Suspend => manager.suspend(false),
+                    CriticalAction::Hibernate => manager.hibernate(false),
+                    CriticalAction::HybridSleep => manager.hybrid_sleep(false),
+                    CriticalAction::PowerOff => manager.power_off(false),
+                }
+            });
+
+        match result {
+            Ok(()) => eprintln!("[Battery] ✓ Critical action {:?} requested", action),
+            Err(e) => eprintln!("[Battery] ✗ Critical action {:?} failed: {}", action, e),
+        }
+    });
+}
+
+fn remaining_text(info: &BatteryInfo) -> String {
+    match info.time_to_empty {
+        Some(minutes) => format!("{}% left ({}h {:02}m)", info.percentage, minutes / 60, minutes % 60),
+        None => format!("{}% left", info.percentage),
+    }
+}
@@ -0,0 +1,84 @@
+use crate::config::{reload_config, HyprlineConfig};
+use crate::infrastructure::notification_client::NotificationClient;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+/// Следит за config.json и применяет изменения, сделанные вне окна настроек
+///
+/// Ошибки разбора показываются уведомлением, текущая конфигурация при этом не меняется.
+pub fn start_config_watching() {
+    let config_path = HyprlineConfig::config_path();
+
+    thread::spawn(move || {
+        let (notify_tx, notify_rx) = channel::<Result<Event, notify::Error>>();
+
+        let mut watcher = match notify::recommended_watcher(notify_tx) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("[ConfigWatcher] ✗ Failed to create watcher: {}", e);
+                return;
+            }
+        };
+
+        // Следим за директорией, т.к. некоторые редакторы пересоздают файл
+        let Some(config_dir) = config_path.parent() else {
+            return;
+        };
+        if let Err(e) = std::fs::create_dir_all(config_dir) {
+            eprintln!("[ConfigWatcher] ✗ Failed to create config directory: {}", e);
+            return;
+        }
+        if let Err(e) = watcher.watch(config_dir, RecursiveMode::NonRecursive) {
+            eprintln!("[ConfigWatcher] ✗ Failed to watch config: {}", e);
+            return;
+        }
+
+        eprintln!("[ConfigWatcher] ✓ Watching config changes: {:?}", config_path);
+
+        // id уведомления об ошибке, чтобы повторные ошибки заменяли его, а не копились
+        let mut error_notification_id = 0;
+
+        while let Ok(res) = notify_rx.recv() {
+            let event = match res {
+                Ok(event) => event,
+                Err(e) => {
+                    eprintln!("[ConfigWatcher] Watch error: {}", e);
+                    continue;
+                }
+            };
+
+            // Проверяем, что событие касается нашего файла
+            let is_our_file = event.paths.iter().any(|p| p.file_name() == config_path.file_name());
+            if !is_our_file || !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                continue;
+            }
+
+            // Редакторы пишут файл в несколько приёмов - ждём и схлопываем пачку событий
+            thread::sleep(Duration::from_millis(100));
+            while notify_rx.try_recv().is_ok() {}
+
+            match reload_config() {
+                Ok(true) => eprintln!("[ConfigWatcher] ✓ Config reloaded"),
+                Ok(false) => {}
+                Err(e) => {
+                    eprintln!("[ConfigWatcher] ✗ Config not applied: {}", e);
+                    error_notification_id = notify_error(&e, error_notification_id);
+                }
+            }
+        }
+    });
+}
+
+/// Показывает уведомление об ошибке в конфигурации, возвращает его id
+fn notify_error(error: &str, replaces_id: u32) -> u32 {
+    let result = NotificationClient::new().and_then(|client| {
+        client.send("Hyprline: config error", &format!("Changes were not applied.\n{}", error), true, replaces_id)
+    });
+
+    result.unwrap_or_else(|e| {
+        eprintln!("[ConfigWatcher] ✗ {}", e);
+        replaces_id
+    })
+}
@@ -0,0 +1,194 @@
+use crate::domain::models::BrightnessDisplay;
+use crate::infrastructure::display_brightness::is_internal_connector;
+use crate::infrastructure::hyprland_client::get_hyprland_client;
+use async_channel::Sender;
+use ddc::Ddc;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Arc;
+
+/// Префикс id внешних мониторов в `list_displays`
+pub const DDC_PREFIX: &str = "ddc:";
+
+const DRM_DIR: &str = "/sys/class/drm";
+
+/// VCP код яркости (Luminance) по MCCS
+const VCP_BRIGHTNESS: u8 = 0x10;
+
+struct DdcMonitor {
+    display: BrightnessDisplay,
+    device: PathBuf,
+    /// Максимальное значение VCP 0x10, которое сообщил монитор
+    maximum: u16,
+}
+
+/// Яркость внешних мониторов по DDC/CI через `/dev/i2c-*`
+///
+/// Обмен по DDC занимает десятки миллисекунд, поэтому значения кешируются,
+/// а запись идёт в отдельном потоке - при перетаскивании слайдера отправляется
+/// только последнее значение.
+pub struct DdcBrightness {
+    monitors: Mutex<Vec<DdcMonitor>>,
+    writer: Mutex<Option<mpsc::Sender<(PathBuf, u16)>>>,
+}
+
+impl DdcBrightness {
+    pub fn new() -> Self {
+        Self {
+            monitors: Mutex::new(Vec::new()),
+            writer: Mutex::new(None),
+        }
+    }
+
+    /// Опрашивает мониторы в фоне и запускает поток записи
+    pub fn start_detection(self: Arc<Self>, tx: Sender<()>) {
+        let (write_tx, write_rx) = mpsc::channel::<(PathBuf, u16)>();
+        *self.writer.lock() = Some(write_tx);
+
+        std::thread::spawn(move || {
+            let monitors = detect_monitors();
+            eprintln!("[Brightness] ✓ Found {} DDC/CI monitor(s)", monitors.len());
+            *self.monitors.lock() = monitors;
+            let _ = tx.send_blocking(());
+        });
+
+        std::thread::spawn(move || {
+            while let Ok(first) = write_rx.recv() {
+                // Схлопываем накопившиеся значения: для каждого монитора - последнее
+                let mut pending: HashMap<PathBuf, u16> = HashMap::new();
+                pending.insert(first.0, first.1);
+                while let Ok((device, value)) = write_rx.try_recv() {
+                    pending.insert(device, value);
+                }
+
+                for (device, value) in pending {
+                    if let Err(e) = write_brightness(&device, value) {
+                        eprintln!("[Brightness] ✗ DDC write to {:?} failed: {}", device, e);
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn list_displays(&self) -> Vec<BrightnessDisplay> {
+        self.monitors.lock().iter().map(|m| m.display.clone()).collect()
+    }
+
+    pub fn set_brightness(&self, id: &str, value: u32) -> Result<(), String> {
+        let value = value.min(100);
+        let mut monitors = self.monitors.lock();
+        let monitor = monitors
+            .iter_mut()
+            .find(|m| m.display.id == id)
+            .ok_or_else(|| format!("Unknown display: {}", id))?;
+
+        let raw = ((value as f64 / 100.0) * monitor.maximum as f64).round() as u16;
+        let writer = self.writer.lock();
+        let writer = writer.as_ref().ok_or("DDC writer is not running")?;
+        writer
+            .send((monitor.device.clone(), raw))
+            .map_err(|_| "DDC writer stopped".to_string())?;
+
+        monitor.display.brightness = value;
+        Ok(())
+    }
+}
+
+/// Внешние мониторы с DDC/CI: i2c-шина берётся из DRM коннектора,
+/// поэтому имя совпадает с именем монитора в Hyprland
+fn detect_monitors() -> Vec<DdcMonitor> {
+    let Ok(entries) = fs::read_dir(DRM_DIR) else {
+        return Vec::new();
+    };
+
+    // Названия мониторов из Hyprland: "Dell Inc. DELL U2720Q"
+    let names: HashMap<String, String> = get_hyprland_client()
+        .monitors()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| (m.name, format!("{} {}", m.make, m.model).trim().to_string()))
+        .collect();
+
+    let mut monitors = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some((_, connector)) = file_name.split_once('-') else {
+            continue;
+        };
+        if is_internal_connector(connector) {
+            continue;
+        }
+        let connected = fs::read_to_string(path.join("status"))
+            .is_ok_and(|status| status.trim() == "connected");
+        if !connected {
+            continue;
+        }
+        let Some(device) = i2c_device(&path) else {
+            continue;
+        };
+
+        match read_brightness(&device) {
+            Ok((value, maximum)) if maximum > 0 => {
+                let name = names
+                    .get(connector)
+                    .filter(|name| !name.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| connector.to_string());
+
+                eprintln!("[Brightness] ✓ DDC/CI monitor {} ({}) on {:?}", connector, name, device);
+                monitors.push(DdcMonitor {
+                    display: BrightnessDisplay {
+                        id: format!("{}{}", DDC_PREFIX, device.display()),
+                        name,
+                        connector: Some(connector.to_string()),
+                        internal: false,
+                        brightness: ((value as f64 / maximum as f64) * 100.0).round() as u32,
+                    },
+                    device,
+                    maximum,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("[Brightness] {} does not support DDC/CI brightness: {}", connector, e),
+        }
+    }
+
+    monitors.sort_by(|a, b| a.display.connector.cmp(&b.display.connector));
+    monitors
+}
+
+/// `/dev/i2c-N` коннектора: ссылка `ddc` или вложенный `i2c-N` (DisplayPort AUX)
+fn i2c_device(connector_path: &Path) -> Option<PathBuf> {
+    let bus = fs::canonicalize(connector_path.join("ddc"))
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .or_else(|| {
+            fs::read_dir(connector_path).ok()?.flatten().find_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with("i2c-").then_some(name)
+            })
+        })?;
+
+    Some(PathBuf::from("/dev").join(bus))
+}
+
+fn read_brightness(device: &Path) -> Result<(u16, u16), String> {
+    let mut ddc = ddc_i2c::from_i2c_device(device)
+        .map_err(|e| format!("Failed to open {:?}: {}", device, e))?;
+    let value = ddc
+        .get_vcp_feature(VCP_BRIGHTNESS)
+        .map_err(|e| format!("Failed to read brightness: {}", e))?;
+    Ok((value.value(), value.maximum()))
+}
+
+fn write_brightness(device: &Path, value: u16) -> Result<(), String> {
+    let mut ddc = ddc_i2c::from_i2c_device(device)
+        .map_err(|e| format!("Failed to open {:?}: {}", device, e))?;
+    ddc.set_vcp_feature(VCP_BRIGHTNESS, value)
+        .map_err(|e| format!("Failed to set brightness: {}", e))
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Индекс .desktop файлов: класс окна (в нижнем регистре) -> значение `Icon=`
+static ICON_INDEX: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Ищет иконку приложения по классу окна Hyprland
+///
+/// Класс сопоставляется с `StartupWMClass`, именем .desktop файла
+/// и последним компонентом reverse-DNS имени (`org.gnome.Nautilus` -> `nautilus`).
+/// Возвращает имя иконки из темы или абсолютный путь к файлу.
+pub fn find_icon_for_class(class: &str) -> Option<String> {
+    if class.is_empty() {
+        return None;
+    }
+
+    ICON_INDEX
+        .get_or_init(build_icon_index)
+        .get(&class.to_lowercase())
+        .cloned()
+}
+
+/// Каталоги applications/ в порядке приоритета XDG
+fn application_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".local/share")
+        });
+    dirs.push(data_home.join("applications"));
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+    for dir in data_dirs.split(':').filter(|d| !d.is_empty()) {
+        dirs.push(PathBuf::from(dir).join("applications"));
+    }
+
+    dirs
+}
+
+fn build_icon_index() -> HashMap<String, String> {
+    let mut index = HashMap::new();
+
+    // Обходим в обратном порядке, чтобы каталоги с большим приоритетом перезаписывали остальные
+    for dir in application_dirs().iter().rev() {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("desktop") {
+                continue;
+            }
+
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let Some((icon, wm_class)) = parse_desktop_entry(&content) else {
+                continue;
+            };
+
+            if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                let stem = stem.to_lowercase();
+                if let Some(last) = stem.rsplit('.').next() {
+                    index.entry(last.to_string()).or_insert_with(|| icon.clone());
+                }
+                index.insert(stem, icon.clone());
+            }
+
+            // StartupWMClass точнее имени файла, поэтому пишем его последним
+            if let Some(wm_class) = wm_class {
+                index.insert(wm_class.to_lowercase(), icon);
+            }
+        }
+    }
+
+    eprintln!("[DesktopEntries] ✓ Indexed {} application icons", index.len());
+    index
+}
+
+/// Возвращает (`Icon`, `StartupWMClass`) из секции `[Desktop Entry]`
+fn parse_desktop_entry(content: &str) -> Option<(String, Option<String>)> {
+    let mut in_main_section = false;
+    let mut icon = None;
+    let mut wm_class = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_main_section = line == "[Desktop Entry]";
+            continue;
+        }
+
+        if !in_main_section {
+            continue;
+        }
+
+        if let Some(value) = line.strip_prefix("Icon=") {
+            icon = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("StartupWMClass=") {
+            wm_class = Some(value.trim().to_string());
+        }
+    }
+
+    icon.filter(|i| !i.is_empty()).map(|icon| (icon, wm_class))
+}
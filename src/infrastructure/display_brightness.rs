@@ -0,0 +1,206 @@
+use crate::domain::brightness_service::BrightnessService;
+use crate::domain::models::BrightnessDisplay;
+use async_channel::Sender;
+use parking_lot::Mutex;
+use std::fs;
+use std::sync::Arc;
+
+#[cfg(feature = "ddc")]
+use crate::infrastructure::ddc_brightness::{DdcBrightness, DDC_PREFIX};
+
+/// Идентификатор встроенной подсветки в `list_displays`
+pub const INTERNAL_DISPLAY_ID: &str = "backlight";
+
+const DRM_DIR: &str = "/sys/class/drm";
+
+/// Имя подключённого встроенного дисплея (eDP/LVDS/DSI) - так же его называет Hyprland
+pub fn internal_connector() -> Option<String> {
+    drm_connectors()
+        .into_iter()
+        .find(|name| is_internal_connector(name))
+}
+
+pub fn is_internal_connector(name: &str) -> bool {
+    ["eDP", "LVDS", "DSI"].iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Подключённые DRM коннекторы: (`card1-DP-2` -> `DP-2`)
+pub fn drm_connectors() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(DRM_DIR) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| {
+            fs::read_to_string(entry.path().join("status"))
+                .is_ok_and(|status| status.trim() == "connected")
+        })
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.split_once('-').map(|(_, connector)| connector.to_string())
+        })
+        .collect()
+}
+
+/// Встроенная подсветка как элемент `list_displays`
+pub fn internal_display(brightness: u32) -> BrightnessDisplay {
+    BrightnessDisplay {
+        id: INTERNAL_DISPLAY_ID.to_string(),
+        name: "Built-in display".to_string(),
+        connector: internal_connector(),
+        internal: true,
+        brightness,
+    }
+}
+
+/// Все дисплеи: встроенная подсветка (Lumen или sysfs) и внешние мониторы по DDC/CI
+///
+/// Без встроенной подсветки основным дисплеем считается первый внешний монитор.
+pub struct DisplayBrightnessService {
+    internal: Option<Arc<dyn BrightnessService + Send + Sync>>,
+    #[cfg(feature = "ddc")]
+    ddc: Arc<DdcBrightness>,
+    /// Для изменений основного дисплея, если это внешний монитор
+    #[cfg_attr(not(feature = "ddc"), allow(dead_code))]
+    callback: Arc<Mutex<Option<Arc<dyn Fn(u32) + Send + Sync>>>>,
+}
+
+impl DisplayBrightnessService {
+    pub fn new(internal: Option<Arc<dyn BrightnessService + Send + Sync>>) -> Self {
+        Self {
+            internal,
+            #[cfg(feature = "ddc")]
+            ddc: Arc::new(DdcBrightness::new()),
+            callback: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Ищет внешние мониторы в фоне (DDC/CI медленный); `tx` - когда список готов
+    pub fn start_external_detection(&self, tx: Sender<()>) {
+        #[cfg(feature = "ddc")]
+        self.ddc.clone().start_detection(tx);
+
+        #[cfg(not(feature = "ddc"))]
+        drop(tx);
+    }
+
+    fn external_displays(&self) -> Vec<BrightnessDisplay> {
+        #[cfg(feature = "ddc")]
+        let displays = self.ddc.list_displays();
+        #[cfg(not(feature = "ddc"))]
+        let displays = Vec::new();
+        displays
+    }
+
+    /// Основной внешний монитор, если встроенной подсветки нет
+    fn primary_external(&self) -> Option<BrightnessDisplay> {
+        if self.internal.is_some() {
+            return None;
+        }
+        self.external_displays().into_iter().next()
+    }
+
+    fn no_display() -> String {
+        "No display with adjustable brightness".to_string()
+    }
+}
+
+impl BrightnessService for DisplayBrightnessService {
+    fn list_displays(&self) -> Vec<BrightnessDisplay> {
+        let mut displays = self
+            .internal
+            .as_ref()
+            .map(|internal| internal.list_displays())
+            .unwrap_or_default();
+        displays.extend(self.external_displays());
+        displays
+    }
+
+    fn set_display_brightness(&self, id: &str, value: u32) -> Result<(), String> {
+        #[cfg(feature = "ddc")]
+        if id.starts_with(DDC_PREFIX) {
+            self.ddc.set_brightness(id, value)?;
+
+            // Внешний монитор - основной: сообщаем об изменении, как это делает подсветка
+            if self.primary_external().is_some_and(|d| d.id == id) {
+                if let Some(cb) = self.callback.lock().as_ref() {
+                    cb(value.min(100));
+                }
+            }
+            return Ok(());
+        }
+
+        match &self.internal {
+            Some(internal) => internal.set_display_brightness(id, value),
+            None => Err(format!("Unknown display: {}", id)),
+        }
+    }
+
+    fn get_brightness(&self) -> Result<u32, String> {
+        if let Some(internal) = &self.internal {
+            return internal.get_brightness();
+        }
+        self.primary_external()
+            .map(|d| d.brightness)
+            .ok_or_else(Self::no_display)
+    }
+
+    fn set_brightness(&self, value: u32) -> Result<(), String> {
+        if let Some(internal) = &self.internal {
+            return internal.set_brightness(value);
+        }
+        let display = self.primary_external().ok_or_else(Self::no_display)?;
+        self.set_display_brightness(&display.id, value)
+    }
+
+    fn increase_brightness(&self, percent: u32) -> Result<(), String> {
+        if let Some(internal) = &self.internal {
+            return internal.increase_brightness(percent);
+        }
+        let current = self.get_brightness()?;
+        self.set_brightness((current + percent).min(100))
+    }
+
+    fn decrease_brightness(&self, percent: u32) -> Result<(), String> {
+        if let Some(internal) = &self.internal {
+            return internal.decrease_brightness(percent);
+        }
+        let current = self.get_brightness()?;
+        self.set_brightness(current.saturating_sub(percent))
+    }
+
+    fn supports_auto_adjustment(&self) -> bool {
+        self.internal
+            .as_ref()
+            .is_some_and(|internal| internal.supports_auto_adjustment())
+    }
+
+    fn enable_auto_adjustment(&self) -> Result<(), String> {
+        match &self.internal {
+            Some(internal) => internal.enable_auto_adjustment(),
+            None => Err("Auto adjustment is not supported for external monitors".to_string()),
+        }
+    }
+
+    fn disable_auto_adjustment(&self) -> Result<(), String> {
+        match &self.internal {
+            Some(internal) => internal.disable_auto_adjustment(),
+            None => Ok(()),
+        }
+    }
+
+    fn is_auto_adjustment_enabled(&self) -> Result<bool, String> {
+        match &self.internal {
+            Some(internal) => internal.is_auto_adjustment_enabled(),
+            None => Ok(false),
+        }
+    }
+
+    fn subscribe_brightness_changed(&self, callback: Arc<dyn Fn(u32) + Send + Sync>) {
+        if let Some(internal) = &self.internal {
+            internal.subscribe_brightness_changed(callback.clone());
+        }
+        *self.callback.lock() = Some(callback);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::domain::models::{
+    BindInfo, DevicesInfo, LayersInfo, MonitorDetails, OptionInfo, WindowInfo, WorkspaceRule,
+};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Разделитель ответов в batch-запросе
+const BATCH_RESPONSE_SEPARATOR: &str = "\n\n\n";
+
+/// Ошибка обращения к Hyprland IPC
+#[derive(Debug)]
+pub enum HyprlandError {
+    /// Не найден каталог экземпляра Hyprland (Hyprland не запущен?)
+    SocketNotFound,
+    /// Ошибка ввода-вывода при работе с сокетом
+    Io(std::io::Error),
+    /// Ответ на `j/` запрос не удалось разобрать
+    Parse { command: String, source: serde_json::Error },
+    /// Hyprland вернул ошибку выполнения команды
+    Command { command: String, response: String },
+}
+
+impl fmt::Display for HyprlandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HyprlandError::SocketNotFound => write!(f, "Hyprland socket not found"),
+            HyprlandError::Io(e) => write!(f, "Hyprland IPC I/O error: {}", e),
+            HyprlandError::Parse { command, source } => {
+                write!(f, "Failed to parse response to '{}': {}", command, source)
+            }
+            HyprlandError::Command { command, response } => {
+                write!(f, "Hyprland rejected '{}': {}", command, response.trim())
+            }
+        }
+    }
+}
+
+impl std::error::Error for HyprlandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HyprlandError::Io(e) => Some(e),
+            HyprlandError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for HyprlandError {
+    fn from(e: std::io::Error) -> Self {
+        HyprlandError::Io(e)
+    }
+}
+
+/// Клиент IPC Hyprland (`.socket.sock`)
+///
+/// Каталог экземпляра определяется один раз при создании клиента.
+pub struct HyprlandClient {
+    instance_dir: Option<PathBuf>,
+}
+
+impl HyprlandClient {
+    fn new() -> Self {
+        let instance_dir = find_instance_dir();
+
+        match &instance_dir {
+            Some(dir) => eprintln!("[HyprlandClient] ✓ Using instance {:?}", dir),
+            None => eprintln!("[HyprlandClient] ✗ Hyprland instance not found"),
+        }
+
+        Self { instance_dir }
+    }
+
+    /// Путь к управляющему сокету (`.socket.sock`)
+    pub fn control_socket(&self) -> Result<PathBuf, HyprlandError> {
+        self.socket(".socket.sock")
+    }
+
+    /// Путь к сокету событий (`.socket2.sock`)
+    pub fn event_socket(&self) -> Result<PathBuf, HyprlandError> {
+        self.socket(".socket2.sock")
+    }
+
+    /// Путь к сокету hyprsunset (`.hyprsunset.sock`)
+    pub fn hyprsunset_socket(&self) -> Result<PathBuf, HyprlandError> {
+        self.socket(".hyprsunset.sock")
+    }
+
+    fn socket(&self, name: &str) -> Result<PathBuf, HyprlandError> {
+        self.instance_dir
+            .as_ref()
+            .map(|dir| dir.join(name))
+            .ok_or(HyprlandError::SocketNotFound)
+    }
+
+    /// Отправляет сырую команду и возвращает ответ как есть
+    pub fn request(&self, command: &str) -> Result<String, HyprlandError> {
+        let mut stream = UnixStream::connect(self.control_socket()?)?;
+        stream.write_all(command.as_bytes())?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+
+        Ok(String::from_utf8_lossy(&response).into_owned())
+    }
+
+    /// Выполняет несколько команд за одно подключение (`[[BATCH]]`)
+    ///
+    /// Возвращает ответы в том же порядке, что и команды.
+    pub fn batch(&self, commands: &[&str]) -> Result<Vec<String>, HyprlandError> {
+        if commands.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self.request(&format!("[[BATCH]]{}", commands.join(";")))?;
+        Ok(split_batch_response(&response, commands.len()))
+    }
+
+    /// Выполняет JSON-запрос (`j/<what>`) и разбирает ответ
+    pub fn query<T: DeserializeOwned>(&self, what: &str) -> Result<T, HyprlandError> {
+        let command = format!("j/{}", what);
+        let response = self.request(&command)?;
+        parse_response(&command, &response)
+    }
+
+    /// Выполняет dispatcher (`dispatch <name> <args>`)
+    pub fn dispatch(&self, dispatcher: &str, args: &str) -> Result<(), HyprlandError> {
+        let command = format!("dispatch {} {}", dispatcher, args);
+        let response = self.request(command.trim_end())?;
+        check_ok(&command, &response)
+    }
+
+    /// Асинхронный вариант `request` для кода, работающего в tokio
+    #[allow(dead_code)]
+    pub async fn request_async(&self, command: &str) -> Result<String, HyprlandError> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut stream = tokio::net::UnixStream::connect(self.control_socket()?).await?;
+        stream.write_all(command.as_bytes()).await?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+
+        Ok(String::from_utf8_lossy(&response).into_owned())
+    }
+
+    /// Асинхронный вариант `query`
+    #[allow(dead_code)]
+    pub async fn query_async<T: DeserializeOwned>(&self, what: &str) -> Result<T, HyprlandError> {
+        let command = format!("j/{}", what);
+        let response = self.request_async(&command).await?;
+        parse_response(&command, &response)
+    }
+
+    /// Асинхронный вариант `dispatch`
+    #[allow(dead_code)]
+    pub async fn dispatch_async(&self, dispatcher: &str, args: &str) -> Result<(), HyprlandError> {
+        let command = format!("dispatch {} {}", dispatcher, args);
+        let response = self.request_async(command.trim_end()).await?;
+        check_ok(&command, &response)
+    }
+
+    // === Типизированные запросы ===
+
+    /// Все окна (`j/clients`)
+    pub fn clients(&self) -> Result<Vec<WindowInfo>, HyprlandError> {
+        self.query("clients")
+    }
+
+    /// Активное окно (`j/activewindow`), `None` если фокуса нет
+    pub fn active_window(&self) -> Result<Option<WindowInfo>, HyprlandError> {
+        let value: serde_json::Value = self.query("activewindow")?;
+
+        // Без активного окна Hyprland возвращает пустой объект
+        if value.as_object().is_some_and(|o| o.is_empty()) {
+            return Ok(None);
+        }
+
+        serde_json::from_value(value)
+            .map(Some)
+            .map_err(|source| HyprlandError::Parse { command: "j/activewindow".to_string(), source })
+    }
+
+    /// Мониторы (`j/monitors`)
+    #[cfg_attr(not(feature = "ddc"), allow(dead_code))]
+    pub fn monitors(&self) -> Result<Vec<MonitorDetails>, HyprlandError> {
+        self.query("monitors")
+    }
+
+    /// Устройства ввода (`j/devices`)
+    pub fn devices(&self) -> Result<DevicesInfo, HyprlandError> {
+        self.query("devices")
+    }
+
+    /// Биндинги (`j/binds`)
+    pub fn binds(&self) -> Result<Vec<BindInfo>, HyprlandError> {
+        self.query("binds")
+    }
+
+    /// Layer-surface'ы по мониторам (`j/layers`)
+    #[allow(dead_code)]
+    pub fn layers(&self) -> Result<HashMap<String, LayersInfo>, HyprlandError> {
+        self.query("layers")
+    }
+
+    /// Правила workspace'ов (`j/workspacerules`)
+    #[allow(dead_code)]
+    pub fn workspace_rules(&self) -> Result<Vec<WorkspaceRule>, HyprlandError> {
+        self.query("workspacerules")
+    }
+
+    /// Значение опции конфига (`j/getoption <name>`)
+    #[allow(dead_code)]
+    pub fn option(&self, name: &str) -> Result<OptionInfo, HyprlandError> {
+        self.query(&format!("getoption {}", name))
+    }
+}
+
+/// Разбирает ответ на `j/` запрос (в том числе один из ответов `batch`)
+pub fn parse_response<T: DeserializeOwned>(command: &str, response: &str) -> Result<T, HyprlandError> {
+    serde_json::from_str(response).map_err(|source| {
+        // На неизвестную команду Hyprland отвечает текстом, а не JSON
+        if !response.trim_start().starts_with(['{', '[']) {
+            HyprlandError::Command { command: command.to_string(), response: response.to_string() }
+        } else {
+            HyprlandError::Parse { command: command.to_string(), source }
+        }
+    })
+}
+
+fn check_ok(command: &str, response: &str) -> Result<(), HyprlandError> {
+    if response.trim() == "ok" {
+        Ok(())
+    } else {
+        Err(HyprlandError::Command { command: command.to_string(), response: response.to_string() })
+    }
+}
+
+fn split_batch_response(response: &str, count: usize) -> Vec<String> {
+    let mut parts: Vec<String> = response
+        .splitn(count, BATCH_RESPONSE_SEPARATOR)
+        .map(|s| s.to_string())
+        .collect();
+    parts.resize(count, String::new());
+    parts
+}
+
+/// Ищет каталог экземпляра Hyprland (`$XDG_RUNTIME_DIR/hypr/<sig>` или `/tmp/hypr/<sig>`)
+fn find_instance_dir() -> Option<PathBuf> {
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").ok();
+
+    if let Ok(sig) = std::env::var("HYPRLAND_INSTANCE_SIGNATURE") {
+        if let Some(runtime_dir) = &runtime_dir {
+            let dir = Path::new(runtime_dir).join("hypr").join(&sig);
+            if dir.join(".socket.sock").exists() {
+                return Some(dir);
+            }
+        }
+        return Some(Path::new("/tmp/hypr").join(&sig));
+    }
+
+    // Сигнатура не задана (например, запуск не из сессии Hyprland) - берём первый найденный экземпляр
+    let mut search_dirs = Vec::new();
+    if let Some(runtime_dir) = &runtime_dir {
+        search_dirs.push(Path::new(runtime_dir).join("hypr"));
+    }
+    search_dirs.push(PathBuf::from("/tmp/hypr"));
+
+    search_dirs.into_iter().find_map(|dir| {
+        std::fs::read_dir(dir).ok()?.flatten().map(|entry| entry.path()).find(|path| {
+            path.join(".socket.sock").exists()
+        })
+    })
+}
+
+static HYPRLAND_CLIENT: OnceLock<HyprlandClient> = OnceLock::new();
+
+/// Получить глобальный клиент Hyprland IPC
+pub fn get_hyprland_client() -> &'static HyprlandClient {
+    HYPRLAND_CLIENT.get_or_init(HyprlandClient::new)
+}
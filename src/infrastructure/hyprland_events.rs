@@ -0,0 +1,480 @@
+use crate::infrastructure::hyprland_client::get_hyprland_client;
+use async_channel::{Receiver, Sender};
+use std::io::{BufRead, BufReader};
+use std::os::unix::net::UnixStream;
+use std::sync::{Mutex, Once, OnceLock};
+use std::thread;
+
+/// Событие Hyprland, полученное из `.socket2.sock`
+///
+/// Формат строки: `EVENT>>DATA`. Адреса окон приходят без префикса `0x`.
+/// Для событий с v2-версией Hyprland присылает обе строки, поэтому
+/// подписчику достаточно обрабатывать одну из них.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HyprlandEvent {
+    /// `workspace>>NAME`
+    Workspace { name: String },
+    /// `workspacev2>>ID,NAME`
+    WorkspaceV2 { id: i32, name: String },
+    /// `focusedmon>>MONNAME,WORKSPACENAME`
+    FocusedMonitor { monitor: String, workspace: String },
+    /// `focusedmonv2>>MONNAME,WORKSPACEID`
+    FocusedMonitorV2 { monitor: String, workspace_id: i32 },
+    /// `activewindow>>CLASS,TITLE`
+    ActiveWindow { class: String, title: String },
+    /// `activewindowv2>>ADDRESS` (пустой адрес - нет активного окна)
+    ActiveWindowV2 { address: String },
+    /// `fullscreen>>0|1`
+    Fullscreen { enabled: bool },
+    /// `monitoradded>>NAME`
+    MonitorAdded { name: String },
+    /// `monitoraddedv2>>ID,NAME,DESCRIPTION`
+    MonitorAddedV2 { id: i32, name: String, description: String },
+    /// `monitorremoved>>NAME`
+    MonitorRemoved { name: String },
+    /// `monitorremovedv2>>ID,NAME,DESCRIPTION`
+    MonitorRemovedV2 { id: i32, name: String, description: String },
+    /// `createworkspace>>NAME`
+    CreateWorkspace { name: String },
+    /// `createworkspacev2>>ID,NAME`
+    CreateWorkspaceV2 { id: i32, name: String },
+    /// `destroyworkspace>>NAME`
+    DestroyWorkspace { name: String },
+    /// `destroyworkspacev2>>ID,NAME`
+    DestroyWorkspaceV2 { id: i32, name: String },
+    /// `moveworkspace>>NAME,MONNAME`
+    MoveWorkspace { name: String, monitor: String },
+    /// `moveworkspacev2>>ID,NAME,MONNAME`
+    MoveWorkspaceV2 { id: i32, name: String, monitor: String },
+    /// `renameworkspace>>ID,NEWNAME`
+    RenameWorkspace { id: i32, new_name: String },
+    /// `activespecial>>NAME,MONNAME` (пустое имя - special workspace закрыт)
+    ActiveSpecial { name: String, monitor: String },
+    /// `activespecialv2>>ID,NAME,MONNAME`
+    ActiveSpecialV2 { id: Option<i32>, name: String, monitor: String },
+    /// `activelayout>>KEYBOARDNAME,LAYOUTNAME`
+    ActiveLayout { keyboard: String, layout: String },
+    /// `openwindow>>ADDRESS,WORKSPACENAME,CLASS,TITLE`
+    OpenWindow { address: String, workspace: String, class: String, title: String },
+    /// `closewindow>>ADDRESS`
+    CloseWindow { address: String },
+    /// `movewindow>>ADDRESS,WORKSPACENAME`
+    MoveWindow { address: String, workspace: String },
+    /// `movewindowv2>>ADDRESS,WORKSPACEID,WORKSPACENAME`
+    MoveWindowV2 { address: String, workspace_id: i32, workspace: String },
+    /// `openlayer>>NAMESPACE`
+    OpenLayer { namespace: String },
+    /// `closelayer>>NAMESPACE`
+    CloseLayer { namespace: String },
+    /// `submap>>NAME` (пустое имя - выход из submap)
+    Submap { name: String },
+    /// `changefloatingmode>>ADDRESS,0|1`
+    ChangeFloatingMode { address: String, floating: bool },
+    /// `urgent>>ADDRESS`
+    Urgent { address: String },
+    /// `screencast>>0|1,OWNER` (OWNER: 0 - монитор, 1 - окно)
+    Screencast { active: bool, owner: String },
+    /// `windowtitle>>ADDRESS`
+    WindowTitle { address: String },
+    /// `windowtitlev2>>ADDRESS,TITLE`
+    WindowTitleV2 { address: String, title: String },
+    /// `togglegroup>>0|1,ADDRESS[,ADDRESS...]`
+    ToggleGroup { exists: bool, addresses: Vec<String> },
+    /// `moveintogroup>>ADDRESS`
+    MoveIntoGroup { address: String },
+    /// `moveoutofgroup>>ADDRESS`
+    MoveOutOfGroup { address: String },
+    /// `ignoregrouplock>>0|1`
+    IgnoreGroupLock { enabled: bool },
+    /// `lockgroups>>0|1`
+    LockGroups { enabled: bool },
+    /// `configreloaded>>`
+    ConfigReloaded,
+    /// `pin>>ADDRESS,0|1`
+    Pin { address: String, pinned: bool },
+    /// `minimized>>ADDRESS,0|1`
+    Minimized { address: String, minimized: bool },
+    /// `bell>>ADDRESS`
+    Bell { address: String },
+    /// Неизвестное событие или событие с некорректными данными
+    Unknown { name: String, data: String },
+}
+
+impl HyprlandEvent {
+    /// Разбирает одну строку из `.socket2.sock` (без завершающего `\n`)
+    ///
+    /// Возвращает `None` только для пустых строк и строк без разделителя `>>`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end_matches(['\r', '\n']);
+        let (name, data) = line.split_once(">>")?;
+
+        let event = Self::parse_known(name, data).unwrap_or_else(|| HyprlandEvent::Unknown {
+            name: name.to_string(),
+            data: data.to_string(),
+        });
+
+        Some(event)
+    }
+
+    fn parse_known(name: &str, data: &str) -> Option<Self> {
+        let event = match name {
+            "workspace" => HyprlandEvent::Workspace { name: data.to_string() },
+            "workspacev2" => {
+                let (id, name) = data.split_once(',')?;
+                HyprlandEvent::WorkspaceV2 { id: id.parse().ok()?, name: name.to_string() }
+            }
+            "focusedmon" => {
+                let (monitor, workspace) = data.split_once(',')?;
+                HyprlandEvent::FocusedMonitor {
+                    monitor: monitor.to_string(),
+                    workspace: workspace.to_string(),
+                }
+            }
+            "focusedmonv2" => {
+                let (monitor, workspace_id) = data.split_once(',')?;
+                HyprlandEvent::FocusedMonitorV2 {
+                    monitor: monitor.to_string(),
+                    workspace_id: workspace_id.parse().ok()?,
+                }
+            }
+            "activewindow" => {
+                // Заголовок может содержать запятые, класс - нет
+                let (class, title) = data.split_once(',').unwrap_or((data, ""));
+                HyprlandEvent::ActiveWindow { class: class.to_string(), title: title.to_string() }
+            }
+            "activewindowv2" => HyprlandEvent::ActiveWindowV2 { address: data.to_string() },
+            "fullscreen" => HyprlandEvent::Fullscreen { enabled: parse_flag(data)? },
+            "monitoradded" => HyprlandEvent::MonitorAdded { name: data.to_string() },
+            "monitoraddedv2" => {
+                let (id, name, description) = split3(data)?;
+                HyprlandEvent::MonitorAddedV2 {
+                    id: id.parse().ok()?,
+                    name: name.to_string(),
+                    description: description.to_string(),
+                }
+            }
+            "monitorremoved" => HyprlandEvent::MonitorRemoved { name: data.to_string() },
+            "monitorremovedv2" => {
+                let (id, name, description) = split3(data)?;
+                HyprlandEvent::MonitorRemovedV2 {
+                    id: id.parse().ok()?,
+                    name: name.to_string(),
+                    description: description.to_string(),
+                }
+            }
+            "createworkspace" => HyprlandEvent::CreateWorkspace { name: data.to_string() },
+            "createworkspacev2" => {
+                let (id, name) = data.split_once(',')?;
+                HyprlandEvent::CreateWorkspaceV2 { id: id.parse().ok()?, name: name.to_string() }
+            }
+            "destroyworkspace" => HyprlandEvent::DestroyWorkspace { name: data.to_string() },
+            "destroyworkspacev2" => {
+                let (id, name) = data.split_once(',')?;
+                HyprlandEvent::DestroyWorkspaceV2 { id: id.parse().ok()?, name: name.to_string() }
+            }
+            "moveworkspace" => {
+                // Имя workspace может содержать запятые, имя монитора - нет
+                let (name, monitor) = data.rsplit_once(',')?;
+                HyprlandEvent::MoveWorkspace { name: name.to_string(), monitor: monitor.to_string() }
+            }
+            "moveworkspacev2" => {
+                let (id, rest) = data.split_once(',')?;
+                let (name, monitor) = rest.rsplit_once(',')?;
+                HyprlandEvent::MoveWorkspaceV2 {
+                    id: id.parse().ok()?,
+                    name: name.to_string(),
+                    monitor: monitor.to_string(),
+                }
+            }
+            "renameworkspace" => {
+                let (id, new_name) = data.split_once(',')?;
+                HyprlandEvent::RenameWorkspace { id: id.parse().ok()?, new_name: new_name.to_string() }
+            }
+            "activespecial" => {
+                let (name, monitor) = data.rsplit_once(',')?;
+                HyprlandEvent::ActiveSpecial { name: name.to_string(), monitor: monitor.to_string() }
+            }
+            "activespecialv2" => {
+                let (id, rest) = data.split_once(',')?;
+                let (name, monitor) = rest.rsplit_once(',')?;
+                HyprlandEvent::ActiveSpecialV2 {
+                    id: id.parse().ok(),
+                    name: name.to_string(),
+                    monitor: monitor.to_string(),
+                }
+            }
+            "activelayout" => {
+                // Имя клавиатуры не содержит запятых, а вот раскладка может
+                let (keyboard, layout) = data.split_once(',')?;
+                HyprlandEvent::ActiveLayout { keyboard: keyboard.to_string(), layout: layout.to_string() }
+            }
+            "openwindow" => {
+                let mut parts = data.splitn(4, ',');
+                HyprlandEvent::OpenWindow {
+                    address: parts.next()?.to_string(),
+                    workspace: parts.next()?.to_string(),
+                    class: parts.next()?.to_string(),
+                    title: parts.next().unwrap_or("").to_string(),
+                }
+            }
+            "closewindow" => HyprlandEvent::CloseWindow { address: data.to_string() },
+            "movewindow" => {
+                let (address, workspace) = data.split_once(',')?;
+                HyprlandEvent::MoveWindow { address: address.to_string(), workspace: workspace.to_string() }
+            }
+            "movewindowv2" => {
+                let (address, workspace_id, workspace) = split3(data)?;
+                HyprlandEvent::MoveWindowV2 {
+                    address: address.to_string(),
+                    workspace_id: workspace_id.parse().ok()?,
+                    workspace: workspace.to_string(),
+                }
+            }
+            "openlayer" => HyprlandEvent::OpenLayer { namespace: data.to_string() },
+            "closelayer" => HyprlandEvent::CloseLayer { namespace: data.to_string() },
+            "submap" => HyprlandEvent::Submap { name: data.to_string() },
+            "changefloatingmode" => {
+                let (address, floating) = data.split_once(',')?;
+                HyprlandEvent::ChangeFloatingMode { address: address.to_string(), floating: parse_flag(floating)? }
+            }
+            "urgent" => HyprlandEvent::Urgent { address: data.to_string() },
+            "screencast" => {
+                let (active, owner) = data.split_once(',')?;
+                HyprlandEvent::Screencast { active: parse_flag(active)?, owner: owner.to_string() }
+            }
+            "windowtitle" => HyprlandEvent::WindowTitle { address: data.to_string() },
+            "windowtitlev2" => {
+                let (address, title) = data.split_once(',').unwrap_or((data, ""));
+                HyprlandEvent::WindowTitleV2 { address: address.to_string(), title: title.to_string() }
+            }
+            "togglegroup" => {
+                let (exists, addresses) = data.split_once(',').unwrap_or((data, ""));
+                HyprlandEvent::ToggleGroup {
+                    exists: parse_flag(exists)?,
+                    addresses: addresses
+                        .split(',')
+                        .filter(|a| !a.is_empty())
+                        .map(|a| a.to_string())
+                        .collect(),
+                }
+            }
+            "moveintogroup" => HyprlandEvent::MoveIntoGroup { address: data.to_string() },
+            "moveoutofgroup" => HyprlandEvent::MoveOutOfGroup { address: data.to_string() },
+            "ignoregrouplock" => HyprlandEvent::IgnoreGroupLock { enabled: parse_flag(data)? },
+            "lockgroups" => HyprlandEvent::LockGroups { enabled: parse_flag(data)? },
+            "configreloaded" => HyprlandEvent::ConfigReloaded,
+            "pin" => {
+                let (address, pinned) = data.split_once(',')?;
+                HyprlandEvent::Pin { address: address.to_string(), pinned: parse_flag(pinned)? }
+            }
+            "minimized" => {
+                let (address, minimized) = data.split_once(',')?;
+                HyprlandEvent::Minimized { address: address.to_string(), minimized: parse_flag(minimized)? }
+            }
+            "bell" => HyprlandEvent::Bell { address: data.to_string() },
+            _ => return None,
+        };
+
+        Some(event)
+    }
+
+    /// Влияет ли событие на список workspace'ов или активное окно
+    pub fn affects_workspaces(&self) -> bool {
+        matches!(
+            self,
+            HyprlandEvent::Workspace { .. }
+                | HyprlandEvent::FocusedMonitor { .. }
+                | HyprlandEvent::ActiveWindow { .. }
+                | HyprlandEvent::MonitorAdded { .. }
+                | HyprlandEvent::MonitorRemoved { .. }
+                | HyprlandEvent::CreateWorkspace { .. }
+                | HyprlandEvent::DestroyWorkspace { .. }
+                | HyprlandEvent::MoveWorkspace { .. }
+                | HyprlandEvent::RenameWorkspace { .. }
+                | HyprlandEvent::ActiveSpecial { .. }
+                | HyprlandEvent::OpenWindow { .. }
+                | HyprlandEvent::CloseWindow { .. }
+                | HyprlandEvent::MoveWindow { .. }
+        )
+    }
+
+    /// Влияет ли событие на активное окно или его состояние
+    pub fn affects_active_window(&self) -> bool {
+        self.affects_workspaces()
+            || matches!(
+                self,
+                HyprlandEvent::WindowTitle { .. }
+                    | HyprlandEvent::WindowTitleV2 { .. }
+                    | HyprlandEvent::Fullscreen { .. }
+                    | HyprlandEvent::ChangeFloatingMode { .. }
+                    | HyprlandEvent::Pin { .. }
+            )
+    }
+}
+
+fn parse_flag(value: &str) -> Option<bool> {
+    match value {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    }
+}
+
+/// Делит строку на три поля; последнее поле может содержать запятые
+fn split3(data: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = data.splitn(3, ',');
+    Some((parts.next()?, parts.next()?, parts.next()?))
+}
+
+/// Общая шина событий Hyprland
+///
+/// Одно соединение с `.socket2.sock` на всё приложение; каждый подписчик
+/// получает собственный канал со всеми событиями.
+pub struct HyprlandEventBus {
+    subscribers: Mutex<Vec<Sender<HyprlandEvent>>>,
+    started: Once,
+}
+
+impl HyprlandEventBus {
+    fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+            started: Once::new(),
+        }
+    }
+
+    /// Подписаться на все события Hyprland
+    ///
+    /// Поток чтения сокета запускается при первой подписке.
+    /// Подписка снимается автоматически, когда `Receiver` удалён.
+    pub fn subscribe(&'static self) -> Receiver<HyprlandEvent> {
+        let (tx, rx) = async_channel::unbounded();
+        let mut subscribers = self.subscribers.lock().unwrap();
+        // Убираем подписчиков, чьи получатели уже удалены
+        subscribers.retain(|tx| !tx.is_closed());
+        subscribers.push(tx);
+        drop(subscribers);
+        self.started.call_once(|| self.start());
+        rx
+    }
+
+    fn dispatch(&self, event: HyprlandEvent) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        // Ошибка try_send для unbounded-канала означает, что получатель удалён
+        subscribers.retain(|tx| tx.try_send(event.clone()).is_ok());
+    }
+
+    fn start(&'static self) {
+        thread::spawn(move || {
+            let socket_path = match get_hyprland_client().event_socket() {
+                Ok(path) => path,
+                Err(e) => {
+                    eprintln!("[HyprlandEvents] ✗ {}, exiting", e);
+                    return;
+                }
+            };
+
+            eprintln!("[HyprlandEvents] ✓ Listening on {:?}", socket_path);
+
+            loop {
+                match UnixStream::connect(&socket_path) {
+                    Ok(stream) => {
+                        let mut reader = BufReader::new(stream);
+                        let mut line = Vec::new();
+
+                        loop {
+                            line.clear();
+                            match reader.read_until(b'\n', &mut line) {
+                                Ok(0) => break,
+                                Ok(_) => {
+                                    let text = String::from_utf8_lossy(&line);
+                                    if let Some(event) = HyprlandEvent::parse(&text) {
+                                        self.dispatch(event);
+                                    }
+                                }
+                                Err(e) => {
+                                    eprintln!("[HyprlandEvents] ✗ Read error: {}", e);
+                                    break;
+                                }
+                            }
+                        }
+
+                        eprintln!("[HyprlandEvents] Connection closed, reconnecting...");
+                    }
+                    Err(e) => {
+                        eprintln!("[HyprlandEvents] ✗ Failed to connect: {}", e);
+                    }
+                }
+                thread::sleep(std::time::Duration::from_millis(100));
+            }
+        });
+    }
+}
+
+static EVENT_BUS: OnceLock<HyprlandEventBus> = OnceLock::new();
+
+/// Получить глобальную шину событий Hyprland
+pub fn get_event_bus() -> &'static HyprlandEventBus {
+    EVENT_BUS.get_or_init(HyprlandEventBus::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windowtitle_keeps_data_with_commas_and_keywords() {
+        assert_eq!(
+            HyprlandEvent::parse("windowtitle>>5581c0a0,workspace 2, moved"),
+            Some(HyprlandEvent::WindowTitle { address: "5581c0a0,workspace 2, moved".to_string() })
+        );
+        assert_eq!(
+            HyprlandEvent::parse("windowtitlev2>>5581c0a0,workspace>>1, a, b"),
+            Some(HyprlandEvent::WindowTitleV2 {
+                address: "5581c0a0".to_string(),
+                title: "workspace>>1, a, b".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn non_numeric_workspace_id_is_unknown() {
+        assert_eq!(
+            HyprlandEvent::parse("workspacev2>>special,name"),
+            Some(HyprlandEvent::Unknown {
+                name: "workspacev2".to_string(),
+                data: "special,name".to_string(),
+            })
+        );
+        assert_eq!(
+            HyprlandEvent::parse("focusedmonv2>>DP-1,abc"),
+            Some(HyprlandEvent::Unknown {
+                name: "focusedmonv2".to_string(),
+                data: "DP-1,abc".to_string(),
+            })
+        );
+        assert_eq!(
+            HyprlandEvent::parse("workspacev2>>3,3"),
+            Some(HyprlandEvent::WorkspaceV2 { id: 3, name: "3".to_string() })
+        );
+    }
+
+    #[test]
+    fn line_without_separator_is_dropped() {
+        assert_eq!(HyprlandEvent::parse("workspace"), None);
+        assert_eq!(HyprlandEvent::parse(""), None);
+        assert_eq!(HyprlandEvent::parse("workspace>2"), None);
+    }
+
+    #[test]
+    fn trailing_crlf_is_trimmed() {
+        assert_eq!(
+            HyprlandEvent::parse("focusedmon>>DP-1,2\r\n"),
+            Some(HyprlandEvent::FocusedMonitor {
+                monitor: "DP-1".to_string(),
+                workspace: "2".to_string(),
+            })
+        );
+        assert_eq!(HyprlandEvent::parse("configreloaded>>\r\n"), Some(HyprlandEvent::ConfigReloaded));
+    }
+}
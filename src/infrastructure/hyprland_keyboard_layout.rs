@@ -1,14 +1,6 @@
-use crate::domain::models::KeyboardLayout;
+use crate::domain::models::{KeyboardDevice, KeyboardLayout};
 use crate::domain::keyboard_layout_service::KeyboardLayoutService;
-use std::process::Command;
-use serde::Deserialize;
-
-#[derive(Debug, Deserialize)]
-struct HyprlandDevice {
-    name: String,
-    #[serde(rename = "active_keymap")]
-    active_keymap: String,
-}
+use crate::infrastructure::hyprland_client::get_hyprland_client;
 
 pub struct HyprlandKeyboardLayoutService;
 
@@ -40,46 +32,46 @@ impl HyprlandKeyboardLayoutService {
     }
 }
 
-impl KeyboardLayoutService for HyprlandKeyboardLayoutService {
-    fn get_current_layout(&self) -> Option<KeyboardLayout> {
-        // Получаем информацию о устройствах через hyprctl
-        let output = Command::new("hyprctl")
-            .args(&["devices", "-j"])
-            .output()
-            .ok()?;
+impl HyprlandKeyboardLayoutService {
+    fn main_keyboard() -> Option<KeyboardDevice> {
+        let devices = match get_hyprland_client().devices() {
+            Ok(devices) => devices,
+            Err(e) => {
+                eprintln!("[KeyboardLayout] ✗ Failed to query devices: {}", e);
+                return None;
+            }
+        };
 
-        if !output.status.success() {
-            return None;
-        }
+        let keyboards: Vec<_> = devices
+            .keyboards
+            .into_iter()
+            .filter(|k| !k.active_keymap.is_empty())
+            .collect();
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        // Предпочитаем основную клавиатуру; в старых версиях Hyprland флага `main` нет,
+        // тогда берём последнюю в списке (обычно это физическая клавиатура, а не виртуальные устройства)
+        keyboards
+            .iter()
+            .find(|k| k.main)
+            .or_else(|| keyboards.last())
+            .cloned()
+    }
+}
 
-        // Парсим JSON
-        let devices_json: serde_json::Value = serde_json::from_str(&stdout).ok()?;
+impl KeyboardLayoutService for HyprlandKeyboardLayoutService {
+    fn get_current_layout(&self) -> Option<KeyboardLayout> {
+        let keyboard = Self::main_keyboard()?;
 
-        // Получаем массив клавиатур
-        let keyboards = devices_json.get("keyboards")?.as_array()?;
+        let short_name = keyboard.active_keymap.clone();
+        let full_name = Self::get_layout_full_name(&short_name);
 
-        // Ищем последнюю клавиатуру с активной раскладкой
-        // (обычно это физическая клавиатура, а не виртуальные устройства)
-        let mut found_layout: Option<KeyboardLayout> = None;
-        
-        for keyboard in keyboards {
-            if let Some(active_keymap) = keyboard.get("active_keymap").and_then(|v| v.as_str()) {
-                if !active_keymap.is_empty() {
-                    let short_name = active_keymap.to_string();
-                    let full_name = Self::get_layout_full_name(&short_name);
+        Some(KeyboardLayout {
+            short_name,
+            full_name,
+        })
+    }
 
-                    // Запоминаем эту раскладку (последняя в списке будет использована)
-                    found_layout = Some(KeyboardLayout {
-                        short_name,
-                        full_name,
-                    });
-                }
-            }
-        }
-        
-        found_layout
+    fn get_caps_lock(&self) -> Option<bool> {
+        Self::main_keyboard().map(|k| k.caps_lock)
     }
 }
-
@@ -1,8 +1,8 @@
 use crate::domain::models::{SubmapInfo, SubmapBinding};
 use crate::domain::submap_service::SubmapService;
+use crate::infrastructure::hyprland_client::get_hyprland_client;
 use std::collections::HashMap;
-use std::io::{Read, Write, BufRead, BufReader};
-use std::os::unix::net::UnixStream;
+use std::io::{BufRead, BufReader};
 use std::sync::{Arc, RwLock};
 use std::path::PathBuf;
 use std::thread;
@@ -27,7 +27,7 @@ impl HyprlandSubmapService {
         // Сначала парсим конфиг для получения названий из комментариев
         service.parse_config_comments();
 
-        // Затем парсим биндинги через hyprctl
+        // Затем парсим биндинги через Hyprland IPC
         service.parse_hyprland_bindings();
 
         service
@@ -114,43 +114,6 @@ impl HyprlandSubmapService {
         });
     }
 
-    fn get_control_socket(&self) -> Option<String> {
-        if let Ok(sig) = std::env::var("HYPRLAND_INSTANCE_SIGNATURE") {
-            if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
-                let socket_path = format!("{}/hypr/{}/.socket.sock", runtime_dir, sig);
-                if std::path::Path::new(&socket_path).exists() {
-                    return Some(socket_path);
-                }
-            }
-            return Some(format!("/tmp/hypr/{}/.socket.sock", sig));
-        }
-        None
-    }
-
-    fn send_request(&self, command: &str) -> Result<String, std::io::Error> {
-        let socket_path = self.get_control_socket().ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::NotFound, "Hyprland control socket not found")
-        })?;
-
-        let mut stream = UnixStream::connect(&socket_path)?;
-        stream.write_all(command.as_bytes())?;
-
-        let mut response = Vec::new();
-        let mut buffer = [0u8; 4096];
-
-        loop {
-            match stream.read(&mut buffer) {
-                Ok(0) => break,
-                Ok(n) => response.extend_from_slice(&buffer[..n]),
-                Err(e) => return Err(e),
-            }
-        }
-
-        String::from_utf8(response).map_err(|e| {
-            std::io::Error::new(std::io::ErrorKind::InvalidData, e)
-        })
-    }
-
     /// Получает путь к конфигу Hyprland
     fn get_config_path() -> Option<PathBuf> {
         // Проверяем XDG_CONFIG_HOME
@@ -254,37 +217,41 @@ impl HyprlandSubmapService {
         }
     }
 
-    /// Парсит биндинги через hyprctl
+    /// Парсит биндинги через Hyprland IPC
     fn parse_hyprland_bindings(&self) {
-        if let Ok(response) = self.send_request("j/binds") {
-            if let Ok(bindings) = serde_json::from_str::<Vec<HyprlandBind>>(&response) {
-                let mut cache = self.bindings_cache.write().unwrap();
-                let names = self.binding_names.read().unwrap();
-
-                for bind in bindings {
-                    let submap_name = bind.submap.clone().unwrap_or_default();
-                    let mods = bind.modmask_to_string();
-
-                    // Проверяем, есть ли название из комментария
-                    let cache_key = format!("{}:{}:{}", submap_name, bind.key, mods);
-                    let display_name = names.get(&cache_key).cloned();
-
-                    let binding = SubmapBinding {
-                        mods,
-                        key: bind.key.clone(),
-                        dispatcher: bind.dispatcher.clone(),
-                        arg: bind.arg.clone(),
-                        display_name,
-                    };
-
-                    cache.entry(submap_name)
-                        .or_insert_with(Vec::new)
-                        .push(binding);
-                }
-
-                eprintln!("[SubmapService] ✓ Parsed {} submaps from Hyprland", cache.len());
+        let bindings = match get_hyprland_client().binds() {
+            Ok(bindings) => bindings,
+            Err(e) => {
+                eprintln!("[SubmapService] ✗ Failed to query binds: {}", e);
+                return;
             }
+        };
+
+        let mut cache = self.bindings_cache.write().unwrap();
+        let names = self.binding_names.read().unwrap();
+
+        for bind in bindings {
+            let submap_name = bind.submap.clone().unwrap_or_default();
+            let mods = bind.modmask_to_string();
+
+            // Проверяем, есть ли название из комментария
+            let cache_key = format!("{}:{}:{}", submap_name, bind.key, mods);
+            let display_name = names.get(&cache_key).cloned();
+
+            let binding = SubmapBinding {
+                mods,
+                key: bind.key,
+                dispatcher: bind.dispatcher,
+                arg: bind.arg,
+                display_name,
+            };
+
+            cache.entry(submap_name)
+                .or_insert_with(Vec::new)
+                .push(binding);
         }
+
+        eprintln!("[SubmapService] ✓ Parsed {} submaps from Hyprland", cache.len());
     }
 
     /// Получает текущее имя submap
@@ -305,54 +272,3 @@ impl SubmapService for HyprlandSubmapService {
         cache.get(submap_name).cloned().unwrap_or_default()
     }
 }
-
-/// Структура для парсинга биндингов из Hyprland JSON
-#[derive(Debug, serde::Deserialize)]
-struct HyprlandBind {
-    #[serde(default)]
-    modmask: u32,
-    key: String,
-    dispatcher: String,
-    arg: String,
-    submap: Option<String>,
-    #[serde(default)]
-    #[allow(dead_code)]
-    locked: bool,
-    #[serde(default)]
-    #[allow(dead_code)]
-    mouse: bool,
-    #[serde(default)]
-    #[allow(dead_code)]
-    release: bool,
-    #[serde(default)]
-    #[allow(dead_code)]
-    repeat: bool,
-}
-
-impl HyprlandBind {
-    fn modmask_to_string(&self) -> String {
-        let mut mods = Vec::new();
-
-        // Hyprland modmask bits:
-        // 1 = Shift, 4 = Ctrl, 8 = Alt, 64 = Super
-        if self.modmask & 64 != 0 {
-            mods.push("SUPER");
-        }
-        if self.modmask & 4 != 0 {
-            mods.push("CTRL");
-        }
-        if self.modmask & 8 != 0 {
-            mods.push("ALT");
-        }
-        if self.modmask & 1 != 0 {
-            mods.push("SHIFT");
-        }
-
-        if mods.is_empty() {
-            String::new()
-        } else {
-            mods.join("+")
-        }
-    }
-}
-
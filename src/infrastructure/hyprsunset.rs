@@ -0,0 +1,254 @@
+use crate::config::{self, NightLightConfig, NightLightSchedule};
+use crate::domain::models::NightLightState;
+use crate::domain::night_light_service::NightLightService;
+use crate::infrastructure::hyprland_client::get_hyprland_client;
+use async_channel::Sender;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use parking_lot::Mutex;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+
+/// Допустимый диапазон температуры hyprsunset
+const MIN_TEMPERATURE: u32 = 1000;
+const MAX_TEMPERATURE: u32 = 20000;
+
+/// Как часто проверяется расписание
+const SCHEDULE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Ночной режим через IPC hyprsunset (`$XDG_RUNTIME_DIR/hypr/<instance>/.hyprsunset.sock`)
+///
+/// hyprsunset применяет температуру сразу ко всем мониторам. Демон должен быть
+/// запущен отдельно (`exec-once = hyprsunset`).
+pub struct HyprsunsetNightLight {
+    state: Mutex<NightLightState>,
+}
+
+impl HyprsunsetNightLight {
+    pub fn new() -> Self {
+        let temperature = config::get_config().read().unwrap().night_light.temperature;
+
+        Self {
+            state: Mutex::new(NightLightState {
+                enabled: false,
+                temperature: temperature.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE),
+                next_change: None,
+            }),
+        }
+    }
+
+    fn send(&self, command: &str) -> Result<(), String> {
+        let path = get_hyprland_client()
+            .hyprsunset_socket()
+            .map_err(|e| e.to_string())?;
+
+        let mut stream = UnixStream::connect(&path)
+            .map_err(|e| format!("hyprsunset is not running ({:?}): {}", path, e))?;
+        let _ = stream.set_read_timeout(Some(std::time::Duration::from_secs(2)));
+        stream
+            .write_all(command.as_bytes())
+            .map_err(|e| format!("Failed to send '{}' to hyprsunset: {}", command, e))?;
+
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+        let response = response.trim();
+
+        if response.is_empty() || response == "ok" {
+            Ok(())
+        } else {
+            Err(format!("hyprsunset rejected '{}': {}", command, response))
+        }
+    }
+
+    /// Запускает проверку расписания из конфига
+    ///
+    /// Режим переключается только на границах интервала (закат/восход или
+    /// `start`/`end`), поэтому ручное переключение действует до следующей границы.
+    /// В `tx` отправляется сигнал при каждом изменении состояния.
+    pub fn start_scheduler(self: Arc<Self>, tx: Sender<()>) {
+        std::thread::spawn(move || {
+            let mut last_scheduled: Option<bool> = None;
+            let mut last_schedule: Option<NightLightSchedule> = None;
+            let mut last_temperature: Option<u32> = None;
+
+            loop {
+                let config = config::get_config().read().unwrap().night_light.clone();
+                let mut changed = false;
+
+                // Температура изменена в конфиге (в т.ч. горячая перезагрузка);
+                // значение со слайдера попадает в конфиг позже и здесь не сбрасывается
+                if last_temperature != Some(config.temperature) {
+                    match self.set_temperature(config.temperature) {
+                        Ok(()) => {
+                            last_temperature = Some(config.temperature);
+                            changed = true;
+                        }
+                        Err(e) => eprintln!("[NightLight] ✗ {}", e),
+                    }
+                }
+
+                // Смена типа расписания - заново применяем текущий интервал
+                if last_schedule != Some(config.schedule) {
+                    last_schedule = Some(config.schedule);
+                    last_scheduled = None;
+                }
+
+                let now = Local::now();
+                let (scheduled, next_change) = match evaluate_schedule(&config, now) {
+                    Some((night, next)) => (Some(night), next),
+                    None => (None, None),
+                };
+
+                if let Some(night) = scheduled {
+                    if last_scheduled != Some(night) {
+                        match self.set_enabled(night) {
+                            Ok(()) => {
+                                last_scheduled = Some(night);
+                                changed = true;
+                            }
+                            // hyprsunset ещё не запущен - повторим на следующей итерации
+                            Err(e) => eprintln!("[NightLight] ✗ {}", e),
+                        }
+                    }
+                }
+
+                {
+                    let mut state = self.state.lock();
+                    if state.next_change != next_change {
+                        state.next_change = next_change;
+                        changed = true;
+                    }
+                }
+
+                if changed && tx.send_blocking(()).is_err() {
+                    break;
+                }
+
+                std::thread::sleep(SCHEDULE_INTERVAL);
+            }
+        });
+    }
+}
+
+impl NightLightService for HyprsunsetNightLight {
+    fn get_state(&self) -> NightLightState {
+        self.state.lock().clone()
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<(), String> {
+        let temperature = self.state.lock().temperature;
+        if enabled {
+            self.send(&format!("temperature {}", temperature))?;
+        } else {
+            self.send("identity")?;
+        }
+
+        self.state.lock().enabled = enabled;
+        eprintln!(
+            "[NightLight] ✓ {}",
+            if enabled { format!("Enabled ({}K)", temperature) } else { "Disabled".to_string() }
+        );
+        Ok(())
+    }
+
+    fn set_temperature(&self, kelvin: u32) -> Result<(), String> {
+        let kelvin = kelvin.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE);
+        let enabled = self.state.lock().enabled;
+        if enabled {
+            self.send(&format!("temperature {}", kelvin))?;
+        }
+
+        self.state.lock().temperature = kelvin;
+        Ok(())
+    }
+}
+
+/// Должен ли режим быть включён сейчас и когда расписание сменится
+///
+/// `None` - расписание не задано, настроено неверно или (для `Sun`) солнце
+/// сегодня не заходит/не восходит.
+fn evaluate_schedule(
+    config: &NightLightConfig,
+    now: DateTime<Local>,
+) -> Option<(bool, Option<DateTime<Local>>)> {
+    match config.schedule {
+        NightLightSchedule::Manual => None,
+        NightLightSchedule::Fixed => {
+            let start = NaiveTime::parse_from_str(&config.start, "%H:%M").ok()?;
+            let end = NaiveTime::parse_from_str(&config.end, "%H:%M").ok()?;
+            if start == end {
+                return None;
+            }
+
+            let time = now.time();
+            let night = if start < end {
+                time >= start && time < end
+            } else {
+                time >= start || time < end
+            };
+
+            let today = now.date_naive();
+            let next = [today, today + Duration::days(1)]
+                .into_iter()
+                .flat_map(|date| [date.and_time(start), date.and_time(end)])
+                .filter_map(|naive| Local.from_local_datetime(&naive).earliest())
+                .filter(|moment| *moment > now)
+                .min();
+
+            Some((night, next))
+        }
+        NightLightSchedule::Sun => {
+            let latitude = config.latitude?;
+            let longitude = config.longitude?;
+
+            let today = now.date_naive();
+            let (sunrise, sunset) = sun_times(today, latitude, longitude)?;
+
+            if now < sunrise {
+                Some((true, Some(sunrise)))
+            } else if now < sunset {
+                Some((false, Some(sunset)))
+            } else {
+                let next = sun_times(today + Duration::days(1), latitude, longitude)
+                    .map(|(sunrise, _)| sunrise);
+                Some((true, next))
+            }
+        }
+    }
+}
+
+/// Восход и закат для даты по уравнению восхода (точность - около минуты)
+///
+/// `None` во время полярного дня или полярной ночи.
+fn sun_times(date: NaiveDate, latitude: f64, longitude: f64) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let j2000 = NaiveDate::from_ymd_opt(2000, 1, 1)?;
+    let days = (date - j2000).num_days() as f64 + 0.0008;
+
+    // Средний солнечный полдень
+    let mean_noon = days - longitude / 360.0;
+    // Средняя аномалия
+    let anomaly = (357.5291 + 0.985_600_28 * mean_noon).rem_euclid(360.0).to_radians();
+    // Уравнение центра
+    let center = 1.9148 * anomaly.sin() + 0.0200 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    // Эклиптическая долгота
+    let ecliptic = (anomaly.to_degrees() + center + 180.0 + 102.9372).rem_euclid(360.0).to_radians();
+    // Солнечный транзит (юлианская дата)
+    let transit = 2_451_545.0 + mean_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic).sin();
+
+    let declination = (ecliptic.sin() * 23.4397_f64.to_radians().sin()).asin();
+    let latitude = latitude.to_radians();
+    // -0.833° - рефракция и радиус диска
+    let cos_hour_angle = ((-0.833_f64).to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees() / 360.0;
+
+    let to_local = |julian: f64| {
+        let timestamp = ((julian - 2_440_587.5) * 86_400.0).round() as i64;
+        Local.timestamp_opt(timestamp, 0).single()
+    };
+
+    Some((to_local(transit - hour_angle)?, to_local(transit + hour_angle)?))
+}
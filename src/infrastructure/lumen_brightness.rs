@@ -1,4 +1,6 @@
 use crate::domain::brightness_service::BrightnessService;
+use crate::domain::models::BrightnessDisplay;
+use crate::infrastructure::display_brightness::{internal_display, INTERNAL_DISPLAY_ID};
 use std::sync::Arc;
 use zbus::{Connection, proxy};
 use parking_lot::Mutex;
@@ -121,6 +123,19 @@ impl LumenBrightnessService {
 }
 
 impl BrightnessService for LumenBrightnessService {
+    fn list_displays(&self) -> Vec<BrightnessDisplay> {
+        self.get_brightness()
+            .map(|brightness| vec![internal_display(brightness)])
+            .unwrap_or_default()
+    }
+
+    fn set_display_brightness(&self, id: &str, value: u32) -> Result<(), String> {
+        if id != INTERNAL_DISPLAY_ID {
+            return Err(format!("Unknown display: {}", id));
+        }
+        self.set_brightness(value)
+    }
+
     fn get_brightness(&self) -> Result<u32, String> {
         let rt = tokio::runtime::Runtime::new()
             .map_err(|e| format!("Failed to create runtime: {}", e))?;
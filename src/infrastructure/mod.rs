@@ -1,19 +1,25 @@
+pub mod hyprland_client;
 pub mod hyprland_ipc;
-pub mod event_listener;
-pub mod monitor_listener;
+pub mod hyprland_events;
 pub mod status_notifier_tray;
 pub mod system_datetime;
 pub mod dbus_status_notifier_watcher;
 pub mod system_battery;
+pub mod battery_alerts;
+pub mod power_profiles;
 pub mod pipewire_volume;
 pub mod hyprland_keyboard_layout;
-pub mod keyboard_layout_listener;
 pub mod system_resources;
 pub mod networkmanager;
 pub mod lumen_brightness;
+pub mod sysfs_brightness;
+pub mod display_brightness;
+#[cfg(feature = "ddc")]
+pub mod ddc_brightness;
 pub mod notification_client;
 pub mod remote_notification_service;
 pub mod hyprland_submap;
-pub mod submap_listener;
-
-
+pub mod desktop_entries;
+pub mod config_watcher;
+pub mod mpris;
+pub mod hyprsunset;
@@ -0,0 +1,442 @@
+use crate::domain::models::{MediaPlayer, PlaybackStatus};
+use crate::domain::mpris_service::MprisService;
+use async_channel::Sender;
+use futures::stream::StreamExt;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use zbus::fdo::{DBusProxy, PropertiesProxy};
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{ObjectPath, OwnedValue};
+use zbus::{proxy, Connection};
+
+/// Префикс имён MPRIS плееров на сессионной шине
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const MPRIS_PATH: &str = "/org/mpris/MediaPlayer2";
+
+#[proxy(interface = "org.mpris.MediaPlayer2", default_path = "/org/mpris/MediaPlayer2")]
+trait MprisRoot {
+    #[zbus(property)]
+    fn identity(&self) -> zbus::Result<String>;
+}
+
+#[proxy(interface = "org.mpris.MediaPlayer2.Player", default_path = "/org/mpris/MediaPlayer2")]
+trait MprisPlayer {
+    #[zbus(name = "PlayPause")]
+    async fn play_pause(&self) -> zbus::Result<()>;
+
+    #[zbus(name = "Next")]
+    async fn next(&self) -> zbus::Result<()>;
+
+    #[zbus(name = "Previous")]
+    async fn previous(&self) -> zbus::Result<()>;
+
+    /// Относительная перемотка (микросекунды)
+    #[zbus(name = "Seek")]
+    async fn seek(&self, offset: i64) -> zbus::Result<()>;
+
+    /// Абсолютная позиция для трека `track_id` (микросекунды)
+    #[zbus(name = "SetPosition")]
+    async fn set_position(&self, track_id: &ObjectPath<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn metadata(&self) -> zbus::Result<HashMap<String, OwnedValue>>;
+
+    #[zbus(property)]
+    fn position(&self) -> zbus::Result<i64>;
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn can_seek(&self) -> zbus::Result<bool>;
+
+    /// Позиция изменилась скачком (перемотка)
+    #[zbus(signal, name = "Seeked")]
+    fn seeked(&self, position: i64) -> zbus::Result<()>;
+}
+
+/// Плеер и служебные данные для отслеживания позиции
+struct PlayerEntry {
+    info: MediaPlayer,
+    /// `mpris:trackid` текущего трека (нужен для SetPosition)
+    track_id: Option<String>,
+    /// Когда была получена `info.position_us`
+    position_at: Instant,
+    /// Порядок появления (для стабильной сортировки)
+    seq: u64,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl PlayerEntry {
+    /// Позиция с учётом времени, прошедшего с последнего запроса (Position не сигналится)
+    fn current_info(&self) -> MediaPlayer {
+        let mut info = self.info.clone();
+        if info.status == PlaybackStatus::Playing {
+            info.position_us += self.position_at.elapsed().as_micros() as i64;
+        }
+        if let Some(length) = info.length_us {
+            info.position_us = info.position_us.min(length);
+        }
+        info
+    }
+}
+
+#[derive(Default)]
+struct MprisState {
+    players: HashMap<String, PlayerEntry>,
+    /// Плеер, выбранный пользователем
+    selected: Option<String>,
+    next_seq: u64,
+}
+
+impl MprisState {
+    fn sorted(&self) -> Vec<&PlayerEntry> {
+        let mut players: Vec<_> = self.players.values().collect();
+        players.sort_by_key(|p| p.seq);
+        players
+    }
+
+    /// Выбранный пользователем, иначе первый играющий, иначе первый на паузе, иначе первый
+    fn active(&self) -> Option<&PlayerEntry> {
+        if let Some(selected) = self.selected.as_ref().and_then(|name| self.players.get(name)) {
+            return Some(selected);
+        }
+
+        let players = self.sorted();
+        players
+            .iter()
+            .find(|p| p.info.status == PlaybackStatus::Playing)
+            .or_else(|| players.iter().find(|p| p.info.status == PlaybackStatus::Paused))
+            .or_else(|| players.first())
+            .copied()
+    }
+}
+
+pub struct DbusMprisService {
+    connection: Arc<Mutex<Option<Connection>>>,
+    state: Arc<Mutex<MprisState>>,
+}
+
+impl DbusMprisService {
+    pub fn new() -> Self {
+        Self {
+            connection: Arc::new(Mutex::new(None)),
+            state: Arc::new(Mutex::new(MprisState::default())),
+        }
+    }
+
+    /// Запускает отслеживание плееров на сессионной шине
+    pub fn start_monitoring(&self, tx: Sender<()>) {
+        let connection = self.connection.clone();
+        let state = self.state.clone();
+
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+
+            runtime.block_on(async {
+                if let Err(e) = Self::monitor_players(connection, state, tx).await {
+                    eprintln!("[Mpris] ✗ Error monitoring players: {}", e);
+                }
+            });
+        });
+    }
+
+    async fn monitor_players(
+        connection: Arc<Mutex<Option<Connection>>>,
+        state: Arc<Mutex<MprisState>>,
+        tx: Sender<()>,
+    ) -> zbus::Result<()> {
+        let conn = Connection::session().await?;
+        *connection.lock().unwrap() = Some(conn.clone());
+
+        let dbus = DBusProxy::new(&conn).await?;
+        let mut owner_changes = dbus.receive_name_owner_changed().await?;
+
+        for name in dbus.list_names().await? {
+            if name.as_str().starts_with(MPRIS_PREFIX) {
+                Self::add_player(&conn, name.to_string(), &state, &tx).await;
+            }
+        }
+        eprintln!("[Mpris] ✓ Watching players ({} found)", state.lock().unwrap().players.len());
+        let _ = tx.try_send(());
+
+        while let Some(signal) = owner_changes.next().await {
+            let Ok(args) = signal.args() else {
+                continue;
+            };
+            let name = args.name().to_string();
+            if !name.starts_with(MPRIS_PREFIX) {
+                continue;
+            }
+
+            if let Some(entry) = state.lock().unwrap().players.remove(&name) {
+                entry.task.abort();
+                eprintln!("[Mpris] Player gone: {}", name);
+            }
+            if args.new_owner().is_some() {
+                Self::add_player(&conn, name, &state, &tx).await;
+            }
+            let _ = tx.try_send(());
+        }
+
+        Ok(())
+    }
+
+    /// Читает состояние плеера и подписывается на его изменения
+    async fn add_player(conn: &Connection, name: String, state: &Arc<Mutex<MprisState>>, tx: &Sender<()>) {
+        let proxies = Self::player_proxies(conn, &name).await;
+        let (root, player) = match proxies {
+            Ok(proxies) => proxies,
+            Err(e) => {
+                eprintln!("[Mpris] ✗ Failed to connect to {}: {}", name, e);
+                return;
+            }
+        };
+
+        let (info, track_id) = Self::fetch_player(&name, &root, &player).await;
+        eprintln!("[Mpris] ✓ Player found: {} ({})", info.identity, name);
+
+        let task = {
+            let conn = conn.clone();
+            let name = name.clone();
+            let state = state.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if let Err(e) = Self::watch_player(&conn, &name, root, player, &state, &tx).await {
+                    eprintln!("[Mpris] ✗ Stopped watching {}: {}", name, e);
+                }
+            })
+        };
+
+        let mut state = state.lock().unwrap();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.players.insert(name, PlayerEntry {
+            info,
+            track_id,
+            position_at: Instant::now(),
+            seq,
+            task,
+        });
+    }
+
+    /// Обновляет плеер по PropertiesChanged и Seeked
+    async fn watch_player(
+        conn: &Connection,
+        name: &str,
+        root: MprisRootProxy<'static>,
+        player: MprisPlayerProxy<'static>,
+        state: &Arc<Mutex<MprisState>>,
+        tx: &Sender<()>,
+    ) -> zbus::Result<()> {
+        let properties = PropertiesProxy::builder(conn)
+            .destination(name.to_string())?
+            .path(MPRIS_PATH)?
+            .build()
+            .await?;
+
+        let changed = properties.receive_properties_changed().await?.map(|_| ());
+        let seeked = player.receive_seeked().await?.map(|_| ());
+        let mut events = futures::stream::select(changed, seeked);
+
+        while events.next().await.is_some() {
+            let (info, track_id) = Self::fetch_player(name, &root, &player).await;
+
+            if let Some(entry) = state.lock().unwrap().players.get_mut(name) {
+                entry.info = info;
+                entry.track_id = track_id;
+                entry.position_at = Instant::now();
+            }
+            let _ = tx.send(()).await;
+        }
+
+        Ok(())
+    }
+
+    /// Proxy без кэша свойств: Position не сигналится, остальное читаем после сигнала
+    async fn player_proxies(
+        conn: &Connection,
+        name: &str,
+    ) -> zbus::Result<(MprisRootProxy<'static>, MprisPlayerProxy<'static>)> {
+        let root = MprisRootProxy::builder(conn)
+            .destination(name.to_string())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        let player = MprisPlayerProxy::builder(conn)
+            .destination(name.to_string())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+        Ok((root, player))
+    }
+
+    async fn fetch_player(
+        name: &str,
+        root: &MprisRootProxy<'_>,
+        player: &MprisPlayerProxy<'_>,
+    ) -> (MediaPlayer, Option<String>) {
+        let identity = root
+            .identity()
+            .await
+            .unwrap_or_else(|_| name.trim_start_matches(MPRIS_PREFIX).to_string());
+
+        let status = match player.playback_status().await.as_deref() {
+            Ok("Playing") => PlaybackStatus::Playing,
+            Ok("Paused") => PlaybackStatus::Paused,
+            _ => PlaybackStatus::Stopped,
+        };
+
+        let metadata = player.metadata().await.unwrap_or_default();
+        let artists = metadata_strings(&metadata, "xesam:artist");
+
+        let info = MediaPlayer {
+            bus_name: name.to_string(),
+            identity,
+            status,
+            title: metadata_string(&metadata, "xesam:title"),
+            artist: (!artists.is_empty()).then(|| artists.join(", ")),
+            album: metadata_string(&metadata, "xesam:album"),
+            art_url: metadata_string(&metadata, "mpris:artUrl"),
+            length_us: metadata_i64(&metadata, "mpris:length").filter(|l| *l > 0),
+            position_us: player.position().await.unwrap_or(0),
+            can_go_next: player.can_go_next().await.unwrap_or(false),
+            can_go_previous: player.can_go_previous().await.unwrap_or(false),
+            can_seek: player.can_seek().await.unwrap_or(false),
+        };
+
+        (info, metadata_string(&metadata, "mpris:trackid"))
+    }
+
+    /// Выполняет вызов на активном плеере
+    fn with_active_player<F, Fut>(&self, action: &str, call: F) -> Result<(), String>
+    where
+        F: FnOnce(MprisPlayerProxy<'static>, Option<String>, i64) -> Fut,
+        Fut: std::future::Future<Output = zbus::Result<()>>,
+    {
+        let (name, track_id, position) = {
+            let state = self.state.lock().unwrap();
+            let active = state.active().ok_or_else(|| "No active media player".to_string())?;
+            (active.info.bus_name.clone(), active.track_id.clone(), active.current_info().position_us)
+        };
+
+        let conn = self
+            .connection
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "Not connected to session bus".to_string())?;
+
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| format!("Failed to create runtime: {}", e))?;
+
+        rt.block_on(async {
+            let player = MprisPlayerProxy::builder(&conn)
+                .destination(name)
+                .map_err(|e| e.to_string())?
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await
+                .map_err(|e| format!("Failed to create player proxy: {}", e))?;
+
+            call(player, track_id, position)
+                .await
+                .map_err(|e| format!("Failed to {}: {}", action, e))
+        })
+    }
+}
+
+fn metadata_string(metadata: &HashMap<String, OwnedValue>, key: &str) -> Option<String> {
+    let value = metadata.get(key)?;
+    let text = if let Ok(s) = value.downcast_ref::<&str>() {
+        s.to_string()
+    } else {
+        value.downcast_ref::<ObjectPath>().ok()?.to_string()
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// `xesam:artist` - массив строк, но некоторые плееры присылают одну строку
+fn metadata_strings(metadata: &HashMap<String, OwnedValue>, key: &str) -> Vec<String> {
+    let Some(value) = metadata.get(key) else {
+        return Vec::new();
+    };
+
+    if let Ok(array) = value.downcast_ref::<zbus::zvariant::Array>() {
+        return array
+            .iter()
+            .filter_map(|item| item.downcast_ref::<&str>().ok())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+
+    metadata_string(metadata, key).into_iter().collect()
+}
+
+/// `mpris:length` по спецификации i64, но встречается и u64
+fn metadata_i64(metadata: &HashMap<String, OwnedValue>, key: &str) -> Option<i64> {
+    let value = metadata.get(key)?;
+    value
+        .downcast_ref::<i64>()
+        .ok()
+        .or_else(|| value.downcast_ref::<u64>().ok().map(|v| v as i64))
+}
+
+impl MprisService for DbusMprisService {
+    fn list_players(&self) -> Vec<MediaPlayer> {
+        self.state
+            .lock()
+            .unwrap()
+            .sorted()
+            .into_iter()
+            .map(PlayerEntry::current_info)
+            .collect()
+    }
+
+    fn get_active_player(&self) -> Option<MediaPlayer> {
+        self.state.lock().unwrap().active().map(PlayerEntry::current_info)
+    }
+
+    fn set_active_player(&self, bus_name: &str) {
+        self.state.lock().unwrap().selected = Some(bus_name.to_string());
+    }
+
+    fn play_pause(&self) -> Result<(), String> {
+        self.with_active_player("toggle playback", |player, _, _| async move {
+            player.play_pause().await
+        })
+    }
+
+    fn next(&self) -> Result<(), String> {
+        self.with_active_player("skip to next track", |player, _, _| async move {
+            player.next().await
+        })
+    }
+
+    fn previous(&self) -> Result<(), String> {
+        self.with_active_player("skip to previous track", |player, _, _| async move {
+            player.previous().await
+        })
+    }
+
+    fn set_position(&self, position_us: i64) -> Result<(), String> {
+        self.with_active_player("seek", |player, track_id, current| async move {
+            // SetPosition требует trackid; без него - относительная перемотка
+            match track_id.as_deref().and_then(|id| ObjectPath::try_from(id).ok()) {
+                Some(track_id) => player.set_position(&track_id, position_us).await,
+                None => player.seek(position_us - current).await,
+            }
+        })
+    }
+}
@@ -1,5 +1,6 @@
-use zbus::{blocking::Connection, proxy};
+use zbus::{blocking::Connection, proxy, zvariant::Value};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Данные уведомления от сервиса
@@ -12,6 +13,12 @@ pub struct NotificationData {
     pub icon: String,
     pub urgency: u8,
     pub timestamp: i64,
+    #[serde(default)]
+    pub has_image: bool,
+    #[serde(default)]
+    pub desktop_entry: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
 }
 
 #[proxy(
@@ -20,18 +27,50 @@ pub struct NotificationData {
     default_path = "/org/freedesktop/Notifications"
 )]
 trait NotificationService {
+    /// Показать уведомление (стандартный метод org.freedesktop.Notifications)
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: &HashMap<&str, &Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+
     /// Получить количество уведомлений
     fn get_notification_count(&self) -> zbus::Result<u32>;
 
     /// Получить историю уведомлений (JSON)
     fn get_history(&self) -> zbus::Result<String>;
 
+    /// Поиск по истории (JSON); `urgency` -1 - любая, `since` 0 - за всё время
+    fn search_history(&self, query: &str, urgency: i32, since: i64) -> zbus::Result<String>;
+
+    /// Получить картинку уведомления (PNG)
+    fn get_image(&self, id: u32) -> zbus::Result<Vec<u8>>;
+
     /// Удалить уведомление по ID
     fn delete_notification(&self, id: u32) -> zbus::Result<bool>;
 
     /// Очистить все уведомления
     fn clear_history(&self) -> zbus::Result<u32>;
 
+    /// Удалить историю приложения
+    fn clear_app(&self, app_name: &str) -> zbus::Result<u32>;
+
+    /// Включён ли DND вручную
+    fn get_dnd_enabled(&self) -> zbus::Result<bool>;
+
+    /// Действует ли DND сейчас (вручную или по расписанию)
+    fn is_dnd_active(&self) -> zbus::Result<bool>;
+
+    /// Включить/выключить DND
+    fn set_dnd_enabled(&self, enabled: bool) -> zbus::Result<()>;
+
     /// Показать окно истории
     fn show_history_window(&self) -> zbus::Result<()>;
 
@@ -41,6 +80,10 @@ trait NotificationService {
     /// Сигнал: количество уведомлений изменилось
     #[zbus(signal)]
     fn notification_count_changed(&self, count: u32) -> zbus::Result<()>;
+
+    /// Сигнал: режим «Не беспокоить» включился или выключился
+    #[zbus(signal)]
+    fn dnd_changed(&self, active: bool) -> zbus::Result<()>;
 }
 
 /// Клиент для связи с hyprline-notifications
@@ -59,6 +102,19 @@ impl NotificationClient {
         Ok(Self { proxy })
     }
 
+    /// Отправить уведомление от имени hyprline
+    /// `replaces_id` - id предыдущего уведомления, которое нужно заменить (0 - новое)
+    pub fn send(&self, summary: &str, body: &str, critical: bool, replaces_id: u32) -> Result<u32, String> {
+        let urgency = Value::U8(if critical { 2 } else { 1 });
+        let icon = if critical { "dialog-error" } else { "dialog-information" };
+        let mut hints = HashMap::new();
+        hints.insert("urgency", &urgency);
+
+        self.proxy
+            .notify("Hyprline", replaces_id, icon, summary, body, &[], &hints, -1)
+            .map_err(|e| format!("Failed to send notification: {}", e))
+    }
+
     /// Получить количество уведомлений
     pub fn get_count(&self) -> Result<u32, String> {
         self.proxy.get_notification_count()
@@ -74,6 +130,22 @@ impl NotificationClient {
             .map_err(|e| format!("Failed to parse history: {}", e))
     }
 
+    /// Поиск по истории
+    pub fn search_history(&self, query: &str, urgency: i32, since: i64) -> Result<Vec<NotificationData>, String> {
+        let json = self.proxy.search_history(query, urgency, since)
+            .map_err(|e| format!("Failed to search history: {}", e))?;
+
+        serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse history: {}", e))
+    }
+
+    /// Получить картинку уведомления; `None`, если её нет
+    pub fn get_image(&self, id: u32) -> Result<Option<Vec<u8>>, String> {
+        self.proxy.get_image(id)
+            .map(|data| Some(data).filter(|data| !data.is_empty()))
+            .map_err(|e| format!("Failed to get image: {}", e))
+    }
+
     /// Удалить уведомление
     pub fn delete(&self, id: u32) -> Result<bool, String> {
         self.proxy.delete_notification(id)
@@ -86,6 +158,24 @@ impl NotificationClient {
             .map_err(|e| format!("Failed to clear history: {}", e))
     }
 
+    /// Включён ли DND вручную
+    pub fn get_dnd_enabled(&self) -> Result<bool, String> {
+        self.proxy.get_dnd_enabled()
+            .map_err(|e| format!("Failed to get DND state: {}", e))
+    }
+
+    /// Включить/выключить DND
+    pub fn set_dnd_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.proxy.set_dnd_enabled(enabled)
+            .map_err(|e| format!("Failed to set DND: {}", e))
+    }
+
+    /// Удалить историю приложения
+    pub fn clear_app(&self, app_name: &str) -> Result<u32, String> {
+        self.proxy.clear_app(app_name)
+            .map_err(|e| format!("Failed to clear app history: {}", e))
+    }
+
     /// Показать окно истории
     pub fn show_history_window(&self) -> Result<(), String> {
         self.proxy.show_history_window()
@@ -104,6 +194,8 @@ impl Default for NotificationClient {
 pub enum NotificationEvent {
     /// Количество уведомлений изменилось
     CountChanged(u32),
+    /// Режим «Не беспокоить» включился или выключился
+    DndChanged(bool),
     /// Сервис стал доступен
     ServiceAvailable,
     /// Сервис стал недоступен
@@ -179,6 +271,9 @@ async fn listen_for_signals(callback: Arc<dyn Fn(NotificationEvent) + Send + Syn
     if let Ok(count) = proxy.get_notification_count().await {
         callback(NotificationEvent::CountChanged(count));
     }
+    if let Ok(active) = proxy.is_dnd_active().await {
+        callback(NotificationEvent::DndChanged(active));
+    }
 
     eprintln!("[NotificationListener] ✓ Subscribed to notification count changes");
 
@@ -186,6 +281,9 @@ async fn listen_for_signals(callback: Arc<dyn Fn(NotificationEvent) + Send + Syn
     let mut count_stream = proxy.receive_notification_count_changed().await
         .map_err(|e| format!("Failed to subscribe: {}", e))?;
     
+    let mut dnd_stream = proxy.receive_dnd_changed().await
+        .map_err(|e| format!("Failed to subscribe: {}", e))?;
+
     let mut name_stream = dbus_proxy.receive_name_owner_changed().await
         .map_err(|e| format!("Failed to subscribe to name changes: {}", e))?;
 
@@ -196,6 +294,11 @@ async fn listen_for_signals(callback: Arc<dyn Fn(NotificationEvent) + Send + Syn
                     callback(NotificationEvent::CountChanged(args.count));
                 }
             }
+            Some(signal) = dnd_stream.next() => {
+                if let Ok(args) = signal.args() {
+                    callback(NotificationEvent::DndChanged(args.active));
+                }
+            }
             Some(signal) = name_stream.next() => {
                 if let Ok(args) = signal.args() {
                     if args.name.as_str() == "org.freedesktop.Notifications" && args.new_owner.is_none() {
@@ -1,218 +1,699 @@
-use crate::domain::models::VolumeInfo;
+use crate::domain::models::{AudioDevice, AudioDeviceKind, AudioStream, MicrophoneInfo, VolumeInfo};
 use crate::domain::volume_service::VolumeService;
-use std::process::Command;
-use std::sync::{Arc, Mutex};
 use async_channel::Sender;
+use pipewire as pw;
+use pw::node::NodeState;
+use pw::registry::{GlobalObject, Registry};
+use pw::spa::param::ParamType;
+use pw::spa::pod::deserialize::PodDeserializer;
+use pw::spa::pod::serialize::PodSerializer;
+use pw::spa::pod::{Object, Pod, Property, PropertyFlags, Value, ValueArray};
+use pw::spa::utils::dict::DictRef;
+use pw::spa::utils::SpaTypes;
+use pw::types::ObjectType;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 use std::thread;
 
-pub struct PipewireVolume {
-    current_info: Arc<Mutex<Option<VolumeInfo>>>,
-    update_txs: Arc<Mutex<Vec<Sender<()>>>>,
+/// Ключ metadata с именем устройства по умолчанию
+fn default_key(kind: AudioDeviceKind) -> &'static str {
+    match kind {
+        AudioDeviceKind::Sink => "default.audio.sink",
+        AudioDeviceKind::Source => "default.audio.source",
+    }
 }
 
-impl PipewireVolume {
-    pub fn new() -> Self {
+/// Ключ metadata, которым пользователь выбирает устройство по умолчанию
+/// (WirePlumber сохраняет выбор и обновляет `default.audio.*`)
+fn configured_key(kind: AudioDeviceKind) -> &'static str {
+    match kind {
+        AudioDeviceKind::Sink => "default.configured.audio.sink",
+        AudioDeviceKind::Source => "default.configured.audio.source",
+    }
+}
+
+/// Команды для потока PipeWire
+#[derive(Debug, Clone)]
+enum VolumeCommand {
+    SetVolume(AudioDeviceKind, u8),
+    SetMute(AudioDeviceKind, bool),
+    ToggleMute(AudioDeviceKind),
+    SetDefault(AudioDeviceKind, String),
+    SetStreamVolume(u32, u8),
+    SetStreamMute(u32, bool),
+}
+
+/// Узел PipeWire с громкостью в Props (устройство или поток приложения)
+struct VolumeControl {
+    proxy: pw::node::Node,
+    _listener: pw::node::NodeListener,
+    /// Линейная громкость каналов из Props `channelVolumes`
+    channel_volumes: Vec<f32>,
+    mute: bool,
+}
+
+impl VolumeControl {
+    fn new(proxy: pw::node::Node, listener: pw::node::NodeListener) -> Self {
+        proxy.subscribe_params(&[ParamType::Props]);
+
         Self {
-            current_info: Arc::new(Mutex::new(None)),
-            update_txs: Arc::new(Mutex::new(Vec::new())),
+            proxy,
+            _listener: listener,
+            channel_volumes: Vec::new(),
+            mute: false,
         }
     }
 
-    /// Запускает мониторинг изменений громкости через PipeWire
-    pub fn start_monitoring(&mut self, update_tx: Sender<()>) {
-        self.update_txs.lock().unwrap().push(update_tx.clone());
-        let current_info = Arc::clone(&self.current_info);
-        let update_txs = Arc::clone(&self.update_txs);
-
-        // Сначала получаем текущее состояние
-        if let Some(info) = Self::get_volume_info_internal() {
-            *current_info.lock().unwrap() = Some(info);
+    /// Громкость в процентах: PipeWire хранит линейную амплитуду,
+    /// пользователю показывается кубический корень (как в wpctl/pavucontrol)
+    fn volume_info(&self) -> Option<VolumeInfo> {
+        if self.channel_volumes.is_empty() {
+            return None;
         }
 
-        // Запускаем фоновый поток для мониторинга только один раз
-        if self.update_txs.lock().unwrap().len() == 1 {
-            thread::spawn(move || {
-                Self::monitor_pipewire_events(current_info, update_txs);
-            });
+        let average = self.channel_volumes.iter().sum::<f32>() / self.channel_volumes.len() as f32;
+        let volume = (average.cbrt() * 100.0).round().clamp(0.0, u8::MAX as f32) as u8;
+
+        Some(VolumeInfo { volume, muted: self.mute })
+    }
+
+    fn set_props(&self, property: Property) {
+        let props = Value::Object(Object {
+            type_: SpaTypes::ObjectParamProps.as_raw(),
+            id: ParamType::Props.as_raw(),
+            properties: vec![property],
+        });
+
+        let bytes = match PodSerializer::serialize(Cursor::new(Vec::new()), &props) {
+            Ok((cursor, _)) => cursor.into_inner(),
+            Err(e) => {
+                eprintln!("[PipewireVolume] ✗ Failed to serialize Props: {:?}", e);
+                return;
+            }
+        };
+
+        if let Some(pod) = Pod::from_bytes(&bytes) {
+            self.proxy.set_param(ParamType::Props, 0, pod);
         }
     }
 
-    /// Мониторинг событий PipeWire через API с подпиской на изменения
-    fn monitor_pipewire_events(current_info: Arc<Mutex<Option<VolumeInfo>>>, update_txs: Arc<Mutex<Vec<Sender<()>>>>) {
-        use pipewire as pw;
+    fn set_volume(&mut self, volume: u8) {
+        // Обратное преобразование: проценты -> линейная амплитуда
+        let linear = (volume as f32 / 100.0).powi(3);
+        let channels = self.channel_volumes.len().max(1);
+        let volumes = vec![linear; channels];
 
-        // Инициализируем PipeWire
-        pw::init();
+        self.set_props(Property {
+            key: pw::spa::sys::SPA_PROP_channelVolumes,
+            flags: PropertyFlags::empty(),
+            value: Value::ValueArray(ValueArray::Float(volumes.clone())),
+        });
 
-        let mainloop = pw::main_loop::MainLoop::new(None).expect("Failed to create PipeWire mainloop");
-        let context = pw::context::Context::new(&mainloop).expect("Failed to create PipeWire context");
-        let core = context.connect(None).expect("Failed to connect to PipeWire");
-        let registry = core.get_registry().expect("Failed to get registry");
-
-        let current_info_global = Arc::clone(&current_info);
-        let update_txs_global = Arc::clone(&update_txs);
-
-        let current_info_remove = Arc::clone(&current_info);
-        let update_txs_remove = Arc::clone(&update_txs);
-
-        // Подписываемся на глобальные события (добавление/удаление объектов)
-        let _listener = registry
-            .add_listener_local()
-            .global(move |_global| {
-                // Проверяем изменения при любом событии в реестре
-                if let Some(info) = Self::get_volume_info_internal() {
-                    let mut current = current_info_global.lock().unwrap();
-                    if current.as_ref() != Some(&info) {
-                        *current = Some(info);
-                        // Отправляем во все каналы
-                        for tx in update_txs_global.lock().unwrap().iter() {
-                            let _ = tx.try_send(());
-                        }
-                    }
-                }
-            })
-            .global_remove(move |_id| {
-                // Проверяем изменения при удалении объектов
-                if let Some(info) = Self::get_volume_info_internal() {
-                    let mut current = current_info_remove.lock().unwrap();
-                    if current.as_ref() != Some(&info) {
-                        *current = Some(info);
-                        // Отправляем во все каналы
-                        for tx in update_txs_remove.lock().unwrap().iter() {
-                            let _ = tx.try_send(());
-                        }
-                    }
-                }
-            })
-            .register();
+        // Оптимистичное обновление, подтверждение придёт событием param
+        self.channel_volumes = volumes;
+    }
 
-        mainloop.run();
+    fn set_mute(&mut self, mute: bool) {
+        self.set_props(Property {
+            key: pw::spa::sys::SPA_PROP_mute,
+            flags: PropertyFlags::empty(),
+            value: Value::Bool(mute),
+        });
+
+        self.mute = mute;
     }
+}
 
-    /// Парсит вывод wpctl get-volume для получения громкости
-    fn parse_volume_output(output: &str) -> Option<VolumeInfo> {
-        // Формат вывода: "Volume: 0.45 [MUTED]" или "Volume: 0.45"
-        let parts: Vec<&str> = output.split_whitespace().collect();
+/// Sink или source, найденный в реестре PipeWire
+struct AudioNode {
+    kind: AudioDeviceKind,
+    name: String,
+    description: String,
+    control: VolumeControl,
+}
 
-        if parts.len() < 2 {
-            return None;
-        }
+/// Поток воспроизведения приложения
+struct PlaybackStream {
+    app_name: String,
+    icon_name: Option<String>,
+    media_title: Option<String>,
+    control: VolumeControl,
+}
+
+impl PlaybackStream {
+    /// Обновляет описание потока из свойств узла (`media.name` меняется со сменой трека)
+    fn update_props(&mut self, props: &DictRef) {
+        self.app_name = props
+            .get("application.name")
+            .or_else(|| props.get("node.description"))
+            .or_else(|| props.get("node.name"))
+            .unwrap_or("Unknown")
+            .to_string();
+        self.icon_name = props
+            .get("application.icon-name")
+            .or_else(|| props.get("application.process.binary"))
+            .map(str::to_string);
+        self.media_title = props.get("media.name").map(str::to_string);
+    }
+}
+
+/// Поток захвата звука (приложение, использующее микрофон)
+struct CaptureStream {
+    _proxy: pw::node::Node,
+    _listener: pw::node::NodeListener,
+    running: bool,
+}
+
+/// Снимок состояния, доступный из других потоков
+#[derive(Debug, Clone, Default, PartialEq)]
+struct AudioSnapshot {
+    volume: Option<VolumeInfo>,
+    microphone: Option<MicrophoneInfo>,
+    devices: Vec<AudioDevice>,
+    streams: Vec<AudioStream>,
+}
+
+/// Состояние потока PipeWire
+#[derive(Default)]
+struct PipewireState {
+    nodes: HashMap<u32, AudioNode>,
+    playbacks: HashMap<u32, PlaybackStream>,
+    captures: HashMap<u32, CaptureStream>,
+    /// `node.name` устройств по умолчанию из metadata "default"
+    default_names: HashMap<AudioDeviceKind, String>,
+    metadata: Option<(pw::metadata::Metadata, pw::metadata::MetadataListener)>,
+}
 
-        // Парсим значение громкости
-        let volume_str = parts[1];
-        let volume_float: f32 = volume_str.parse().ok()?;
-        let volume = (volume_float * 100.0).round() as u8;
+impl PipewireState {
+    fn default_node(&self, kind: AudioDeviceKind) -> Option<&AudioNode> {
+        let name = self.default_names.get(&kind)?;
+        self.nodes.values().find(|n| n.kind == kind && n.name == *name)
+    }
 
-        // Проверяем наличие [MUTED]
-        let muted = output.contains("[MUTED]");
+    fn default_control_mut(&mut self, kind: AudioDeviceKind) -> Option<&mut VolumeControl> {
+        let name = self.default_names.get(&kind)?;
+        let node = self.nodes.values_mut().find(|n| n.kind == kind && n.name == *name);
+        if node.is_none() {
+            eprintln!("[PipewireVolume] ✗ No default {:?} device", kind);
+        }
+        node.map(|n| &mut n.control)
+    }
+
+    /// Громкость устройства или потока по id узла
+    fn control_mut(&mut self, id: u32) -> Option<&mut VolumeControl> {
+        if let Some(node) = self.nodes.get_mut(&id) {
+            return Some(&mut node.control);
+        }
+        self.playbacks.get_mut(&id).map(|s| &mut s.control)
+    }
 
-        Some(VolumeInfo { volume, muted })
+    fn remove(&mut self, id: u32) -> bool {
+        self.nodes.remove(&id).is_some()
+            || self.playbacks.remove(&id).is_some()
+            || self.captures.remove(&id).is_some()
     }
 
-    /// Получает ID дефолтного sink (аудио выхода)
-    fn get_default_sink_id() -> Option<String> {
-        // Используем @DEFAULT_AUDIO_SINK@ как универсальный идентификатор
-        Some("@DEFAULT_AUDIO_SINK@".to_string())
+    fn snapshot(&self) -> AudioSnapshot {
+        let volume = self
+            .default_node(AudioDeviceKind::Sink)
+            .and_then(|n| n.control.volume_info());
+
+        let in_use = self.captures.values().any(|s| s.running);
+        let microphone = self
+            .default_node(AudioDeviceKind::Source)
+            .and_then(|n| n.control.volume_info())
+            .map(|info| MicrophoneInfo {
+                volume: info.volume,
+                muted: info.muted,
+                in_use,
+            });
+
+        let mut devices: Vec<AudioDevice> = self
+            .nodes
+            .iter()
+            .map(|(id, node)| AudioDevice {
+                id: *id,
+                name: node.name.clone(),
+                description: node.description.clone(),
+                kind: node.kind,
+                is_default: self.default_names.get(&node.kind) == Some(&node.name),
+            })
+            .collect();
+        devices.sort_by(|a, b| a.description.cmp(&b.description));
+
+        // Потоки без Props ещё не готовы - показываем их после первого события param
+        let mut streams: Vec<AudioStream> = self
+            .playbacks
+            .iter()
+            .filter_map(|(id, stream)| {
+                let info = stream.control.volume_info()?;
+                Some(AudioStream {
+                    id: *id,
+                    app_name: stream.app_name.clone(),
+                    icon_name: stream.icon_name.clone(),
+                    media_title: stream.media_title.clone(),
+                    volume: info.volume,
+                    muted: info.muted,
+                })
+            })
+            .collect();
+        streams.sort_by(|a, b| a.app_name.cmp(&b.app_name).then(a.id.cmp(&b.id)));
+
+        AudioSnapshot { volume, microphone, devices, streams }
     }
 
-    /// Внутренний метод для получения информации о громкости (используется для мониторинга)
-    fn get_volume_info_internal() -> Option<VolumeInfo> {
-        let sink_id = Self::get_default_sink_id()?;
+    fn execute(&mut self, command: VolumeCommand) {
+        match command {
+            VolumeCommand::SetVolume(kind, volume) => {
+                if let Some(control) = self.default_control_mut(kind) {
+                    control.set_volume(volume);
+                }
+            }
+            VolumeCommand::SetMute(kind, mute) => {
+                if let Some(control) = self.default_control_mut(kind) {
+                    control.set_mute(mute);
+                }
+            }
+            VolumeCommand::ToggleMute(kind) => {
+                if let Some(control) = self.default_control_mut(kind) {
+                    control.set_mute(!control.mute);
+                }
+            }
+            VolumeCommand::SetDefault(kind, name) => {
+                let Some((metadata, _)) = self.metadata.as_ref() else {
+                    eprintln!("[PipewireVolume] ✗ Default metadata is not available");
+                    return;
+                };
 
-        let output = Command::new("wpctl")
-            .args(&["get-volume", &sink_id])
-            .output()
-            .ok()?;
+                let value = serde_json::json!({ "name": name }).to_string();
+                metadata.set_property(0, configured_key(kind), Some("Spa:String:JSON"), Some(&value));
+                eprintln!("[PipewireVolume] ✓ Default {:?} set to {}", kind, name);
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Self::parse_volume_output(&stdout)
+                self.default_names.insert(kind, name);
+            }
+            VolumeCommand::SetStreamVolume(id, volume) => match self.playbacks.get_mut(&id) {
+                Some(stream) => stream.control.set_volume(volume),
+                None => eprintln!("[PipewireVolume] ✗ Stream {} not found", id),
+            },
+            VolumeCommand::SetStreamMute(id, mute) => match self.playbacks.get_mut(&id) {
+                Some(stream) => stream.control.set_mute(mute),
+                None => eprintln!("[PipewireVolume] ✗ Stream {} not found", id),
+            },
+        }
     }
 }
 
-impl VolumeService for PipewireVolume {
-    fn get_volume_info(&self) -> Option<VolumeInfo> {
-        // Возвращаем кэшированное значение, которое обновляется через мониторинг
-        self.current_info.lock().unwrap().clone()
+/// Общие для потоков данные: снимок состояния и подписчики
+#[derive(Clone)]
+struct Shared {
+    snapshot: Arc<Mutex<AudioSnapshot>>,
+    update_txs: Arc<Mutex<Vec<Sender<()>>>>,
+}
+
+impl Shared {
+    /// Обновляет снимок и уведомляет подписчиков, если он изменился
+    fn publish(&self, state: &PipewireState) {
+        let snapshot = state.snapshot();
+
+        let mut current = self.snapshot.lock().unwrap();
+        if *current == snapshot {
+            return;
+        }
+        *current = snapshot;
+        drop(current);
+
+        for tx in self.update_txs.lock().unwrap().iter() {
+            let _ = tx.try_send(());
+        }
     }
+}
 
-    fn set_volume(&self, volume: u8) -> Result<(), String> {
-        let volume = volume.min(100);
-        let volume_float = volume as f32 / 100.0;
+pub struct PipewireVolume {
+    shared: Shared,
+    commands: Mutex<Option<pw::channel::Sender<VolumeCommand>>>,
+}
 
-        let sink_id = Self::get_default_sink_id()
-            .ok_or_else(|| "Failed to get default sink ID".to_string())?;
+impl PipewireVolume {
+    pub fn new() -> Self {
+        Self {
+            shared: Shared {
+                snapshot: Arc::new(Mutex::new(AudioSnapshot::default())),
+                update_txs: Arc::new(Mutex::new(Vec::new())),
+            },
+            commands: Mutex::new(None),
+        }
+    }
 
-        let output = Command::new("wpctl")
-            .args(&["set-volume", &sink_id, &format!("{:.2}", volume_float)])
-            .output()
-            .map_err(|e| format!("Failed to execute wpctl: {}", e))?;
+    /// Запускает мониторинг изменений громкости, устройств и потоков через PipeWire
+    pub fn start_monitoring(&mut self, update_tx: Sender<()>) {
+        self.shared.update_txs.lock().unwrap().push(update_tx);
 
-        if !output.status.success() {
-            return Err(format!("wpctl failed with status: {}", output.status));
+        // Запускаем фоновый поток только один раз
+        let mut commands = self.commands.lock().unwrap();
+        if commands.is_some() {
+            return;
         }
 
-        // Обновляем кэшированное значение
-        if let Some(info) = Self::get_volume_info_internal() {
-            *self.current_info.lock().unwrap() = Some(info);
-            // Отправляем уведомление об обновлении во все каналы
-            for tx in self.update_txs.lock().unwrap().iter() {
-                let _ = tx.try_send(());
+        let (command_tx, command_rx) = pw::channel::channel::<VolumeCommand>();
+        *commands = Some(command_tx);
+
+        let shared = self.shared.clone();
+        thread::spawn(move || {
+            if let Err(e) = Self::run_pipewire_loop(shared, command_rx) {
+                eprintln!("[PipewireVolume] ✗ PipeWire loop failed: {}", e);
             }
-        }
+        });
+    }
+
+    /// Основной цикл PipeWire: отслеживание узлов, metadata и выполнение команд
+    fn run_pipewire_loop(
+        shared: Shared,
+        command_rx: pw::channel::Receiver<VolumeCommand>,
+    ) -> Result<(), pw::Error> {
+        pw::init();
+
+        let mainloop = pw::main_loop::MainLoop::new(None)?;
+        let context = pw::context::Context::new(&mainloop)?;
+        let core = context.connect(None)?;
+        let registry = Rc::new(core.get_registry()?);
+
+        let state = Rc::new(RefCell::new(PipewireState::default()));
+
+        // Команды из UI-потока
+        let _commands = {
+            let state = state.clone();
+            let shared = shared.clone();
+            command_rx.attach(mainloop.loop_(), move |command| {
+                let mut state = state.borrow_mut();
+                state.execute(command);
+                shared.publish(&state);
+            })
+        };
+
+        let _registry_listener = {
+            let registry_weak = Rc::downgrade(&registry);
+            let state_global = state.clone();
+            let state_remove = state.clone();
+            let shared_global = shared.clone();
+            let shared_remove = shared.clone();
+
+            registry
+                .add_listener_local()
+                .global(move |global| {
+                    let Some(registry) = registry_weak.upgrade() else {
+                        return;
+                    };
+                    let props = global.props;
+
+                    match global.type_ {
+                        ObjectType::Node => {
+                            let media_class = props.and_then(|p| p.get("media.class"));
+                            let is_monitor = props.and_then(|p| p.get("stream.monitor")) == Some("true");
+
+                            let role = match media_class {
+                                Some("Audio/Sink") => NodeRole::Device(AudioDeviceKind::Sink),
+                                Some("Audio/Source") => NodeRole::Device(AudioDeviceKind::Source),
+                                Some("Stream/Output/Audio") => NodeRole::Playback,
+                                // Измерители уровня (pavucontrol и т.п.) не считаются использованием микрофона
+                                Some("Stream/Input/Audio") if !is_monitor => NodeRole::Capture,
+                                _ => return,
+                            };
+
+                            bind_node(&registry, global, role, &state_global, &shared_global);
+                        }
+                        ObjectType::Metadata => {
+                            if props.and_then(|p| p.get("metadata.name")) == Some("default") {
+                                bind_metadata(&registry, global, &state_global, &shared_global);
+                            }
+                        }
+                        _ => {}
+                    }
+                })
+                .global_remove(move |id| {
+                    let mut state = state_remove.borrow_mut();
+                    if state.remove(id) {
+                        shared_remove.publish(&state);
+                    }
+                })
+                .register()
+        };
 
+        eprintln!("[PipewireVolume] ✓ Connected to PipeWire");
+        mainloop.run();
         Ok(())
     }
 
-    fn toggle_mute(&self) -> Result<(), String> {
-        let sink_id = Self::get_default_sink_id()
-            .ok_or_else(|| "Failed to get default sink ID".to_string())?;
+    fn send(&self, command: VolumeCommand) -> Result<(), String> {
+        let commands = self.commands.lock().unwrap();
+        let sender = commands
+            .as_ref()
+            .ok_or_else(|| "PipeWire monitoring is not started".to_string())?;
+
+        sender
+            .send(command)
+            .map_err(|_| "PipeWire loop is not running".to_string())
+    }
+}
 
-        let output = Command::new("wpctl")
-            .args(&["set-mute", &sink_id, "toggle"])
-            .output()
-            .map_err(|e| format!("Failed to execute wpctl: {}", e))?;
+/// Чем является узел PipeWire для hyprline
+#[derive(Debug, Clone, Copy)]
+enum NodeRole {
+    Device(AudioDeviceKind),
+    Playback,
+    Capture,
+}
+
+/// Привязывает узел: Props для громкости, info для состояния и свойств потока
+fn bind_node(
+    registry: &Registry,
+    global: &GlobalObject<&DictRef>,
+    role: NodeRole,
+    state: &Rc<RefCell<PipewireState>>,
+    shared: &Shared,
+) {
+    let proxy: pw::node::Node = match registry.bind(global) {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            eprintln!("[PipewireVolume] ✗ Failed to bind node {}: {}", global.id, e);
+            return;
+        }
+    };
+
+    let id = global.id;
+    let state_param = state.clone();
+    let state_info = state.clone();
+    let shared_param = shared.clone();
+    let shared_info = shared.clone();
+    let listener = proxy
+        .add_listener_local()
+        .param(move |_seq, param_type, _index, _next, param| {
+            if param_type != ParamType::Props {
+                return;
+            }
+            let Some((volumes, mute)) = param.and_then(parse_props) else {
+                return;
+            };
+
+            let mut state = state_param.borrow_mut();
+            if let Some(control) = state.control_mut(id) {
+                if let Some(volumes) = volumes {
+                    control.channel_volumes = volumes;
+                }
+                if let Some(mute) = mute {
+                    control.mute = mute;
+                }
+            }
+            shared_param.publish(&state);
+        })
+        .info(move |info| {
+            let mut state = state_info.borrow_mut();
+            if let Some(capture) = state.captures.get_mut(&id) {
+                capture.running = matches!(info.state(), NodeState::Running);
+            }
+            if let (Some(playback), Some(props)) = (state.playbacks.get_mut(&id), info.props()) {
+                playback.update_props(props);
+            }
+            shared_info.publish(&state);
+        })
+        .register();
+
+    let props = global.props;
+    let mut state = state.borrow_mut();
+
+    match role {
+        NodeRole::Device(kind) => {
+            let name = props.and_then(|p| p.get("node.name")).unwrap_or_default().to_string();
+            let description = props
+                .and_then(|p| p.get("node.description").or_else(|| p.get("node.nick")))
+                .map(str::to_string)
+                .unwrap_or_else(|| name.clone());
+
+            state.nodes.insert(id, AudioNode {
+                kind,
+                name,
+                description,
+                control: VolumeControl::new(proxy, listener),
+            });
+        }
+        NodeRole::Playback => {
+            let mut stream = PlaybackStream {
+                app_name: String::new(),
+                icon_name: None,
+                media_title: None,
+                control: VolumeControl::new(proxy, listener),
+            };
+            if let Some(props) = props {
+                stream.update_props(props);
+            }
+            state.playbacks.insert(id, stream);
+        }
+        NodeRole::Capture => {
+            state.captures.insert(id, CaptureStream {
+                _proxy: proxy,
+                _listener: listener,
+                running: false,
+            });
+        }
+    }
+}
 
-        if !output.status.success() {
-            return Err(format!("wpctl failed with status: {}", output.status));
+/// Следит за устройствами по умолчанию в metadata "default"
+fn bind_metadata(
+    registry: &Registry,
+    global: &GlobalObject<&DictRef>,
+    state: &Rc<RefCell<PipewireState>>,
+    shared: &Shared,
+) {
+    let metadata: pw::metadata::Metadata = match registry.bind(global) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            eprintln!("[PipewireVolume] ✗ Failed to bind metadata: {}", e);
+            return;
         }
+    };
+
+    let state_property = state.clone();
+    let shared = shared.clone();
+    let listener = metadata
+        .add_listener_local()
+        .property(move |_subject, key, _type, value| {
+            let kind = [AudioDeviceKind::Sink, AudioDeviceKind::Source]
+                .into_iter()
+                .find(|kind| key == Some(default_key(*kind)));
+
+            if let Some(kind) = kind {
+                let name = value.and_then(parse_metadata_name);
+                eprintln!("[PipewireVolume] Default {:?}: {:?}", kind, name);
+
+                let mut state = state_property.borrow_mut();
+                match name {
+                    Some(name) => state.default_names.insert(kind, name),
+                    None => state.default_names.remove(&kind),
+                };
+                shared.publish(&state);
+            }
+            0
+        })
+        .register();
+
+    state.borrow_mut().metadata = Some((metadata, listener));
+}
 
-        // Обновляем кэшированное значение
-        if let Some(info) = Self::get_volume_info_internal() {
-            *self.current_info.lock().unwrap() = Some(info);
-            // Отправляем уведомление об обновлении во все каналы
-            for tx in self.update_txs.lock().unwrap().iter() {
-                let _ = tx.try_send(());
+/// Разбирает Props: (`channelVolumes`, `mute`)
+fn parse_props(param: &Pod) -> Option<(Option<Vec<f32>>, Option<bool>)> {
+    let (_, value) = PodDeserializer::deserialize_any_from(param.as_bytes()).ok()?;
+    let Value::Object(object) = value else {
+        return None;
+    };
+
+    let mut volumes = None;
+    let mut mute = None;
+
+    for property in object.properties {
+        match (property.key, property.value) {
+            (pw::spa::sys::SPA_PROP_channelVolumes, Value::ValueArray(ValueArray::Float(v))) => {
+                volumes = Some(v);
             }
+            (pw::spa::sys::SPA_PROP_mute, Value::Bool(m)) => mute = Some(m),
+            _ => {}
         }
+    }
 
-        Ok(())
+    Some((volumes, mute))
+}
+
+/// Значение metadata вида `{"name":"alsa_output..."}`
+fn parse_metadata_name(value: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(value)
+        .ok()?
+        .get("name")?
+        .as_str()
+        .map(str::to_string)
+}
+
+impl VolumeService for PipewireVolume {
+    fn get_volume_info(&self) -> Option<VolumeInfo> {
+        // Возвращаем кэшированное значение, которое обновляется через мониторинг
+        self.shared.snapshot.lock().unwrap().volume.clone()
+    }
+
+    fn set_volume(&self, volume: u8) -> Result<(), String> {
+        self.send(VolumeCommand::SetVolume(AudioDeviceKind::Sink, volume.min(100)))
+    }
+
+    fn toggle_mute(&self) -> Result<(), String> {
+        self.send(VolumeCommand::ToggleMute(AudioDeviceKind::Sink))
     }
 
     fn set_mute(&self, muted: bool) -> Result<(), String> {
-        let sink_id = Self::get_default_sink_id()
-            .ok_or_else(|| "Failed to get default sink ID".to_string())?;
+        self.send(VolumeCommand::SetMute(AudioDeviceKind::Sink, muted))
+    }
 
-        let mute_arg = if muted { "1" } else { "0" };
+    fn list_devices(&self, kind: AudioDeviceKind) -> Vec<AudioDevice> {
+        self.shared
+            .snapshot
+            .lock()
+            .unwrap()
+            .devices
+            .iter()
+            .filter(|d| d.kind == kind)
+            .cloned()
+            .collect()
+    }
 
-        let output = Command::new("wpctl")
-            .args(&["set-mute", &sink_id, mute_arg])
-            .output()
-            .map_err(|e| format!("Failed to execute wpctl: {}", e))?;
+    fn set_default_device(&self, kind: AudioDeviceKind, name: &str) -> Result<(), String> {
+        self.send(VolumeCommand::SetDefault(kind, name.to_string()))
+    }
 
-        if !output.status.success() {
-            return Err(format!("wpctl failed with status: {}", output.status));
-        }
+    fn get_microphone_info(&self) -> Option<MicrophoneInfo> {
+        self.shared.snapshot.lock().unwrap().microphone.clone()
+    }
 
-        // Обновляем кэшированное значение
-        if let Some(info) = Self::get_volume_info_internal() {
-            *self.current_info.lock().unwrap() = Some(info);
-            // Отправляем уведомление об обновлении во все каналы
-            for tx in self.update_txs.lock().unwrap().iter() {
-                let _ = tx.try_send(());
-            }
-        }
+    fn set_microphone_volume(&self, volume: u8) -> Result<(), String> {
+        self.send(VolumeCommand::SetVolume(AudioDeviceKind::Source, volume.min(100)))
+    }
 
-        Ok(())
+    fn toggle_microphone_mute(&self) -> Result<(), String> {
+        self.send(VolumeCommand::ToggleMute(AudioDeviceKind::Source))
+    }
+
+    fn set_microphone_mute(&self, muted: bool) -> Result<(), String> {
+        self.send(VolumeCommand::SetMute(AudioDeviceKind::Source, muted))
+    }
+
+    fn list_streams(&self) -> Vec<AudioStream> {
+        self.shared.snapshot.lock().unwrap().streams.clone()
+    }
+
+    fn set_stream_volume(&self, id: u32, volume: u8) -> Result<(), String> {
+        self.send(VolumeCommand::SetStreamVolume(id, volume.min(100)))
+    }
+
+    fn set_stream_mute(&self, id: u32, muted: bool) -> Result<(), String> {
+        self.send(VolumeCommand::SetStreamMute(id, muted))
     }
 }
 
@@ -220,4 +701,3 @@ impl VolumeService for PipewireVolume {
 pub fn create_volume_channel() -> (Sender<()>, async_channel::Receiver<()>) {
     async_channel::unbounded()
 }
-
@@ -0,0 +1,84 @@
+use crate::domain::models::PowerProfile;
+use crate::domain::power_profile_service::PowerProfileService;
+use async_channel::Sender;
+use std::collections::HashMap;
+use zbus::zvariant::OwnedValue;
+use zbus::{blocking::Connection, proxy};
+
+#[proxy(
+    interface = "net.hadess.PowerProfiles",
+    default_service = "net.hadess.PowerProfiles",
+    default_path = "/net/hadess/PowerProfiles"
+)]
+trait PowerProfiles {
+    #[zbus(property)]
+    fn active_profile(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn set_active_profile(&self, profile: &str) -> zbus::Result<()>;
+
+    /// Поддерживаемые профили: словари с ключами `Profile`, `Driver`, ...
+    #[zbus(property)]
+    fn profiles(&self) -> zbus::Result<Vec<HashMap<String, OwnedValue>>>;
+}
+
+/// Профили питания через power-profiles-daemon (`net.hadess.PowerProfiles`)
+pub struct DbusPowerProfiles {
+    proxy: Option<PowerProfilesProxyBlocking<'static>>,
+}
+
+impl DbusPowerProfiles {
+    pub fn new() -> Self {
+        let proxy = Connection::system()
+            .and_then(|conn| PowerProfilesProxyBlocking::new(&conn))
+            .inspect_err(|e| eprintln!("[PowerProfiles] ✗ Not available: {}", e))
+            .ok();
+
+        Self { proxy }
+    }
+
+    /// Следит за сменой профиля (в т.ч. из других программ)
+    pub fn start_monitoring(&self, tx: Sender<()>) {
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+
+        std::thread::spawn(move || {
+            for _ in proxy.receive_active_profile_changed() {
+                if tx.send_blocking(()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+impl PowerProfileService for DbusPowerProfiles {
+    fn available_profiles(&self) -> Vec<PowerProfile> {
+        let Some(proxy) = &self.proxy else {
+            return Vec::new();
+        };
+
+        proxy
+            .profiles()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|profile| profile.get("Profile")?.downcast_ref::<&str>().ok())
+            .filter_map(PowerProfile::from_id)
+            .collect()
+    }
+
+    fn active_profile(&self) -> Option<PowerProfile> {
+        let profile = self.proxy.as_ref()?.active_profile().ok()?;
+        PowerProfile::from_id(&profile)
+    }
+
+    fn set_profile(&self, profile: PowerProfile) -> Result<(), String> {
+        let proxy = self.proxy.as_ref().ok_or("power-profiles-daemon is not available")?;
+        proxy
+            .set_active_profile(profile.id())
+            .map_err(|e| format!("Failed to set power profile: {}", e))?;
+        eprintln!("[PowerProfiles] ✓ Switched to {}", profile.id());
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::domain::models::{Notification, NotificationUrgency};
+use crate::domain::models::{Notification, NotificationFilter, NotificationUrgency};
 use crate::domain::notification_service::NotificationService;
 use crate::infrastructure::notification_client::{NotificationClient, NotificationData};
 use std::sync::Mutex;
@@ -73,6 +73,9 @@ impl RemoteNotificationService {
             },
             timestamp,
             actions: Vec::new(),
+            has_image: data.has_image,
+            desktop_entry: data.desktop_entry,
+            category: data.category,
         }
     }
 }
@@ -125,6 +128,47 @@ impl NotificationService for RemoteNotificationService {
         }
     }
 
+    fn search_history(&self, filter: &NotificationFilter) -> Vec<Notification> {
+        if !self.ensure_connected() {
+            return Vec::new();
+        }
+
+        let urgency = match filter.urgency {
+            Some(NotificationUrgency::Low) => 0,
+            Some(NotificationUrgency::Normal) => 1,
+            Some(NotificationUrgency::Critical) => 2,
+            None => -1,
+        };
+        let since = filter
+            .since
+            .and_then(|since| since.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_secs() as i64)
+            .unwrap_or(0);
+
+        let client = self.client.lock().unwrap();
+        if let Some(ref c) = *client {
+            c.search_history(&filter.query, urgency, since)
+                .unwrap_or_else(|e| {
+                    eprintln!("[NotificationService] ✗ {}", e);
+                    Vec::new()
+                })
+                .into_iter()
+                .map(Self::convert_notification)
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_image(&self, id: u32) -> Option<Vec<u8>> {
+        if !self.ensure_connected() {
+            return None;
+        }
+
+        let client = self.client.lock().unwrap();
+        client.as_ref()?.get_image(id).ok().flatten()
+    }
+
     fn clear_history(&self) {
         if !self.ensure_connected() {
             return;
@@ -147,6 +191,17 @@ impl NotificationService for RemoteNotificationService {
         }
     }
 
+    fn clear_app(&self, app_name: &str) {
+        if !self.ensure_connected() {
+            return;
+        }
+
+        let client = self.client.lock().unwrap();
+        if let Some(ref c) = *client {
+            let _ = c.clear_app(app_name);
+        }
+    }
+
     fn show_history_window(&self) {
         if !self.ensure_connected() {
             return;
@@ -157,4 +212,26 @@ impl NotificationService for RemoteNotificationService {
             let _ = c.show_history_window();
         }
     }
+
+    fn is_dnd_enabled(&self) -> bool {
+        if !self.ensure_connected() {
+            return false;
+        }
+
+        let client = self.client.lock().unwrap();
+        client.as_ref().and_then(|c| c.get_dnd_enabled().ok()).unwrap_or(false)
+    }
+
+    fn set_dnd_enabled(&self, enabled: bool) {
+        if !self.ensure_connected() {
+            return;
+        }
+
+        let client = self.client.lock().unwrap();
+        if let Some(ref c) = *client {
+            if let Err(e) = c.set_dnd_enabled(enabled) {
+                eprintln!("[NotificationService] ✗ {}", e);
+            }
+        }
+    }
 }
@@ -0,0 +1,224 @@
+use crate::domain::brightness_service::BrightnessService;
+use crate::domain::models::BrightnessDisplay;
+use crate::infrastructure::display_brightness::{internal_display, INTERNAL_DISPLAY_ID};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zbus::{proxy, Connection};
+
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+/// Сессия logind: запись яркости без прав root
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait LoginSession {
+    /// Устанавливает яркость устройства `subsystem/name` в единицах драйвера
+    #[zbus(name = "SetBrightness")]
+    async fn set_brightness(&self, subsystem: &str, name: &str, brightness: u32) -> zbus::Result<()>;
+}
+
+/// Яркость подсветки через `/sys/class/backlight` и logind
+///
+/// Используется, когда Lumen недоступен. Чтение - из sysfs, запись - через
+/// `org.freedesktop.login1.Session.SetBrightness`, изменения отслеживаются по
+/// inotify на `actual_brightness`. Автоматическая регулировка не поддерживается.
+pub struct SysfsBrightnessService {
+    /// Имя устройства в `/sys/class/backlight`
+    name: String,
+    path: PathBuf,
+    max_brightness: u32,
+    callback: Arc<Mutex<Option<Arc<dyn Fn(u32) + Send + Sync>>>>,
+}
+
+impl SysfsBrightnessService {
+    pub fn new() -> Result<Self, String> {
+        let path = Self::find_device()?;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or("Invalid backlight device path")?;
+
+        let max_brightness = read_u32(&path.join("max_brightness"))?;
+        if max_brightness == 0 {
+            return Err(format!("Backlight {} reports max_brightness 0", name));
+        }
+
+        eprintln!("[Brightness] ✓ Using sysfs backlight {} (max {})", name, max_brightness);
+
+        Ok(Self {
+            name,
+            path,
+            max_brightness,
+            callback: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Выбирает устройство подсветки: firmware, затем platform, затем raw
+    ///
+    /// Такой же порядок использует ядро и большинство утилит - raw-интерфейсы
+    /// GPU обычно дублируют более точные firmware/platform.
+    fn find_device() -> Result<PathBuf, String> {
+        let entries = fs::read_dir(BACKLIGHT_DIR)
+            .map_err(|e| format!("Failed to read {}: {}", BACKLIGHT_DIR, e))?;
+
+        let mut devices: Vec<(u8, PathBuf)> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .map(|path| {
+                let kind = fs::read_to_string(path.join("type")).unwrap_or_default();
+                let priority = match kind.trim() {
+                    "firmware" => 0,
+                    "platform" => 1,
+                    _ => 2,
+                };
+                (priority, path)
+            })
+            .collect();
+
+        devices.sort();
+        devices
+            .into_iter()
+            .next()
+            .map(|(_, path)| path)
+            .ok_or_else(|| "No backlight devices found".to_string())
+    }
+
+    fn read_percent(path: &Path, max_brightness: u32) -> Result<u32, String> {
+        // actual_brightness - фактическое значение от драйвера; у некоторых его нет
+        let raw = read_u32(&path.join("actual_brightness"))
+            .or_else(|_| read_u32(&path.join("brightness")))?;
+        Ok(((raw as f64 / max_brightness as f64) * 100.0).round() as u32)
+    }
+
+    /// Запускает отслеживание изменений яркости (клавиши, другие программы)
+    pub fn start_monitoring(self: Arc<Self>) {
+        let callback = self.callback.clone();
+        let path = self.path.clone();
+        let max_brightness = self.max_brightness;
+
+        std::thread::spawn(move || {
+            let (notify_tx, notify_rx) = std::sync::mpsc::channel::<Result<Event, notify::Error>>();
+
+            let mut watcher = match notify::recommended_watcher(notify_tx) {
+                Ok(w) => w,
+                Err(e) => {
+                    eprintln!("[Brightness] ✗ Failed to create watcher: {}", e);
+                    return;
+                }
+            };
+
+            let actual = path.join("actual_brightness");
+            let watched = if actual.exists() { actual } else { path.join("brightness") };
+            if let Err(e) = watcher.watch(&watched, RecursiveMode::NonRecursive) {
+                eprintln!("[Brightness] ✗ Failed to watch {:?}: {}", watched, e);
+                return;
+            }
+
+            eprintln!("[Brightness] ✓ Watching {:?}", watched);
+
+            let mut last = Self::read_percent(&path, max_brightness).ok();
+
+            while let Ok(res) = notify_rx.recv() {
+                match res {
+                    Ok(event) if matches!(event.kind, EventKind::Modify(_)) => {}
+                    Ok(_) => continue,
+                    Err(e) => {
+                        eprintln!("[Brightness] Watch error: {}", e);
+                        continue;
+                    }
+                }
+
+                let Ok(brightness) = Self::read_percent(&path, max_brightness) else {
+                    continue;
+                };
+                if last == Some(brightness) {
+                    continue;
+                }
+                last = Some(brightness);
+
+                if let Some(cb) = callback.lock().as_ref() {
+                    cb(brightness);
+                }
+            }
+        });
+    }
+}
+
+impl BrightnessService for SysfsBrightnessService {
+    fn list_displays(&self) -> Vec<BrightnessDisplay> {
+        self.get_brightness()
+            .map(|brightness| vec![internal_display(brightness)])
+            .unwrap_or_default()
+    }
+
+    fn set_display_brightness(&self, id: &str, value: u32) -> Result<(), String> {
+        if id != INTERNAL_DISPLAY_ID {
+            return Err(format!("Unknown display: {}", id));
+        }
+        self.set_brightness(value)
+    }
+
+    fn get_brightness(&self) -> Result<u32, String> {
+        Self::read_percent(&self.path, self.max_brightness)
+    }
+
+    fn set_brightness(&self, value: u32) -> Result<(), String> {
+        let raw = ((value.min(100) as f64 / 100.0) * self.max_brightness as f64).round() as u32;
+
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| format!("Failed to create runtime: {}", e))?;
+
+        rt.block_on(async {
+            let connection = Connection::system().await
+                .map_err(|e| format!("Failed to connect to system bus: {}", e))?;
+            let proxy = LoginSessionProxy::new(&connection).await
+                .map_err(|e| format!("Failed to create logind proxy: {}", e))?;
+            proxy.set_brightness("backlight", &self.name, raw).await
+                .map_err(|e| format!("Failed to set brightness: {}", e))
+        })
+    }
+
+    fn increase_brightness(&self, percent: u32) -> Result<(), String> {
+        let current = self.get_brightness()?;
+        self.set_brightness((current + percent).min(100))
+    }
+
+    fn decrease_brightness(&self, percent: u32) -> Result<(), String> {
+        let current = self.get_brightness()?;
+        self.set_brightness(current.saturating_sub(percent))
+    }
+
+    fn supports_auto_adjustment(&self) -> bool {
+        false
+    }
+
+    fn enable_auto_adjustment(&self) -> Result<(), String> {
+        Err("Auto adjustment is not supported by the sysfs backlight backend".to_string())
+    }
+
+    fn disable_auto_adjustment(&self) -> Result<(), String> {
+        // Выключать нечего
+        Ok(())
+    }
+
+    fn is_auto_adjustment_enabled(&self) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    fn subscribe_brightness_changed(&self, callback: Arc<dyn Fn(u32) + Send + Sync>) {
+        *self.callback.lock() = Some(callback);
+    }
+}
+
+fn read_u32(path: &Path) -> Result<u32, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?
+        .trim()
+        .parse()
+        .map_err(|e| format!("Invalid value in {:?}: {}", path, e))
+}
@@ -1,28 +1,150 @@
 use crate::domain::battery_service::BatteryService;
-use crate::domain::models::{BatteryInfo, BatteryStatus};
+use crate::domain::models::{BatteryInfo, BatteryKind, BatteryStatus};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use async_channel::Sender;
-use zbus::Connection;
-use zbus::fdo::PropertiesProxy;
 use futures::stream::StreamExt;
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+use zbus::{proxy, Connection, MatchRule, MessageStream};
 
+const UPOWER_SERVICE: &str = "org.freedesktop.UPower";
+/// Все устройства UPower (включая DisplayDevice) лежат под этим путём
+const UPOWER_DEVICES_PATH: &str = "/org/freedesktop/UPower/devices";
+
+/// UPower `Type` для блока питания - у него нет заряда
+const TYPE_LINE_POWER: u32 = 1;
+
+#[proxy(
+    interface = "org.freedesktop.UPower",
+    default_service = "org.freedesktop.UPower",
+    default_path = "/org/freedesktop/UPower"
+)]
+trait UPower {
+    fn enumerate_devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+
+    /// Составное устройство: все батареи ноутбука как одна
+    fn get_display_device(&self) -> zbus::Result<OwnedObjectPath>;
+
+    #[zbus(signal)]
+    fn device_added(&self, device: ObjectPath<'_>) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn device_removed(&self, device: ObjectPath<'_>) -> zbus::Result<()>;
+}
+
+#[proxy(interface = "org.freedesktop.UPower.Device", default_service = "org.freedesktop.UPower")]
+trait UPowerDevice {
+    #[zbus(property, name = "Type")]
+    fn kind(&self) -> zbus::Result<u32>;
+
+    /// Питает систему (батарея ноутбука, ИБП), а не периферию
+    #[zbus(property)]
+    fn power_supply(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn is_present(&self) -> zbus::Result<bool>;
+
+    #[zbus(property)]
+    fn percentage(&self) -> zbus::Result<f64>;
+
+    #[zbus(property)]
+    fn state(&self) -> zbus::Result<u32>;
+
+    #[zbus(property)]
+    fn time_to_empty(&self) -> zbus::Result<i64>;
+
+    #[zbus(property)]
+    fn time_to_full(&self) -> zbus::Result<i64>;
+
+    #[zbus(property)]
+    fn energy_rate(&self) -> zbus::Result<f64>;
+
+    /// Текущая ёмкость относительно паспортной, %
+    #[zbus(property)]
+    fn capacity(&self) -> zbus::Result<f64>;
+
+    #[zbus(property)]
+    fn model(&self) -> zbus::Result<String>;
+}
+
+/// Устройство UPower с зарядом
+struct Device {
+    info: BatteryInfo,
+    power_supply: bool,
+    present: bool,
+}
+
+/// Изменение, пришедшее от UPower
+enum DeviceEvent {
+    Changed(String),
+    Added(String),
+    Removed(String),
+}
+
+#[derive(Default)]
+struct BatteryState {
+    /// Составная батарея (DisplayDevice)
+    display: Option<BatteryInfo>,
+    /// Все устройства, кроме DisplayDevice, по пути D-Bus
+    devices: HashMap<String, Device>,
+}
+
+impl BatteryState {
+    /// Батарея ноутбука: DisplayDevice с моделью и здоровьем отдельных батарей
+    fn main_battery(&self) -> Option<BatteryInfo> {
+        let mut info = self.display.clone()?;
+
+        let mut batteries: Vec<&Device> = self
+            .devices
+            .values()
+            .filter(|d| d.power_supply && d.present && d.info.kind == BatteryKind::Battery)
+            .collect();
+        batteries.sort_by(|a, b| a.info.model.cmp(&b.info.model));
+
+        info.model = batteries
+            .iter()
+            .map(|d| d.info.model.as_str())
+            .filter(|model| !model.is_empty())
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        let health: Vec<u8> = batteries.iter().filter_map(|d| d.info.health).collect();
+        if !health.is_empty() {
+            let average = health.iter().map(|&h| h as u32).sum::<u32>() / health.len() as u32;
+            info.health = Some(average as u8);
+        }
+
+        Some(info)
+    }
+
+    fn peripherals(&self) -> Vec<BatteryInfo> {
+        let mut peripherals: Vec<BatteryInfo> = self
+            .devices
+            .values()
+            .filter(|d| !d.power_supply && (d.present || d.info.percentage > 0))
+            .map(|d| d.info.clone())
+            .collect();
+        peripherals.sort_by(|a, b| a.kind.name().cmp(b.kind.name()).then_with(|| a.model.cmp(&b.model)));
+        peripherals
+    }
+}
+
+/// Батареи через UPower: основная (все батареи ноутбука) и периферия
 pub struct SystemBatteryService {
-    cached_info: Arc<Mutex<Option<BatteryInfo>>>,
-    battery_path: Arc<Mutex<Option<String>>>,
+    state: Arc<Mutex<BatteryState>>,
 }
 
 impl SystemBatteryService {
     pub fn new() -> Self {
         Self {
-            cached_info: Arc::new(Mutex::new(None)),
-            battery_path: Arc::new(Mutex::new(None)),
+            state: Arc::new(Mutex::new(BatteryState::default())),
         }
     }
 
     /// Запускает мониторинг событий батареи и возвращает канал для получения событий
     pub fn start_monitoring(&self, tx: Sender<()>) {
-        let cached_info = self.cached_info.clone();
-        let battery_path = self.battery_path.clone();
+        let state = self.state.clone();
 
         std::thread::spawn(move || {
             let runtime = tokio::runtime::Builder::new_current_thread()
@@ -31,130 +153,121 @@ impl SystemBatteryService {
                 .unwrap();
 
             runtime.block_on(async {
-                if let Err(e) = Self::monitor_battery_events(tx, cached_info, battery_path).await {
+                if let Err(e) = Self::monitor_devices(tx, state).await {
                     eprintln!("[Battery] Error monitoring battery events: {}", e);
                 }
             });
         });
     }
 
-    /// Мониторинг событий батареи через UPower D-Bus
-    async fn monitor_battery_events(
-        tx: Sender<()>,
-        cached_info: Arc<Mutex<Option<BatteryInfo>>>,
-        battery_path_arc: Arc<Mutex<Option<String>>>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        println!("[Battery] Starting UPower D-Bus monitoring...");
-
+    /// Мониторинг устройств UPower: изменения свойств, подключение и отключение
+    async fn monitor_devices(tx: Sender<()>, state: Arc<Mutex<BatteryState>>) -> zbus::Result<()> {
         let conn = Connection::system().await?;
+        let upower = UPowerProxy::new(&conn).await?;
+        let display_path = upower.get_display_device().await?.to_string();
+
+        // Подписываемся до начального чтения, чтобы не пропустить изменения
+        let rule = MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender(UPOWER_SERVICE)?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .path_namespace(UPOWER_DEVICES_PATH)?
+            .build();
+        let changed = MessageStream::for_match_rule(rule, &conn, None)
+            .await?
+            .filter_map(|msg| async move {
+                let msg = msg.ok()?;
+                let path = msg.header().path()?.to_string();
+                Some(DeviceEvent::Changed(path))
+            });
+        let added = upower.receive_device_added().await?.filter_map(|signal| async move {
+            let args = signal.args().ok()?;
+            Some(DeviceEvent::Added(args.device().to_string()))
+        });
+        let removed = upower.receive_device_removed().await?.filter_map(|signal| async move {
+            let args = signal.args().ok()?;
+            Some(DeviceEvent::Removed(args.device().to_string()))
+        });
+        let mut events = std::pin::pin!(futures::stream::select(changed, futures::stream::select(added, removed)));
 
-        // Находим устройство батареи
-        let battery_path = Self::find_battery_device(&conn).await?;
-        println!("[Battery] ✓ Found battery device: {}", battery_path);
-
-        // Сохраняем путь к батарее
-        *battery_path_arc.lock().unwrap() = Some(battery_path.clone());
+        state.lock().unwrap().display = Self::fetch_display(&conn, &display_path).await;
 
-        // Получаем начальное состояние
-        if let Some(info) = Self::fetch_battery_info(&conn, &battery_path).await {
-            *cached_info.lock().unwrap() = Some(info);
+        for path in upower.enumerate_devices().await? {
+            let path = path.to_string();
+            if let Some(device) = Self::fetch_device(&conn, &path).await {
+                state.lock().unwrap().devices.insert(path, device);
+            }
         }
 
-        // Создаём proxy для получения сигналов изменения свойств
-        let properties = PropertiesProxy::builder(&conn)
-            .destination("org.freedesktop.UPower")?
-            .path(battery_path.clone())?
-            .build()
-            .await?;
-
-        println!("[Battery] ✓ Listening for battery property changes");
-
-        // Подписываемся на изменения свойств
-        let mut stream = properties.receive_properties_changed().await?;
-
-        while let Some(_signal) = stream.next().await {
-            println!("[Battery] ⚡ Battery property changed event received");
+        {
+            let state = state.lock().unwrap();
+            let main = if state.display.is_some() { "found" } else { "not found" };
+            eprintln!(
+                "[Battery] ✓ Listening for UPower changes (battery {}, {} peripheral(s))",
+                main,
+                state.peripherals().len()
+            );
+        }
+        let _ = tx.send(()).await;
 
-            // Обновляем кэш с новыми данными
-            if let Some(info) = Self::fetch_battery_info(&conn, &battery_path).await {
-                *cached_info.lock().unwrap() = Some(info);
+        while let Some(event) = events.next().await {
+            match event {
+                DeviceEvent::Changed(path) if path == display_path => {
+                    state.lock().unwrap().display = Self::fetch_display(&conn, &path).await;
+                }
+                DeviceEvent::Changed(path) | DeviceEvent::Added(path) => {
+                    match Self::fetch_device(&conn, &path).await {
+                        Some(device) => {
+                            state.lock().unwrap().devices.insert(path, device);
+                        }
+                        None => {
+                            state.lock().unwrap().devices.remove(&path);
+                        }
+                    }
+                }
+                DeviceEvent::Removed(path) => {
+                    state.lock().unwrap().devices.remove(&path);
+                }
             }
 
             // Отправляем событие в UI
             if let Err(e) = tx.send(()).await {
                 eprintln!("[Battery] Failed to send event: {}", e);
+                break;
             }
         }
 
         Ok(())
     }
 
-    /// Поиск батареи через UPower D-Bus
-    async fn find_battery_device(conn: &Connection) -> Result<String, Box<dyn std::error::Error>> {
-        use zbus::zvariant::Value;
-        use zbus::names::InterfaceName;
-
-        // Подключаемся к UPower
-        let proxy = zbus::Proxy::new(
-            conn,
-            "org.freedesktop.UPower",
-            "/org/freedesktop/UPower",
-            "org.freedesktop.UPower",
-        ).await?;
-
-        // Получаем список устройств
-        let devices: Vec<zbus::zvariant::OwnedObjectPath> = proxy
-            .call("EnumerateDevices", &())
-            .await?;
-
-        // Ищем батарею
-        for device in devices {
-            let device_proxy = PropertiesProxy::builder(conn)
-                .destination("org.freedesktop.UPower")?
-                .path(device.clone())?
-                .build()
-                .await?;
-
-            // Проверяем тип устройства (Type = 2 означает Battery)
-            let interface: InterfaceName<'_> = "org.freedesktop.UPower.Device".try_into()?;
-            if let Ok(device_type) = device_proxy.get(interface, "Type").await {
-                let value: Value = device_type.try_into()?;
-                if let Value::U32(type_val) = value {
-                    if type_val == 2 {
-                        return Ok(device.as_str().to_string());
-                    }
-                }
-            }
-        }
-
-        Err("No battery device found".into())
+    /// Составная батарея; `None`, если батарей в системе нет
+    async fn fetch_display(conn: &Connection, path: &str) -> Option<BatteryInfo> {
+        Self::fetch_device(conn, path)
+            .await
+            .filter(|device| device.present)
+            .map(|device| device.info)
     }
 
-    /// Получение информации о батарее через UPower D-Bus
-    async fn fetch_battery_info(conn: &Connection, battery_path: &str) -> Option<BatteryInfo> {
-        use zbus::names::InterfaceName;
-
-        let battery_path_obj: zbus::zvariant::ObjectPath = battery_path.try_into().ok()?;
-        let properties = PropertiesProxy::builder(conn)
-            .destination("org.freedesktop.UPower").ok()?
-            .path(battery_path_obj).ok()?
+    /// Читает устройство UPower; `None` для блоков питания и пропавших устройств
+    async fn fetch_device(conn: &Connection, path: &str) -> Option<Device> {
+        let device = UPowerDeviceProxy::builder(conn)
+            .path(path.to_string())
+            .ok()?
+            .cache_properties(CacheProperties::No)
             .build()
             .await
             .ok()?;
 
-        let interface: InterfaceName<'_> = "org.freedesktop.UPower.Device".try_into().ok()?;
-
-        // Читаем процент заряда
-        let percentage_value = properties.get(interface, "Percentage").await.ok()?;
-        let percentage_double: f64 = percentage_value.try_into().ok()?;
-        let percentage = percentage_double.round() as u8;
+        let kind = device.kind().await.ok()?;
+        // 0 - DisplayDevice без батареи (настольный компьютер)
+        if kind == TYPE_LINE_POWER || kind == 0 {
+            return None;
+        }
 
-        // Читаем статус
-        let interface: InterfaceName<'_> = "org.freedesktop.UPower.Device".try_into().ok()?;
-        let state_value = properties.get(interface, "State").await.ok()?;
-        let state: u32 = state_value.try_into().ok()?;
+        let percentage = device.percentage().await.unwrap_or(0.0).round() as u8;
 
-        let status = match state {
+        let status = match device.state().await.unwrap_or(0) {
             1 => BatteryStatus::Charging,
             2 => BatteryStatus::Discharging,
             3 => BatteryStatus::Discharging,
@@ -164,30 +277,32 @@ impl SystemBatteryService {
             _ => BatteryStatus::Unknown,
         };
 
-        // Читаем время до разрядки/зарядки
-        let interface: InterfaceName<'_> = "org.freedesktop.UPower.Device".try_into().ok()?;
-        let time_to_empty_value = properties.get(interface, "TimeToEmpty").await.ok()?;
-        let time_to_empty_secs: i64 = time_to_empty_value.try_into().ok()?;
-        let time_to_empty = if time_to_empty_secs > 0 {
-            Some((time_to_empty_secs / 60) as u32)
-        } else {
-            None
-        };
-
-        let interface: InterfaceName<'_> = "org.freedesktop.UPower.Device".try_into().ok()?;
-        let time_to_full_value = properties.get(interface, "TimeToFull").await.ok()?;
-        let time_to_full_secs: i64 = time_to_full_value.try_into().ok()?;
-        let time_to_full = if time_to_full_secs > 0 {
-            Some((time_to_full_secs / 60) as u32)
-        } else {
-            None
-        };
+        // Время до разрядки/зарядки (секунды -> минуты)
+        let minutes = |secs: i64| (secs > 0).then(|| (secs / 60) as u32);
+        let time_to_empty = minutes(device.time_to_empty().await.unwrap_or(0));
+        let time_to_full = minutes(device.time_to_full().await.unwrap_or(0));
 
-        Some(BatteryInfo {
-            percentage: percentage.min(100),
-            status,
-            time_to_empty,
-            time_to_full,
+        let energy_rate = device.energy_rate().await.ok().filter(|rate| *rate > 0.0);
+        let health = device
+            .capacity()
+            .await
+            .ok()
+            .filter(|capacity| *capacity > 0.0)
+            .map(|capacity| capacity.round().min(100.0) as u8);
+
+        Some(Device {
+            info: BatteryInfo {
+                percentage: percentage.min(100),
+                status,
+                time_to_empty,
+                time_to_full,
+                kind: BatteryKind::from_upower(kind),
+                model: device.model().await.unwrap_or_default().trim().to_string(),
+                energy_rate,
+                health,
+            },
+            power_supply: device.power_supply().await.unwrap_or(false),
+            present: device.is_present().await.unwrap_or(false),
         })
     }
 }
@@ -195,7 +310,10 @@ impl SystemBatteryService {
 impl BatteryService for SystemBatteryService {
     fn get_battery_info(&self) -> Option<BatteryInfo> {
         // Возвращаем закэшированное значение (обновляется через события)
-        self.cached_info.lock().unwrap().clone()
+        self.state.lock().unwrap().main_battery()
     }
-}
 
+    fn get_peripherals(&self) -> Vec<BatteryInfo> {
+        self.state.lock().unwrap().peripherals()
+    }
+}
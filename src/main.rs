@@ -16,8 +16,10 @@ use domain::system_resources_service::SystemResourcesService;
 use domain::network_service::NetworkService;
 use domain::brightness_service::BrightnessService;
 use domain::submap_service::SubmapService;
+use domain::mpris_service::MprisService;
+use domain::night_light_service::NightLightService;
+use domain::power_profile_service::PowerProfileService;
 use domain::status_notifier_watcher_service::StatusNotifierWatcherService;
-use domain::models::DateTimeConfig;
 use infrastructure::hyprland_ipc::HyprlandIpc;
 use infrastructure::status_notifier_tray::StatusNotifierTrayService;
 use infrastructure::system_datetime::SystemDateTimeService;
@@ -28,10 +30,16 @@ use infrastructure::dbus_status_notifier_watcher::DbusStatusNotifierWatcher;
 use infrastructure::remote_notification_service::RemoteNotificationService;
 use infrastructure::hyprland_keyboard_layout::HyprlandKeyboardLayoutService;
 use infrastructure::lumen_brightness::LumenBrightnessService;
+use infrastructure::sysfs_brightness::SysfsBrightnessService;
+use infrastructure::display_brightness::DisplayBrightnessService;
 use infrastructure::hyprland_submap::HyprlandSubmapService;
-use infrastructure::monitor_listener::{start_monitor_listener, MonitorEvent};
+use infrastructure::mpris::DbusMprisService;
+use infrastructure::hyprsunset::HyprsunsetNightLight;
+use infrastructure::battery_alerts::BatteryAlerts;
+use infrastructure::power_profiles::DbusPowerProfiles;
+use infrastructure::hyprland_events::{get_event_bus, HyprlandEvent};
 use ui::bar::Bar;
-use ui::volume_osd::VolumeOsd;
+use ui::osd::{show_osd, Osd, OsdContent, OsdKind};
 use shared_state::get_shared_state;
 
 use gtk4::prelude::*;
@@ -44,15 +52,20 @@ fn main() -> glib::ExitCode {
         .build();
 
     app.connect_startup(|app| {
+        let display = gdk::Display::default().expect("error initializing gtk4 style context");
+
         let provider = gtk4::CssProvider::new();
         provider.load_from_data(include_str!("styles.css"));
 
         gtk4::style_context_add_provider_for_display(
-            &gdk::Display::default().expect("error initializing gtk4 style context"),
+            &display,
             &provider,
             gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
         );
 
+        // Тема и style.css пользователя поверх встроенных стилей
+        ui::theme::init_user_styles(&display);
+
         build_ui(app);
     });
 
@@ -96,7 +109,6 @@ fn build_ui(app: &gtk4::Application) {
     
     // Создаём DateTime сервис
     let datetime_service: Arc<dyn DateTimeService + Send + Sync> = Arc::new(SystemDateTimeService::new());
-    let datetime_config = DateTimeConfig::default();
     
     // Создаём Battery сервис с мониторингом событий
     let (battery_tx, battery_rx) = async_channel::unbounded();
@@ -104,6 +116,12 @@ fn build_ui(app: &gtk4::Application) {
     battery_service_impl.start_monitoring(battery_tx);
     let battery_service: Arc<dyn BatteryService + Send + Sync> = battery_service_impl;
 
+    // Создаём сервис профилей питания (power-profiles-daemon)
+    let (power_profile_tx, power_profile_rx) = async_channel::unbounded::<()>();
+    let power_profile_service_impl = DbusPowerProfiles::new();
+    power_profile_service_impl.start_monitoring(power_profile_tx);
+    let power_profile_service: Arc<dyn PowerProfileService + Send + Sync> = Arc::new(power_profile_service_impl);
+
     // Создаём Volume сервис с мониторингом
     let (volume_tx, volume_rx) = infrastructure::pipewire_volume::create_volume_channel();
     let (volume_osd_tx, volume_osd_rx) = async_channel::unbounded();
@@ -113,8 +131,8 @@ fn build_ui(app: &gtk4::Application) {
     volume_service_impl.start_monitoring(volume_osd_tx);
     let volume_service: Arc<dyn VolumeService + Send + Sync> = Arc::new(volume_service_impl);
 
-    // Создаём Volume OSD (On-Screen Display)
-    let volume_osd = Arc::new(VolumeOsd::new(app));
+    // Создаём OSD (On-Screen Display) для громкости, яркости, раскладки и т.д.
+    Osd::start(app);
 
     // Создаём Notification сервис (подключается к hyprline-notifications через D-Bus)
     let notification_service: Arc<dyn NotificationService + Send + Sync> =
@@ -124,11 +142,8 @@ fn build_ui(app: &gtk4::Application) {
     let keyboard_layout_service: Arc<dyn KeyboardLayoutService + Send + Sync> = 
         Arc::new(HyprlandKeyboardLayoutService::new());
 
-    // Создаём канал для событий смены раскладки
-    let (keyboard_layout_tx, keyboard_layout_rx) = infrastructure::keyboard_layout_listener::create_keyboard_layout_channel();
-    
-    // Запускаем мониторинг событий раскладки
-    infrastructure::keyboard_layout_listener::start_keyboard_layout_listener(keyboard_layout_tx);
+    // Подписываемся на события Hyprland (смена раскладки)
+    let keyboard_layout_rx = get_event_bus().subscribe();
 
     // Создаём SystemResources сервис
     let system_resources_service: Arc<dyn SystemResourcesService + Send + Sync> =
@@ -138,47 +153,47 @@ fn build_ui(app: &gtk4::Application) {
     let network_service: Arc<dyn NetworkService + Send + Sync> =
         Arc::new(NetworkManagerService::new());
 
-    // Создаём Brightness сервис
-    let brightness_service: Arc<dyn BrightnessService + Send + Sync> = match LumenBrightnessService::new() {
-        Ok(service) => {
-            let service_arc = Arc::new(service);
-
-            // Пробуем получить текущую яркость для проверки
-            if let Ok(brightness) = service_arc.get_brightness() {
-                eprintln!("[Brightness] ✓ Connected ({}%)", brightness);
-            }
-
-            // Запускаем мониторинг сигналов яркости
-            service_arc.clone().start_signal_monitoring();
-            service_arc
-        }
-        Err(e) => {
-            eprintln!("[Brightness] ✗ Failed to connect: {}", e);
-            eprintln!("[Brightness] Make sure Lumen service is running");
-            panic!("Cannot create brightness service");
-        }
-    };
+    // Создаём Brightness сервис: встроенная подсветка (Lumen или sysfs + logind)
+    // и внешние мониторы по DDC/CI
+    let (brightness_displays_tx, brightness_displays_rx) = async_channel::unbounded::<()>();
+    let brightness_service_impl = DisplayBrightnessService::new(create_internal_brightness_service());
+    brightness_service_impl.start_external_detection(brightness_displays_tx);
+    let brightness_service: Arc<dyn BrightnessService + Send + Sync> = Arc::new(brightness_service_impl);
 
     // Подписываемся на изменения яркости и будем обновлять SharedState
     let shared_state_brightness = get_shared_state();
     brightness_service.subscribe_brightness_changed(Arc::new(move |value| {
         shared_state_brightness.update_brightness(value);
+        show_osd(
+            OsdKind::Brightness,
+            ui::brightness::brightness_icon(value),
+            OsdContent::Level { value: value.min(100) as u8, muted: false },
+        );
     }));
 
     // Создаём Submap сервис
     let submap_service_impl = Arc::new(HyprlandSubmapService::new());
     let submap_service: Arc<dyn SubmapService + Send + Sync> = submap_service_impl.clone();
 
-    // Создаём канал для событий submap
-    let (submap_tx, submap_rx) = infrastructure::submap_listener::create_submap_channel();
-
-    // Запускаем мониторинг событий submap
-    infrastructure::submap_listener::start_submap_listener(submap_tx);
+    // Подписываемся на события Hyprland (смена submap)
+    let submap_rx = get_event_bus().subscribe();
 
     // Запускаем мониторинг изменений конфига Hyprland для обновления названий биндингов
     let (config_change_tx, config_change_rx) = async_channel::unbounded::<()>();
     submap_service_impl.clone().start_config_monitoring(config_change_tx);
 
+    // Создаём MPRIS сервис для медиаплееров
+    let (media_tx, media_rx) = async_channel::unbounded::<()>();
+    let mpris_service_impl = DbusMprisService::new();
+    mpris_service_impl.start_monitoring(media_tx);
+    let mpris_service: Arc<dyn MprisService + Send + Sync> = Arc::new(mpris_service_impl);
+
+    // Создаём сервис ночного режима (hyprsunset) и запускаем расписание
+    let (night_light_tx, night_light_rx) = async_channel::unbounded::<()>();
+    let night_light_service_impl = Arc::new(HyprsunsetNightLight::new());
+    night_light_service_impl.clone().start_scheduler(night_light_tx);
+    let night_light_service: Arc<dyn NightLightService + Send + Sync> = night_light_service_impl;
+
     // Создаём канал для обновлений трея
     let (tray_tx, tray_rx) = async_channel::unbounded();
     
@@ -214,6 +229,7 @@ fn build_ui(app: &gtk4::Application) {
                 if let Some(info) = volume_service.get_volume_info() {
                     shared_state.update_volume(Some(info));
                 }
+                shared_state.update_microphone(volume_service.get_microphone_info());
             }
             glib::ControlFlow::Continue
         });
@@ -223,15 +239,79 @@ fn build_ui(app: &gtk4::Application) {
     {
         let shared_state = shared_state.clone();
         let battery_service = battery_service.clone();
+        let mut alerts = BatteryAlerts::new();
         glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
             while let Ok(_) = battery_rx.try_recv() {
                 let info = battery_service.get_battery_info();
+                alerts.check(info.as_ref());
                 shared_state.update_battery(info);
             }
             glib::ControlFlow::Continue
         });
     }
 
+    // Обработка смены профиля питания
+    {
+        let shared_state = shared_state.clone();
+        let power_profile_service = power_profile_service.clone();
+        shared_state.update_power_profile(power_profile_service.active_profile());
+        glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+            let mut changed = false;
+            while power_profile_rx.try_recv().is_ok() {
+                changed = true;
+            }
+            if changed {
+                shared_state.update_power_profile(power_profile_service.active_profile());
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    // Обработка событий медиаплееров
+    {
+        let shared_state = shared_state.clone();
+        let mpris_service = mpris_service.clone();
+        glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+            let mut changed = false;
+            while media_rx.try_recv().is_ok() {
+                changed = true;
+            }
+            if changed {
+                shared_state.update_media(mpris_service.list_players());
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    // Обработка изменений ночного режима (расписание)
+    {
+        let shared_state = shared_state.clone();
+        let night_light_service = night_light_service.clone();
+        shared_state.update_night_light(Some(night_light_service.get_state()));
+        glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+            let mut changed = false;
+            while night_light_rx.try_recv().is_ok() {
+                changed = true;
+            }
+            if changed {
+                shared_state.update_night_light(Some(night_light_service.get_state()));
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    // Внешние мониторы найдены (опрос DDC/CI идёт в фоне)
+    {
+        let shared_state = shared_state.clone();
+        let brightness_service = brightness_service.clone();
+        glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+            while brightness_displays_rx.try_recv().is_ok() {
+                shared_state.update_brightness_displays(brightness_service.list_displays());
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
     // Обработка событий трея
     {
         let shared_state = shared_state.clone();
@@ -246,9 +326,18 @@ fn build_ui(app: &gtk4::Application) {
     // Обработка событий раскладки клавиатуры
     {
         let shared_state = shared_state.clone();
+        // activelayout приходит для каждой клавиатуры - OSD только при смене раскладки
+        let mut last_shown = keyboard_layout_service.get_current_layout().map(|l| l.full_name);
         glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
-            while let Ok(layout_name) = keyboard_layout_rx.try_recv() {
+            while let Ok(event) = keyboard_layout_rx.try_recv() {
+                let HyprlandEvent::ActiveLayout { layout: layout_name, .. } = event else {
+                    continue;
+                };
                 let full_name = get_layout_full_name(&layout_name);
+                if last_shown.as_ref() != Some(&full_name) {
+                    show_osd(OsdKind::KeyboardLayout, "󰌌", OsdContent::Text(full_name.clone()));
+                    last_shown = Some(full_name.clone());
+                }
                 let layout = domain::models::KeyboardLayout {
                     short_name: layout_name,
                     full_name,
@@ -259,12 +348,51 @@ fn build_ui(app: &gtk4::Application) {
         });
     }
 
+    // Caps Lock: Hyprland не присылает событий, поэтому опрашиваем, пока OSD включён.
+    // Запрос j/devices синхронный, поэтому опрос идёт в отдельном потоке,
+    // а в GTK поток приходят только изменения состояния
+    {
+        let keyboard_layout_service = keyboard_layout_service.clone();
+        let (caps_tx, caps_rx) = async_channel::unbounded::<bool>();
+
+        std::thread::spawn(move || {
+            let mut last_state = keyboard_layout_service.get_caps_lock();
+            loop {
+                std::thread::sleep(std::time::Duration::from_millis(250));
+
+                if !config::get_config().read().unwrap().osd.caps_lock {
+                    last_state = None;
+                    continue;
+                }
+
+                let state = keyboard_layout_service.get_caps_lock();
+                if let (Some(enabled), Some(_)) = (state, last_state) {
+                    if state != last_state && caps_tx.send_blocking(enabled).is_err() {
+                        break;
+                    }
+                }
+                last_state = state;
+            }
+        });
+
+        glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+            while let Ok(enabled) = caps_rx.try_recv() {
+                let text = if enabled { "Caps Lock On" } else { "Caps Lock Off" };
+                show_osd(OsdKind::CapsLock, "󰪛", OsdContent::Text(text.to_string()));
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
     // Обработка событий submap
     {
         let shared_state = shared_state.clone();
         let submap_service_clone = submap_service.clone();
         glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
-            while let Ok(submap_name) = submap_rx.try_recv() {
+            while let Ok(event) = submap_rx.try_recv() {
+                let HyprlandEvent::Submap { name: submap_name } = event else {
+                    continue;
+                };
                 eprintln!("[Main] Submap changed: '{}' (active: {})", submap_name, !submap_name.is_empty());
                 let bindings = submap_service_clone.get_submap_bindings(&submap_name);
                 eprintln!("[Main] Found {} bindings for submap", bindings.len());
@@ -300,27 +428,68 @@ fn build_ui(app: &gtk4::Application) {
         });
     }
 
-    // Volume OSD обработка
+    // OSD громкости и микрофона
     {
-        let volume_osd_clone = volume_osd.clone();
         let volume_service_clone = volume_service.clone();
+        // Событие приходит и при изменении устройств/потоков - OSD только при смене уровня или mute
+        let mut last_volume = volume_service_clone.get_volume_info();
+        let mut last_microphone = volume_service_clone.get_microphone_info();
         glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
             while let Ok(_) = volume_osd_rx.try_recv() {
-                if let Some(info) = volume_service_clone.get_volume_info() {
-                    volume_osd_clone.show_volume(info.volume, info.muted);
+                let volume = volume_service_clone.get_volume_info();
+                // Первое событие после подключения к PipeWire - не изменение громкости
+                if volume != last_volume && last_volume.is_some() {
+                    if let Some(ref info) = volume {
+                        show_osd(
+                            OsdKind::Volume,
+                            ui::volume::volume_icon(info.volume, info.muted),
+                            OsdContent::Level { value: info.volume, muted: info.muted },
+                        );
+                    }
+                }
+                last_volume = volume;
+
+                let microphone = volume_service_clone.get_microphone_info();
+                if let (Some(info), Some(old)) = (&microphone, &last_microphone) {
+                    if info.volume != old.volume || info.muted != old.muted {
+                        show_osd(
+                            OsdKind::Microphone,
+                            if info.muted { "󰍭" } else { "󰍬" },
+                            OsdContent::Level { value: info.volume, muted: info.muted },
+                        );
+                    }
                 }
+                last_microphone = microphone;
             }
             glib::ControlFlow::Continue
         });
     }
 
-    // Централизованное обновление системных ресурсов каждые 2 секунды
+    // Централизованное обновление системных ресурсов (интервал из настроек виджета)
     {
         let shared_state = shared_state.clone();
         let system_resources_service = system_resources_service.clone();
-        glib::timeout_add_local(std::time::Duration::from_secs(2), move || {
-            let resources = system_resources_service.get_resources();
-            shared_state.update_system_resources(resources);
+
+        // Интервал читаем из конфига один раз и перечитываем только при его изменении
+        let (config_tx, config_rx) = async_channel::unbounded::<()>();
+        config::subscribe_config_changes(move || {
+            let _ = config_tx.send_blocking(());
+        });
+
+        let mut interval = config::get_config().read().unwrap().system_resources_interval();
+        let mut elapsed = 0;
+        glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
+            if config_rx.try_recv().is_ok() {
+                while config_rx.try_recv().is_ok() {}
+                interval = config::get_config().read().unwrap().system_resources_interval();
+            }
+
+            elapsed += 1;
+            if elapsed >= interval {
+                elapsed = 0;
+                let resources = system_resources_service.get_resources();
+                shared_state.update_system_resources(resources);
+            }
             glib::ControlFlow::Continue
         });
     }
@@ -353,6 +522,7 @@ fn build_ui(app: &gtk4::Application) {
     if let Ok(brightness) = brightness_service.get_brightness() {
         shared_state.update_brightness(brightness);
     }
+    shared_state.update_brightness_displays(brightness_service.list_displays());
     // Инициализация системных ресурсов
     shared_state.update_system_resources(system_resources_service.get_resources());
     // Инициализация сети
@@ -377,6 +547,9 @@ fn build_ui(app: &gtk4::Application) {
                     NotificationEvent::CountChanged(count) => {
                         shared_state_for_listener.update_notifications(count as usize);
                     }
+                    NotificationEvent::DndChanged(active) => {
+                        shared_state_for_listener.set_notification_dnd(active);
+                    }
                     NotificationEvent::ServiceAvailable => {
                         eprintln!("[Main] Notification service connected");
                         shared_state_for_listener.set_notification_service_available(true);
@@ -384,6 +557,7 @@ fn build_ui(app: &gtk4::Application) {
                     NotificationEvent::ServiceUnavailable => {
                         eprintln!("[Main] Notification service disconnected");
                         shared_state_for_listener.set_notification_service_available(false);
+                        shared_state_for_listener.set_notification_dnd(false);
                         shared_state_for_listener.update_notifications(0);
                     }
                 }
@@ -393,7 +567,10 @@ fn build_ui(app: &gtk4::Application) {
     }
 
     let workspace_keys = parse_workspace_bindings();
-    let monitors = service.get_monitors();
+    let monitors = service.get_monitors().unwrap_or_else(|e| {
+        eprintln!("[Main] ✗ Failed to query monitors: {}", e);
+        Vec::new()
+    });
 
     // Создаём bars и храним их для hot reload и динамического управления
     let bars: Arc<std::sync::Mutex<Vec<Bar>>> = Arc::new(std::sync::Mutex::new(
@@ -405,8 +582,8 @@ fn build_ui(app: &gtk4::Application) {
                 service.clone(),
                 tray_service.clone(),
                 datetime_service.clone(),
-                datetime_config.clone(),
                 battery_service.clone(),
+                power_profile_service.clone(),
                 volume_service.clone(),
                 notification_service.clone(),
                 keyboard_layout_service.clone(),
@@ -414,6 +591,8 @@ fn build_ui(app: &gtk4::Application) {
                 network_service.clone(),
                 brightness_service.clone(),
                 submap_service.clone(),
+                mpris_service.clone(),
+                night_light_service.clone(),
                 shared_state.clone(),
             )]
         } else {
@@ -425,8 +604,8 @@ fn build_ui(app: &gtk4::Application) {
                     service.clone(),
                     tray_service.clone(),
                     datetime_service.clone(),
-                    datetime_config.clone(),
                     battery_service.clone(),
+                    power_profile_service.clone(),
                     volume_service.clone(),
                     notification_service.clone(),
                     keyboard_layout_service.clone(),
@@ -434,6 +613,8 @@ fn build_ui(app: &gtk4::Application) {
                     network_service.clone(),
                     brightness_service.clone(),
                     submap_service.clone(),
+                    mpris_service.clone(),
+                    night_light_service.clone(),
                     shared_state.clone(),
                 )
             }).collect()
@@ -449,6 +630,9 @@ fn build_ui(app: &gtk4::Application) {
             let _ = config_tx.send_blocking(());
         });
 
+        // Изменения config.json, сделанные вручную или другими программами
+        infrastructure::config_watcher::start_config_watching();
+
         glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
             while config_rx.try_recv().is_ok() {
                 eprintln!("[Main] Config changed, rebuilding widgets...");
@@ -463,11 +647,7 @@ fn build_ui(app: &gtk4::Application) {
 
     // Подписка на события мониторов (добавление/удаление)
     {
-        let (monitor_tx, monitor_rx) = async_channel::unbounded::<MonitorEvent>();
-
-        start_monitor_listener(move |event| {
-            let _ = monitor_tx.send_blocking(event);
-        });
+        let monitor_rx = get_event_bus().subscribe();
 
         let bars_for_monitors = bars.clone();
         let app_clone = app.clone();
@@ -475,8 +655,8 @@ fn build_ui(app: &gtk4::Application) {
         let service_clone = service.clone();
         let tray_service_clone = tray_service.clone();
         let datetime_service_clone = datetime_service.clone();
-        let datetime_config_clone = datetime_config.clone();
         let battery_service_clone = battery_service.clone();
+        let power_profile_service_clone = power_profile_service.clone();
         let volume_service_clone = volume_service.clone();
         let notification_service_clone = notification_service.clone();
         let keyboard_layout_service_clone = keyboard_layout_service.clone();
@@ -484,12 +664,15 @@ fn build_ui(app: &gtk4::Application) {
         let network_service_clone = network_service.clone();
         let brightness_service_clone = brightness_service.clone();
         let submap_service_clone = submap_service.clone();
+        let mpris_service_clone = mpris_service.clone();
+        let night_light_service_clone = night_light_service.clone();
         let shared_state_clone = shared_state.clone();
 
         glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
             while let Ok(event) = monitor_rx.try_recv() {
                 match event {
-                    MonitorEvent::Added(monitor_name) => {
+                    HyprlandEvent::MonitorAdded { name: monitor_name } => {
+                        eprintln!("[Main] Monitor added: {}", monitor_name);
                         // Задержка, чтобы GDK успел зарегистрировать новый монитор
                         let bars_clone = bars_for_monitors.clone();
                         let app = app_clone.clone();
@@ -497,8 +680,8 @@ fn build_ui(app: &gtk4::Application) {
                         let service = service_clone.clone();
                         let tray_service = tray_service_clone.clone();
                         let datetime_service = datetime_service_clone.clone();
-                        let datetime_config = datetime_config_clone.clone();
                         let battery_service = battery_service_clone.clone();
+                        let power_profile_service = power_profile_service_clone.clone();
                         let volume_service = volume_service_clone.clone();
                         let notification_service = notification_service_clone.clone();
                         let keyboard_layout_service = keyboard_layout_service_clone.clone();
@@ -506,6 +689,8 @@ fn build_ui(app: &gtk4::Application) {
                         let network_service = network_service_clone.clone();
                         let brightness_service = brightness_service_clone.clone();
                         let submap_service = submap_service_clone.clone();
+                        let mpris_service = mpris_service_clone.clone();
+                        let night_light_service = night_light_service_clone.clone();
                         let shared_state = shared_state_clone.clone();
 
                         glib::timeout_add_local_once(std::time::Duration::from_millis(300), move || {
@@ -526,8 +711,8 @@ fn build_ui(app: &gtk4::Application) {
                                 service,
                                 tray_service,
                                 datetime_service,
-                                datetime_config,
                                 battery_service,
+                                power_profile_service,
                                 volume_service,
                                 notification_service,
                                 keyboard_layout_service,
@@ -535,6 +720,8 @@ fn build_ui(app: &gtk4::Application) {
                                 network_service,
                                 brightness_service,
                                 submap_service,
+                                mpris_service,
+                                night_light_service,
                                 shared_state,
                             );
 
@@ -545,7 +732,8 @@ fn build_ui(app: &gtk4::Application) {
                             eprintln!("[Main] ✓ Bar created for monitor: {}", monitor_name);
                         });
                     }
-                    MonitorEvent::Removed(monitor_name) => {
+                    HyprlandEvent::MonitorRemoved { name: monitor_name } => {
+                        eprintln!("[Main] Monitor removed: {}", monitor_name);
                         let mut bars = bars_for_monitors.lock().unwrap();
 
                         // Находим и удаляем бар для этого монитора
@@ -558,6 +746,7 @@ fn build_ui(app: &gtk4::Application) {
                             eprintln!("[Main] No bar found for monitor: {}", monitor_name);
                         }
                     }
+                    _ => {}
                 }
             }
             glib::ControlFlow::Continue
@@ -573,3 +762,35 @@ fn build_ui(app: &gtk4::Application) {
         }
     }
 }
+
+/// Lumen (с автоматической регулировкой), если он отвечает, иначе подсветка из sysfs
+fn create_internal_brightness_service() -> Option<Arc<dyn BrightnessService + Send + Sync>> {
+    let lumen = LumenBrightnessService::new()
+        .and_then(|service| service.get_brightness().map(|brightness| (service, brightness)));
+
+    match lumen {
+        Ok((service, brightness)) => {
+            eprintln!("[Brightness] ✓ Connected to Lumen ({}%)", brightness);
+            let service_arc = Arc::new(service);
+            // Запускаем мониторинг сигналов яркости
+            service_arc.clone().start_signal_monitoring();
+            return Some(service_arc);
+        }
+        Err(e) => {
+            eprintln!("[Brightness] Lumen is not available ({}), falling back to sysfs backlight", e);
+        }
+    }
+
+    match SysfsBrightnessService::new() {
+        Ok(service) => {
+            let service_arc = Arc::new(service);
+            service_arc.clone().start_monitoring();
+            Some(service_arc)
+        }
+        Err(e) => {
+            // Например, настольный компьютер - остаются только внешние мониторы
+            eprintln!("[Brightness] No sysfs backlight: {}", e);
+            None
+        }
+    }
+}
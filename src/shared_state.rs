@@ -1,4 +1,7 @@
-use crate::domain::models::{BatteryInfo, KeyboardLayout, NetworkConnection, SubmapInfo, SystemResources, TrayItem, VolumeInfo};
+use crate::domain::models::{
+    BatteryInfo, BrightnessDisplay, KeyboardLayout, MediaPlayer, MicrophoneInfo, NetworkConnection,
+    NightLightState, PowerProfile, SubmapInfo, SystemResources, TrayItem, VolumeInfo,
+};
 use std::sync::{Arc, Mutex, RwLock};
 
 /// Тип callback-функции для обновления виджетов
@@ -29,19 +32,28 @@ impl Callbacks {
 pub struct SharedState {
     // Состояния
     pub battery_info: RwLock<Option<BatteryInfo>>,
+    pub power_profile: RwLock<Option<PowerProfile>>,
     pub volume_info: RwLock<Option<VolumeInfo>>,
+    pub microphone_info: RwLock<Option<MicrophoneInfo>>,
+    pub media_players: RwLock<Vec<MediaPlayer>>,
     pub tray_items: RwLock<Vec<TrayItem>>,
     pub keyboard_layout: RwLock<Option<KeyboardLayout>>,
     pub notification_count: RwLock<usize>,
+    /// Режим «Не беспокоить» действует (вручную или по расписанию)
+    pub notification_dnd: RwLock<bool>,
     pub notification_service_available: RwLock<bool>,
     pub brightness: RwLock<u32>,
+    pub brightness_displays: RwLock<Vec<BrightnessDisplay>>,
     pub system_resources: RwLock<Option<SystemResources>>,
     pub network_connection: RwLock<Option<NetworkConnection>>,
     pub submap: RwLock<SubmapInfo>,
+    pub night_light: RwLock<Option<NightLightState>>,
 
     // Callback-и для обновления UI
     battery_callbacks: Mutex<Callbacks>,
     volume_callbacks: Mutex<Callbacks>,
+    microphone_callbacks: Mutex<Callbacks>,
+    media_callbacks: Mutex<Callbacks>,
     tray_callbacks: Mutex<Callbacks>,
     keyboard_layout_callbacks: Mutex<Callbacks>,
     notification_callbacks: Mutex<Callbacks>,
@@ -50,23 +62,32 @@ pub struct SharedState {
     network_callbacks: Mutex<Callbacks>,
     config_changed_callbacks: Mutex<Callbacks>,
     submap_callbacks: Mutex<Callbacks>,
+    night_light_callbacks: Mutex<Callbacks>,
 }
 
 impl SharedState {
     pub fn new() -> Self {
         Self {
             battery_info: RwLock::new(None),
+            power_profile: RwLock::new(None),
             volume_info: RwLock::new(None),
+            microphone_info: RwLock::new(None),
+            media_players: RwLock::new(Vec::new()),
             tray_items: RwLock::new(Vec::new()),
             keyboard_layout: RwLock::new(None),
             notification_count: RwLock::new(0),
+            notification_dnd: RwLock::new(false),
             notification_service_available: RwLock::new(false),
             brightness: RwLock::new(100),
+            brightness_displays: RwLock::new(Vec::new()),
             system_resources: RwLock::new(None),
             network_connection: RwLock::new(None),
             submap: RwLock::new(SubmapInfo::default()),
+            night_light: RwLock::new(None),
             battery_callbacks: Mutex::new(Callbacks::new()),
             volume_callbacks: Mutex::new(Callbacks::new()),
+            microphone_callbacks: Mutex::new(Callbacks::new()),
+            media_callbacks: Mutex::new(Callbacks::new()),
             tray_callbacks: Mutex::new(Callbacks::new()),
             keyboard_layout_callbacks: Mutex::new(Callbacks::new()),
             notification_callbacks: Mutex::new(Callbacks::new()),
@@ -75,6 +96,7 @@ impl SharedState {
             network_callbacks: Mutex::new(Callbacks::new()),
             config_changed_callbacks: Mutex::new(Callbacks::new()),
             submap_callbacks: Mutex::new(Callbacks::new()),
+            night_light_callbacks: Mutex::new(Callbacks::new()),
         }
     }
 
@@ -88,6 +110,16 @@ impl SharedState {
         self.battery_info.read().unwrap().clone()
     }
 
+    /// Профиль питания показывается в виджете батареи - уведомляем его подписчиков
+    pub fn update_power_profile(&self, profile: Option<PowerProfile>) {
+        *self.power_profile.write().unwrap() = profile;
+        self.battery_callbacks.lock().unwrap().notify_all();
+    }
+
+    pub fn get_power_profile(&self) -> Option<PowerProfile> {
+        *self.power_profile.read().unwrap()
+    }
+
     pub fn subscribe_battery<F>(&self, callback: F)
     where
         F: Fn() + Send + Sync + 'static,
@@ -112,6 +144,59 @@ impl SharedState {
         self.volume_callbacks.lock().unwrap().add(Box::new(callback));
     }
 
+    // === Microphone ===
+    pub fn update_microphone(&self, info: Option<MicrophoneInfo>) {
+        *self.microphone_info.write().unwrap() = info;
+        self.microphone_callbacks.lock().unwrap().notify_all();
+    }
+
+    #[allow(dead_code)]
+    pub fn get_microphone(&self) -> Option<MicrophoneInfo> {
+        self.microphone_info.read().unwrap().clone()
+    }
+
+    pub fn subscribe_microphone<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.microphone_callbacks.lock().unwrap().add(Box::new(callback));
+    }
+
+    // === Media ===
+    pub fn update_media(&self, players: Vec<MediaPlayer>) {
+        *self.media_players.write().unwrap() = players;
+        self.media_callbacks.lock().unwrap().notify_all();
+    }
+
+    #[allow(dead_code)]
+    pub fn get_media(&self) -> Vec<MediaPlayer> {
+        self.media_players.read().unwrap().clone()
+    }
+
+    pub fn subscribe_media<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.media_callbacks.lock().unwrap().add(Box::new(callback));
+    }
+
+    // === Night light ===
+    pub fn update_night_light(&self, state: Option<NightLightState>) {
+        *self.night_light.write().unwrap() = state;
+        self.night_light_callbacks.lock().unwrap().notify_all();
+    }
+
+    pub fn get_night_light(&self) -> Option<NightLightState> {
+        self.night_light.read().unwrap().clone()
+    }
+
+    pub fn subscribe_night_light<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.night_light_callbacks.lock().unwrap().add(Box::new(callback));
+    }
+
     // === Tray ===
     pub fn update_tray(&self, items: Vec<TrayItem>) {
         *self.tray_items.write().unwrap() = items;
@@ -166,6 +251,15 @@ impl SharedState {
         *self.notification_count.read().unwrap()
     }
 
+    pub fn set_notification_dnd(&self, active: bool) {
+        *self.notification_dnd.write().unwrap() = active;
+        self.notification_callbacks.lock().unwrap().notify_all();
+    }
+
+    pub fn is_notification_dnd(&self) -> bool {
+        *self.notification_dnd.read().unwrap()
+    }
+
     pub fn subscribe_notifications<F>(&self, callback: F)
     where
         F: Fn() + Send + Sync + 'static,
@@ -183,6 +277,15 @@ impl SharedState {
         *self.brightness.read().unwrap()
     }
 
+    pub fn update_brightness_displays(&self, displays: Vec<BrightnessDisplay>) {
+        *self.brightness_displays.write().unwrap() = displays;
+        self.brightness_callbacks.lock().unwrap().notify_all();
+    }
+
+    pub fn get_brightness_displays(&self) -> Vec<BrightnessDisplay> {
+        self.brightness_displays.read().unwrap().clone()
+    }
+
     pub fn subscribe_brightness<F>(&self, callback: F)
     where
         F: Fn() + Send + Sync + 'static,
@@ -1,35 +1,151 @@
+use crate::config::ActiveWindowOptions;
+use crate::domain::models::WindowInfo;
 use crate::domain::workspace_service::WorkspaceService;
+use crate::ui::app_icon::create_app_icon;
 use gtk4::prelude::*;
+use regex::Regex;
+use std::cell::RefCell;
 use std::sync::Arc;
 
 pub struct ActiveWindowWidget {
-    label: gtk4::Label,
+    container: gtk4::Box,
+    icon_slot: gtk4::Box,
+    class_label: gtk4::Label,
+    title_label: gtk4::Label,
+    state_label: gtk4::Label,
     service: Arc<dyn WorkspaceService + Send + Sync>,
+    options: ActiveWindowOptions,
+    /// Скомпилированные правила переписывания заголовка
+    rules: Vec<(Regex, String)>,
+    /// Класс, для которого создана текущая иконка
+    icon_class: RefCell<Option<String>>,
 }
 
 impl ActiveWindowWidget {
-    pub fn new(service: Arc<dyn WorkspaceService + Send + Sync>) -> Self {
-        let label = gtk4::Label::new(Some(""));
-        label.add_css_class("active-window");
-        label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
-        label.set_max_width_chars(50);
-        label.set_xalign(0.0); // Выравнивание по левому краю
+    pub fn new(service: Arc<dyn WorkspaceService + Send + Sync>, options: ActiveWindowOptions) -> Self {
+        let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+        container.add_css_class("active-window");
 
-        Self { label, service }
+        let icon_slot = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+        container.append(&icon_slot);
+
+        let class_label = gtk4::Label::new(None);
+        class_label.add_css_class("active-window-class");
+        container.append(&class_label);
+
+        let title_label = gtk4::Label::new(Some(""));
+        title_label.add_css_class("active-window-title");
+        title_label.set_xalign(0.0); // Выравнивание по левому краю
+        if options.max_length > 0 {
+            title_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+            title_label.set_max_width_chars(options.max_length as i32);
+        }
+        container.append(&title_label);
+
+        let state_label = gtk4::Label::new(None);
+        state_label.add_css_class("active-window-state");
+        container.append(&state_label);
+
+        let rules = options
+            .rewrite_rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(regex) => Some((regex, rule.replacement.clone())),
+                Err(e) => {
+                    eprintln!("[ActiveWindow] ✗ Invalid rewrite rule '{}': {}", rule.pattern, e);
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            container,
+            icon_slot,
+            class_label,
+            title_label,
+            state_label,
+            service,
+            options,
+            rules,
+            icon_class: RefCell::new(None),
+        }
     }
 
-    pub fn widget(&self) -> &gtk4::Label {
-        &self.label
+    pub fn widget(&self) -> &gtk4::Box {
+        &self.container
     }
 
     pub fn update(&self) {
-        let title = self.service.get_active_window_title();
+        let window = match self.service.get_active_window() {
+            Ok(window) => window,
+            Err(e) => {
+                eprintln!("[ActiveWindow] ✗ Failed to query active window: {}", e);
+                return;
+            }
+        };
+
+        let Some(window) = window else {
+            self.set_icon(None);
+            self.class_label.set_visible(false);
+            self.state_label.set_visible(false);
+            self.title_label.set_text("");
+            self.container.set_tooltip_text(None);
+            return;
+        };
+
+        self.set_icon(self.options.show_icon.then_some(window.class.as_str()));
+
+        self.class_label.set_text(&window.class);
+        self.class_label.set_visible(self.options.show_class && !window.class.is_empty());
+
+        self.title_label.set_text(&self.rewrite_title(&window.title));
+        self.container.set_tooltip_text(Some(&window.title));
 
-        if title.is_empty() {
-            self.label.set_text("");
-        } else {
-            self.label.set_text(&title);
+        let state = if self.options.show_state { state_indicator(&window) } else { String::new() };
+        self.state_label.set_text(&state);
+        self.state_label.set_visible(!state.is_empty());
+    }
+
+    /// Применяет правила переписывания по порядку
+    fn rewrite_title(&self, title: &str) -> String {
+        self.rules
+            .iter()
+            .fold(title.to_string(), |title, (regex, replacement)| {
+                regex.replace_all(&title, replacement.as_str()).into_owned()
+            })
+            .trim()
+            .to_string()
+    }
+
+    /// Пересоздаёт иконку только при смене класса окна
+    fn set_icon(&self, class: Option<&str>) {
+        let mut icon_class = self.icon_class.borrow_mut();
+        if icon_class.as_deref() == class {
+            return;
+        }
+
+        while let Some(child) = self.icon_slot.first_child() {
+            self.icon_slot.remove(&child);
         }
+        if let Some(class) = class {
+            self.icon_slot.append(&create_app_icon(class, 16));
+        }
+        self.icon_slot.set_visible(class.is_some());
+        *icon_class = class.map(str::to_string);
     }
 }
 
+/// Индикатор состояния окна: fullscreen, floating, pinned
+fn state_indicator(window: &WindowInfo) -> String {
+    let mut icons = Vec::new();
+    if window.fullscreen > 0 {
+        icons.push("󰊓");
+    }
+    if window.floating {
+        icons.push("󰖲");
+    }
+    if window.pinned {
+        icons.push("󰐃");
+    }
+    icons.join(" ")
+}
@@ -0,0 +1,27 @@
+use crate::infrastructure::desktop_entries::find_icon_for_class;
+
+/// Иконка, если для приложения ничего не нашлось
+const FALLBACK_ICON: &str = "application-x-executable";
+
+/// Создаёт иконку приложения по классу окна Hyprland
+pub fn create_app_icon(class: &str, size: i32) -> gtk4::Image {
+    let icon = find_icon_for_class(class).unwrap_or_else(|| class.to_lowercase());
+
+    let image = if icon.starts_with('/') && std::path::Path::new(&icon).exists() {
+        gtk4::Image::from_file(&icon)
+    } else {
+        let has_icon = gtk4::gdk::Display::default()
+            .map(|display| gtk4::IconTheme::for_display(&display).has_icon(&icon))
+            .unwrap_or(false);
+
+        if has_icon {
+            gtk4::Image::from_icon_name(&icon)
+        } else {
+            gtk4::Image::from_icon_name(FALLBACK_ICON)
+        }
+    };
+
+    image.set_pixel_size(size);
+    image.add_css_class("app-icon");
+    image
+}
@@ -1,8 +1,9 @@
-use crate::config::{get_config, subscribe_config_changes, WidgetType, WidgetPosition};
+use crate::config::{get_config, subscribe_config_changes, DateTimeOptions, WidgetConfig, WidgetType, WidgetPosition};
 use crate::domain::workspace_service::WorkspaceService;
 use crate::domain::system_tray_service::SystemTrayService;
 use crate::domain::datetime_service::DateTimeService;
 use crate::domain::battery_service::BatteryService;
+use crate::domain::power_profile_service::PowerProfileService;
 use crate::domain::volume_service::VolumeService;
 use crate::domain::notification_service::NotificationService;
 use crate::domain::keyboard_layout_service::KeyboardLayoutService;
@@ -10,8 +11,9 @@ use crate::domain::system_resources_service::SystemResourcesService;
 use crate::domain::network_service::NetworkService;
 use crate::domain::brightness_service::BrightnessService;
 use crate::domain::submap_service::SubmapService;
-use crate::domain::models::DateTimeConfig;
-use crate::infrastructure::event_listener;
+use crate::domain::mpris_service::MprisService;
+use crate::domain::night_light_service::NightLightService;
+use crate::infrastructure::hyprland_events::get_event_bus;
 use crate::shared_state::SharedState;
 use crate::ui::{
     active_window::ActiveWindowWidget, datetime::DateTimeWidget, menu::Menu,
@@ -19,14 +21,16 @@ use crate::ui::{
     volume::VolumeWidget, notifications::NotificationWidget,
     keyboard_layout::KeyboardLayoutWidget, system_resources::SystemResourcesWidget,
     network::NetworkWidget, brightness::BrightnessWidget, submap::SubmapWidget,
+    taskbar::TaskbarWidget, microphone::MicrophoneWidget, media::MediaWidget,
+    night_light::NightLightWidget,
 };
 use gtk4::prelude::*;
 use gtk4::{gdk, glib};
 use gtk4_layer_shell::{Edge, Layer, LayerShell};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
-use std::sync::{mpsc, Arc, Mutex};
+use std::sync::{Arc, Mutex};
 
 /// Контекст для создания виджетов (все сервисы)
 #[derive(Clone)]
@@ -37,8 +41,8 @@ pub struct WidgetContext {
     pub workspace_service: Arc<dyn WorkspaceService + Send + Sync>,
     pub tray_service: Arc<dyn SystemTrayService + Send + Sync>,
     pub datetime_service: Arc<dyn DateTimeService + Send + Sync>,
-    pub datetime_config: DateTimeConfig,
     pub battery_service: Arc<dyn BatteryService + Send + Sync>,
+    pub power_profile_service: Arc<dyn PowerProfileService + Send + Sync>,
     pub volume_service: Arc<dyn VolumeService + Send + Sync>,
     pub notification_service: Arc<dyn NotificationService + Send + Sync>,
     pub keyboard_layout_service: Arc<dyn KeyboardLayoutService + Send + Sync>,
@@ -46,6 +50,8 @@ pub struct WidgetContext {
     pub network_service: Arc<dyn NetworkService + Send + Sync>,
     pub brightness_service: Arc<dyn BrightnessService + Send + Sync>,
     pub submap_service: Arc<dyn SubmapService + Send + Sync>,
+    pub mpris_service: Arc<dyn MprisService + Send + Sync>,
+    pub night_light_service: Arc<dyn NightLightService + Send + Sync>,
     pub shared_state: Arc<SharedState>,
 }
 
@@ -63,6 +69,10 @@ struct CreatedWidgets {
     network: Option<NetworkWidget>,
     brightness: Option<BrightnessWidget>,
     submap: Option<Arc<Mutex<SubmapWidget>>>,
+    taskbar: Option<Arc<Mutex<TaskbarWidget>>>,
+    microphone: Option<Arc<Mutex<MicrophoneWidget>>>,
+    media: Option<Arc<Mutex<MediaWidget>>>,
+    night_light: Option<Arc<Mutex<NightLightWidget>>>,
 }
 
 impl CreatedWidgets {
@@ -80,10 +90,43 @@ impl CreatedWidgets {
             network: None,
             brightness: None,
             submap: None,
+            taskbar: None,
+            microphone: None,
+            media: None,
+            night_light: None,
+        }
+    }
+
+    /// Сбрасывает ссылку на виджет указанного типа
+    fn remove(&mut self, widget_type: WidgetType) {
+        match widget_type {
+            WidgetType::Menu => {}
+            WidgetType::Workspaces => self.workspaces = None,
+            WidgetType::ActiveWindow => self.active_window = None,
+            WidgetType::DateTime => self.datetime = None,
+            WidgetType::SystemTray => self.system_tray = None,
+            WidgetType::Battery => self.battery = None,
+            WidgetType::Volume => self.volume = None,
+            WidgetType::Notifications => self.notifications = None,
+            WidgetType::KeyboardLayout => self.keyboard_layout = None,
+            WidgetType::SystemResources => self.system_resources = None,
+            WidgetType::Network => self.network = None,
+            WidgetType::Brightness => self.brightness = None,
+            WidgetType::Submap => self.submap = None,
+            WidgetType::Taskbar => self.taskbar = None,
+            WidgetType::Microphone => self.microphone = None,
+            WidgetType::Media => self.media = None,
+            WidgetType::NightLight => self.night_light = None,
         }
     }
 }
 
+/// Виджет, размещённый на панели, и конфигурация, с которой он создан
+struct PlacedWidget {
+    config: WidgetConfig,
+    root: gtk4::Widget,
+}
+
 pub struct Bar {
     window: gtk4::ApplicationWindow,
     left_box: gtk4::Box,
@@ -91,6 +134,8 @@ pub struct Bar {
     right_box: gtk4::Box,
     context: Rc<WidgetContext>,
     widgets: Rc<RefCell<CreatedWidgets>>,
+    /// Размещённые виджеты по типу (для пересборки только изменившихся)
+    placed: HashMap<WidgetType, PlacedWidget>,
     shared_state: Arc<SharedState>,
 }
 
@@ -102,8 +147,8 @@ impl Bar {
         service: Arc<dyn WorkspaceService + Send + Sync>,
         tray_service: Arc<dyn SystemTrayService + Send + Sync>,
         datetime_service: Arc<dyn DateTimeService + Send + Sync>,
-        datetime_config: DateTimeConfig,
         battery_service: Arc<dyn BatteryService + Send + Sync>,
+        power_profile_service: Arc<dyn PowerProfileService + Send + Sync>,
         volume_service: Arc<dyn VolumeService + Send + Sync>,
         notification_service: Arc<dyn NotificationService + Send + Sync>,
         keyboard_layout_service: Arc<dyn KeyboardLayoutService + Send + Sync>,
@@ -111,6 +156,8 @@ impl Bar {
         network_service: Arc<dyn NetworkService + Send + Sync>,
         brightness_service: Arc<dyn BrightnessService + Send + Sync>,
         submap_service: Arc<dyn SubmapService + Send + Sync>,
+        mpris_service: Arc<dyn MprisService + Send + Sync>,
+        night_light_service: Arc<dyn NightLightService + Send + Sync>,
         shared_state: Arc<SharedState>,
     ) -> Self {
         let window = gtk4::ApplicationWindow::new(app);
@@ -175,8 +222,8 @@ impl Bar {
             workspace_service: service,
             tray_service,
             datetime_service,
-            datetime_config,
             battery_service,
+            power_profile_service,
             volume_service,
             notification_service,
             keyboard_layout_service,
@@ -184,6 +231,8 @@ impl Bar {
             network_service,
             brightness_service,
             submap_service,
+            mpris_service,
+            night_light_service,
             shared_state: shared_state.clone(),
         });
 
@@ -196,6 +245,7 @@ impl Bar {
             right_box,
             context,
             widgets,
+            placed: HashMap::new(),
             shared_state,
         };
 
@@ -219,13 +269,10 @@ impl Bar {
     }
 
     /// Перестраивает виджеты на основе конфигурации
+    ///
+    /// Пересоздаются только виджеты, которые появились или у которых изменились options;
+    /// остальные переставляются по новым зонам и порядку без пересоздания.
     pub fn rebuild_widgets(&mut self) {
-        // Очищаем зоны
-        self.clear_zones();
-
-        // Сбрасываем ссылки на виджеты
-        *self.widgets.borrow_mut() = CreatedWidgets::new();
-
         // Загружаем конфигурацию и получаем профиль для этого монитора
         let (left_widgets, center_widgets, right_widgets) = {
             let config = get_config().read().unwrap();
@@ -233,54 +280,110 @@ impl Bar {
 
             let mut left: Vec<_> = profile.widgets.iter()
                 .filter(|w| w.enabled && w.position == WidgetPosition::Left)
-                .map(|w| (w.widget_type, w.order))
+                .cloned()
                 .collect();
             let mut center: Vec<_> = profile.widgets.iter()
                 .filter(|w| w.enabled && w.position == WidgetPosition::Center)
-                .map(|w| (w.widget_type, w.order))
+                .cloned()
                 .collect();
             let mut right: Vec<_> = profile.widgets.iter()
                 .filter(|w| w.enabled && w.position == WidgetPosition::Right)
-                .map(|w| (w.widget_type, w.order))
+                .cloned()
                 .collect();
 
-            left.sort_by_key(|(_, order)| *order);
-            center.sort_by_key(|(_, order)| *order);
-            right.sort_by_key(|(_, order)| *order);
+            left.sort_by_key(|w| w.order);
+            center.sort_by_key(|w| w.order);
+            right.sort_by_key(|w| w.order);
 
             (left, center, right)
         };
 
-        // Создаём виджеты для каждой зоны
-        for (widget_type, _) in left_widgets {
-            self.create_widget(widget_type, &self.left_box.clone());
-        }
-        for (widget_type, _) in center_widgets {
-            self.create_widget(widget_type, &self.center_box.clone());
+        let desired: Vec<&WidgetConfig> = left_widgets.iter()
+            .chain(center_widgets.iter())
+            .chain(right_widgets.iter())
+            .collect();
+
+        // Конфигурация панели не изменилась
+        let unchanged = desired.len() == self.placed.len()
+            && desired.iter().all(|w| {
+                self.placed.get(&w.widget_type).is_some_and(|p| p.config == **w)
+            });
+        if unchanged {
+            return;
         }
-        for (widget_type, _) in right_widgets {
-            self.create_widget(widget_type, &self.right_box.clone());
+
+        // Удаляем виджеты, которых больше нет или у которых изменились options
+        let widgets = self.widgets.clone();
+        self.placed.retain(|widget_type, placed| {
+            let keep = desired.iter().any(|w| {
+                w.widget_type == *widget_type && w.options == placed.config.options
+            });
+            if !keep {
+                widgets.borrow_mut().remove(*widget_type);
+            }
+            keep
+        });
+
+        // Снимаем оставшиеся виджеты с зон и расставляем всё в новом порядке
+        self.clear_zones();
+
+        let zones = [
+            (self.left_box.clone(), left_widgets),
+            (self.center_box.clone(), center_widgets),
+            (self.right_box.clone(), right_widgets),
+        ];
+
+        let mut arranged = HashSet::new();
+        let mut created = 0;
+        let mut reused = 0;
+
+        for (zone, zone_widgets) in zones {
+            for widget_config in zone_widgets {
+                // Один экземпляр виджета каждого типа на панель
+                if !arranged.insert(widget_config.widget_type) {
+                    eprintln!("[Bar] ✗ Duplicate widget {:?} skipped", widget_config.widget_type);
+                    continue;
+                }
+
+                if let Some(placed) = self.placed.get_mut(&widget_config.widget_type) {
+                    zone.append(&placed.root);
+                    placed.config = widget_config;
+                    reused += 1;
+                    continue;
+                }
+
+                self.create_widget(&widget_config, &zone);
+                if let Some(root) = zone.last_child() {
+                    self.placed.insert(widget_config.widget_type, PlacedWidget {
+                        config: widget_config,
+                        root,
+                    });
+                }
+                created += 1;
+            }
         }
 
-        // Обновляем все виджеты с текущими данными
-        self.initial_update();
+        if created > 0 {
+            // Обновляем все виджеты с текущими данными
+            self.initial_update();
 
-        // Обновляем трей с текущими данными
-        let items = self.shared_state.get_tray();
-        let widgets = self.widgets.borrow();
-        if let Some(ref widget) = widgets.system_tray {
-            widget.lock().unwrap().update(&items);
+            // Обновляем трей с текущими данными
+            let items = self.shared_state.get_tray();
+            let widgets = self.widgets.borrow();
+            if let Some(ref widget) = widgets.system_tray {
+                widget.lock().unwrap().update(&items);
+            }
         }
 
-        eprintln!("[Bar] ✓ Widgets rebuilt");
+        eprintln!("[Bar] ✓ Widgets rebuilt ({} created, {} kept)", created, reused);
     }
 
     /// Создаёт виджет и добавляет его в контейнер
-    fn create_widget(&self, widget_type: WidgetType, container: &gtk4::Box) {
+    fn create_widget(&self, widget_config: &WidgetConfig, container: &gtk4::Box) {
         let ctx = &self.context;
         let mut widgets = self.widgets.borrow_mut();
 
-        match widget_type {
+        match widget_config.widget_type {
             WidgetType::Menu => {
                 let menu = Menu::new();
                 let button = menu.create_button(&ctx.app);
@@ -291,19 +394,24 @@ impl Bar {
                     ctx.monitor_name.clone(),
                     ctx.workspace_keys.clone(),
                     ctx.workspace_service.clone(),
+                    widget_config.options(),
                 )));
                 container.append(widget.lock().unwrap().widget());
                 widgets.workspaces = Some(widget);
             }
             WidgetType::ActiveWindow => {
-                let widget = Arc::new(Mutex::new(ActiveWindowWidget::new(ctx.workspace_service.clone())));
+                let widget = Arc::new(Mutex::new(ActiveWindowWidget::new(
+                    ctx.workspace_service.clone(),
+                    widget_config.options(),
+                )));
                 container.append(widget.lock().unwrap().widget());
                 widgets.active_window = Some(widget);
             }
             WidgetType::DateTime => {
+                let options: DateTimeOptions = widget_config.options();
                 let widget = Arc::new(Mutex::new(DateTimeWidget::new(
                     ctx.datetime_service.clone(),
-                    ctx.datetime_config.clone(),
+                    options.to_datetime_config(),
                 )));
                 container.append(widget.lock().unwrap().widget());
                 widgets.datetime = Some(widget);
@@ -314,7 +422,11 @@ impl Bar {
                 widgets.system_tray = Some(widget);
             }
             WidgetType::Battery => {
-                let widget = Arc::new(Mutex::new(BatteryWidget::new(ctx.battery_service.clone())));
+                let widget = Arc::new(Mutex::new(BatteryWidget::new(
+                    ctx.battery_service.clone(),
+                    ctx.power_profile_service.clone(),
+                    widget_config.options(),
+                )));
                 container.append(widget.lock().unwrap().widget());
                 widgets.battery = Some(widget);
             }
@@ -334,7 +446,10 @@ impl Bar {
                 widgets.keyboard_layout = Some(widget);
             }
             WidgetType::SystemResources => {
-                let widget = Arc::new(Mutex::new(SystemResourcesWidget::new(ctx.system_resources_service.clone())));
+                let widget = Arc::new(Mutex::new(SystemResourcesWidget::new(
+                    ctx.system_resources_service.clone(),
+                    widget_config.options(),
+                )));
                 container.append(widget.lock().unwrap().widget());
                 widgets.system_resources = Some(widget);
             }
@@ -344,7 +459,7 @@ impl Bar {
                 widgets.network = Some(widget);
             }
             WidgetType::Brightness => {
-                let widget = BrightnessWidget::new(ctx.brightness_service.clone());
+                let widget = BrightnessWidget::new(ctx.brightness_service.clone(), &ctx.monitor_name);
                 container.append(&widget.container);
                 widgets.brightness = Some(widget);
             }
@@ -353,41 +468,89 @@ impl Bar {
                 container.append(widget.lock().unwrap().widget());
                 widgets.submap = Some(widget);
             }
+            WidgetType::Taskbar => {
+                let widget = Arc::new(Mutex::new(TaskbarWidget::new(
+                    ctx.monitor_name.clone(),
+                    ctx.workspace_service.clone(),
+                    widget_config.options(),
+                )));
+                container.append(widget.lock().unwrap().widget());
+                widgets.taskbar = Some(widget);
+            }
+            WidgetType::Microphone => {
+                let widget = Arc::new(Mutex::new(MicrophoneWidget::new(ctx.volume_service.clone())));
+                container.append(widget.lock().unwrap().widget());
+                widgets.microphone = Some(widget);
+            }
+            WidgetType::Media => {
+                let widget = Arc::new(Mutex::new(MediaWidget::new(ctx.mpris_service.clone())));
+                container.append(widget.lock().unwrap().widget());
+                widgets.media = Some(widget);
+            }
+            WidgetType::NightLight => {
+                let widget = Arc::new(Mutex::new(NightLightWidget::new(ctx.night_light_service.clone())));
+                container.append(widget.lock().unwrap().widget());
+                widgets.night_light = Some(widget);
+            }
         }
     }
 
     pub fn setup_event_listener(&self) {
-        let (tx, rx) = mpsc::channel();
+        let events_rx = get_event_bus().subscribe();
 
-        event_listener::start_event_listener(move || {
-            let _ = tx.send(());
-        });
+        // Таймеры держат слабую ссылку на виджеты: после удаления бара они
+        // останавливаются, а их получатели закрываются
 
         // Обработка событий Hyprland (workspaces, active window)
-        let widgets = self.widgets.clone();
+        let widgets = Rc::downgrade(&self.widgets);
         glib::timeout_add_local(std::time::Duration::from_millis(16), move || {
+            let Some(widgets) = widgets.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
             let mut should_update = false;
+            let mut should_update_active_window = false;
+            let mut should_update_taskbar = false;
 
-            while rx.try_recv().is_ok() {
-                should_update = true;
+            while let Ok(event) = events_rx.try_recv() {
+                if event.affects_workspaces() {
+                    should_update = true;
+                }
+                if event.affects_active_window() {
+                    should_update_active_window = true;
+                }
+                if let Some(ref widget) = widgets.borrow().taskbar {
+                    if widget.lock().unwrap().handle_event(&event) {
+                        should_update_taskbar = true;
+                    }
+                }
             }
 
+            let widgets = widgets.borrow();
             if should_update {
-                let widgets = widgets.borrow();
                 if let Some(ref widget) = widgets.workspaces {
                     widget.lock().unwrap().update();
                 }
+            }
+            if should_update_active_window {
                 if let Some(ref widget) = widgets.active_window {
                     widget.lock().unwrap().update();
                 }
             }
+            if should_update_taskbar {
+                if let Some(ref widget) = widgets.taskbar {
+                    widget.lock().unwrap().update();
+                }
+            }
 
             glib::ControlFlow::Continue
         });
 
         // Обновление времени каждую секунду
-        let widgets = self.widgets.clone();
+        let widgets = Rc::downgrade(&self.widgets);
         glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
+            let Some(widgets) = widgets.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
             let widgets = widgets.borrow();
             if let Some(ref widget) = widgets.datetime {
                 widget.lock().unwrap().update_time();
@@ -405,7 +568,7 @@ impl Bar {
     fn setup_shared_state_subscriptions(&self) {
         // Подписка на обновления батареи
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let (sender, receiver) = async_channel::unbounded::<()>();
 
             self.shared_state.subscribe_battery(move || {
@@ -413,6 +576,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let widgets = widgets.borrow();
                     if let Some(ref widget) = widgets.battery {
@@ -425,7 +591,7 @@ impl Bar {
 
         // Подписка на обновления громкости
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let (sender, receiver) = async_channel::unbounded::<()>();
 
             self.shared_state.subscribe_volume(move || {
@@ -433,6 +599,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let widgets = widgets.borrow();
                     if let Some(ref widget) = widgets.volume {
@@ -443,9 +612,78 @@ impl Bar {
             });
         }
 
+        // Подписка на обновления микрофона
+        {
+            let widgets = Rc::downgrade(&self.widgets);
+            let (sender, receiver) = async_channel::unbounded::<()>();
+
+            self.shared_state.subscribe_microphone(move || {
+                let _ = sender.send_blocking(());
+            });
+
+            glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
+                while receiver.try_recv().is_ok() {
+                    let widgets = widgets.borrow();
+                    if let Some(ref widget) = widgets.microphone {
+                        widget.lock().unwrap().update();
+                    }
+                }
+                glib::ControlFlow::Continue
+            });
+        }
+
+        // Подписка на обновления медиаплееров
+        {
+            let widgets = Rc::downgrade(&self.widgets);
+            let (sender, receiver) = async_channel::unbounded::<()>();
+
+            self.shared_state.subscribe_media(move || {
+                let _ = sender.send_blocking(());
+            });
+
+            glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
+                while receiver.try_recv().is_ok() {
+                    let widgets = widgets.borrow();
+                    if let Some(ref widget) = widgets.media {
+                        widget.lock().unwrap().update();
+                    }
+                }
+                glib::ControlFlow::Continue
+            });
+        }
+
+        // Подписка на изменения ночного режима
+        {
+            let widgets = Rc::downgrade(&self.widgets);
+            let (sender, receiver) = async_channel::unbounded::<()>();
+
+            self.shared_state.subscribe_night_light(move || {
+                let _ = sender.send_blocking(());
+            });
+
+            glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
+                while receiver.try_recv().is_ok() {
+                    let widgets = widgets.borrow();
+                    if let Some(ref widget) = widgets.night_light {
+                        widget.lock().unwrap().update();
+                    }
+                }
+                glib::ControlFlow::Continue
+            });
+        }
+
         // Подписка на обновления трея
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let shared_state = self.shared_state.clone();
             let (sender, receiver) = async_channel::unbounded::<()>();
 
@@ -454,6 +692,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let items = shared_state.get_tray();
                     let widgets = widgets.borrow();
@@ -467,7 +708,7 @@ impl Bar {
 
         // Подписка на обновления раскладки клавиатуры
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let (sender, receiver) = async_channel::unbounded::<()>();
 
             self.shared_state.subscribe_keyboard_layout(move || {
@@ -475,6 +716,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let widgets = widgets.borrow();
                     if let Some(ref widget) = widgets.keyboard_layout {
@@ -487,7 +731,7 @@ impl Bar {
 
         // Подписка на обновления уведомлений
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let (sender, receiver) = async_channel::unbounded::<()>();
 
             self.shared_state.subscribe_notifications(move || {
@@ -495,6 +739,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let widgets = widgets.borrow();
                     if let Some(ref widget) = widgets.notifications {
@@ -507,7 +754,7 @@ impl Bar {
 
         // Подписка на обновления системных ресурсов
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let (sender, receiver) = async_channel::unbounded::<()>();
 
             self.shared_state.subscribe_system_resources(move || {
@@ -515,6 +762,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let widgets = widgets.borrow();
                     if let Some(ref widget) = widgets.system_resources {
@@ -527,7 +777,7 @@ impl Bar {
 
         // Подписка на обновления submap
         {
-            let widgets = self.widgets.clone();
+            let widgets = Rc::downgrade(&self.widgets);
             let (sender, receiver) = async_channel::unbounded::<()>();
 
             self.shared_state.subscribe_submap(move || {
@@ -535,6 +785,9 @@ impl Bar {
             });
 
             glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+                let Some(widgets) = widgets.upgrade() else {
+                    return glib::ControlFlow::Break;
+                };
                 while receiver.try_recv().is_ok() {
                     let widgets = widgets.borrow();
                     if let Some(ref widget) = widgets.submap {
@@ -555,6 +808,9 @@ impl Bar {
         if let Some(ref widget) = widgets.active_window {
             widget.lock().unwrap().update();
         }
+        if let Some(ref widget) = widgets.taskbar {
+            widget.lock().unwrap().update();
+        }
         if let Some(ref widget) = widgets.datetime {
             widget.lock().unwrap().update_time();
         }
@@ -564,6 +820,15 @@ impl Bar {
         if let Some(ref widget) = widgets.volume {
             widget.lock().unwrap().update();
         }
+        if let Some(ref widget) = widgets.microphone {
+            widget.lock().unwrap().update();
+        }
+        if let Some(ref widget) = widgets.media {
+            widget.lock().unwrap().update();
+        }
+        if let Some(ref widget) = widgets.night_light {
+            widget.lock().unwrap().update();
+        }
         if let Some(ref widget) = widgets.notifications {
             widget.lock().unwrap().update();
         }
@@ -1,21 +1,75 @@
 use gtk4::prelude::*;
 use std::sync::Arc;
+use crate::config::BatteryOptions;
 use crate::domain::battery_service::BatteryService;
-use crate::domain::models::{BatteryInfo, BatteryStatus};
+use crate::domain::models::{BatteryInfo, BatteryKind, BatteryStatus};
+use crate::domain::power_profile_service::PowerProfileService;
+use crate::shared_state::get_shared_state;
 
+/// Виджет батареи
+///
+/// На панели - основная батарея (или самое разряженное периферийное устройство,
+/// если батареи нет), по клику - подробности, профиль питания и заряд периферии.
 pub struct BatteryWidget {
     container: gtk4::Box,
+    content: gtk4::Box,
+    details: gtk4::Box,
+    profiles: gtk4::Box,
+    peripherals: gtk4::Box,
     service: Arc<dyn BatteryService + Send + Sync>,
+    power_profile_service: Arc<dyn PowerProfileService + Send + Sync>,
+    options: BatteryOptions,
 }
 
 impl BatteryWidget {
-    pub fn new(service: Arc<dyn BatteryService + Send + Sync>) -> Self {
+    pub fn new(
+        service: Arc<dyn BatteryService + Send + Sync>,
+        power_profile_service: Arc<dyn PowerProfileService + Send + Sync>,
+        options: BatteryOptions,
+    ) -> Self {
         let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
         container.add_css_class("battery-widget");
 
+        let content = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        container.append(&content);
+
+        // Popover с подробностями и периферией
+        let popover_box = gtk4::Box::new(gtk4::Orientation::Vertical, 8);
+        popover_box.add_css_class("battery-popover");
+
+        let details = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+        popover_box.append(&details);
+
+        let profiles = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        profiles.add_css_class("battery-profiles");
+        profiles.set_homogeneous(true);
+        popover_box.append(&profiles);
+
+        let peripherals = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        popover_box.append(&peripherals);
+
+        let popover = gtk4::Popover::new();
+        popover.set_parent(&container);
+        popover.set_child(Some(&popover_box));
+
+        let gesture = gtk4::GestureClick::new();
+        let popover_weak = popover.downgrade();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Some(p) = popover_weak.upgrade() {
+                p.popup();
+            }
+        });
+        container.add_controller(gesture);
+
         Self {
             container,
+            content,
+            details,
+            profiles,
+            peripherals,
             service,
+            power_profile_service,
+            options,
         }
     }
 
@@ -24,27 +78,43 @@ impl BatteryWidget {
     }
 
     pub fn update(&self) {
-        // Очищаем контейнер
-        while let Some(child) = self.container.first_child() {
-            self.container.remove(&child);
+        // Очищаем содержимое
+        while let Some(child) = self.content.first_child() {
+            self.content.remove(&child);
         }
 
-        // Получаем информацию о батарее
-        if let Some(battery_info) = self.service.get_battery_info() {
+        let battery = self.service.get_battery_info();
+        let peripherals = self.service.get_peripherals();
+
+        self.update_popover(battery.as_ref(), &peripherals);
+        self.update_profiles();
+
+        // Без батареи ноутбука показываем самое разряженное устройство
+        let shown = battery.clone().or_else(|| {
+            peripherals.iter().min_by_key(|p| p.percentage).cloned()
+        });
+
+        if let Some(battery_info) = shown {
             println!("[BatteryWidget] Updated: {}% {:?}", battery_info.percentage, battery_info.status);
 
             // Создаём иконку
-            let icon = self.create_battery_icon(&battery_info);
-            self.container.append(&icon);
+            let icon = gtk4::Label::new(Some(battery_icon(&battery_info)));
+            icon.add_css_class("battery-icon");
+            self.content.append(&icon);
 
             // Создаём лейбл с процентами
-            let label = gtk4::Label::new(Some(&format!("{}%", battery_info.percentage)));
-            label.add_css_class("battery-percentage");
-            self.container.append(&label);
+            if self.options.show_percentage {
+                let label = gtk4::Label::new(Some(&format!("{}%", battery_info.percentage)));
+                label.add_css_class("battery-percentage");
+                self.content.append(&label);
+            }
 
             // Устанавливаем tooltip с дополнительной информацией
-            let tooltip = self.create_tooltip(&battery_info);
-            self.container.set_tooltip_text(Some(&tooltip));
+            let mut tooltip = self.create_tooltip(&battery_info);
+            if battery.is_some() && !peripherals.is_empty() {
+                tooltip.push_str(&format!("{} device(s) - click for details", peripherals.len()));
+            }
+            self.container.set_tooltip_text(Some(tooltip.trim_end()));
 
             // Устанавливаем CSS класс в зависимости от уровня заряда
             self.apply_battery_level_class(&battery_info);
@@ -52,36 +122,135 @@ impl BatteryWidget {
             // Батарея не обнаружена
             let label = gtk4::Label::new(Some("No Battery"));
             label.add_css_class("battery-not-found");
-            self.container.append(&label);
+            self.content.append(&label);
+            self.container.set_tooltip_text(None);
         }
     }
 
-    /// Создаёт иконку батареи в зависимости от статуса и уровня заряда
-    fn create_battery_icon(&self, info: &BatteryInfo) -> gtk4::Label {
-        let icon_text = match info.status {
-            BatteryStatus::Charging => "󰂄", // Nerd Font: battery charging
-            BatteryStatus::Full => "󰁹", // Nerd Font: battery full
-            _ => {
-                // Выбираем иконку в зависимости от уровня заряда
-                match info.percentage {
-                    90..=100 => "󰁹", // battery full
-                    70..=89 => "󰂂", // battery 90%
-                    50..=69 => "󰂀", // battery 70%
-                    30..=49 => "󰁾", // battery 50%
-                    10..=29 => "󰁼", // battery 30%
-                    _ => "󰁺", // battery low/critical
-                }
+    /// Заполняет popover: основная батарея и список периферии
+    fn update_popover(&self, battery: Option<&BatteryInfo>, peripherals: &[BatteryInfo]) {
+        while let Some(child) = self.details.first_child() {
+            self.details.remove(&child);
+        }
+        while let Some(child) = self.peripherals.first_child() {
+            self.peripherals.remove(&child);
+        }
+
+        if let Some(info) = battery {
+            let title = gtk4::Label::new(Some(&format!("Battery {}%", info.percentage)));
+            title.set_halign(gtk4::Align::Start);
+            title.add_css_class("battery-popover-title");
+            self.details.append(&title);
+
+            for line in self.create_tooltip(info).lines().skip(1) {
+                let label = gtk4::Label::new(Some(line));
+                label.set_halign(gtk4::Align::Start);
+                label.add_css_class("battery-detail");
+                self.details.append(&label);
             }
-        };
+        }
+        self.details.set_visible(battery.is_some());
+
+        if peripherals.is_empty() {
+            self.peripherals.set_visible(battery.is_none());
+            if battery.is_none() {
+                let label = gtk4::Label::new(Some("No batteries found"));
+                label.add_css_class("battery-not-found");
+                self.peripherals.append(&label);
+            }
+            return;
+        }
+        self.peripherals.set_visible(true);
 
-        let icon = gtk4::Label::new(Some(icon_text));
-        icon.add_css_class("battery-icon");
-        icon
+        let title = gtk4::Label::new(Some("Devices"));
+        title.set_halign(gtk4::Align::Start);
+        title.add_css_class("battery-popover-title");
+        self.peripherals.append(&title);
+
+        for device in peripherals {
+            let row = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+            row.add_css_class("battery-device");
+
+            let icon = gtk4::Label::new(Some(kind_icon(device.kind)));
+            icon.add_css_class("battery-device-icon");
+            row.append(&icon);
+
+            let name = if device.model.is_empty() {
+                device.kind.name().to_string()
+            } else {
+                device.model.clone()
+            };
+            let name_label = gtk4::Label::new(Some(&name));
+            name_label.set_halign(gtk4::Align::Start);
+            name_label.set_hexpand(true);
+            name_label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+            name_label.set_max_width_chars(28);
+            name_label.add_css_class("battery-device-name");
+            row.append(&name_label);
+
+            let level = match device.status {
+                BatteryStatus::Charging => format!("󰂄 {}%", device.percentage),
+                _ => format!("{}%", device.percentage),
+            };
+            let level_label = gtk4::Label::new(Some(&level));
+            level_label.add_css_class("battery-device-level");
+            if device.percentage <= self.options.low_threshold {
+                level_label.add_css_class("battery-device-low");
+            }
+            row.append(&level_label);
+
+            self.peripherals.append(&row);
+        }
+    }
+
+    /// Кнопки профилей питания; скрыты, если power-profiles-daemon не запущен
+    fn update_profiles(&self) {
+        while let Some(child) = self.profiles.first_child() {
+            self.profiles.remove(&child);
+        }
+
+        let available = self.power_profile_service.available_profiles();
+        self.profiles.set_visible(!available.is_empty());
+
+        let active = get_shared_state().get_power_profile();
+        for profile in available {
+            let button = gtk4::Button::new();
+            button.add_css_class("battery-profile-button");
+            button.set_tooltip_text(Some(profile.name()));
+
+            let content = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+            let icon = gtk4::Label::new(Some(profile.icon()));
+            icon.add_css_class("battery-profile-icon");
+            let name = gtk4::Label::new(Some(profile.name()));
+            name.add_css_class("battery-profile-name");
+            content.append(&icon);
+            content.append(&name);
+            button.set_child(Some(&content));
+
+            if active == Some(profile) {
+                button.add_css_class("active");
+            }
+
+            let service = Arc::clone(&self.power_profile_service);
+            button.connect_clicked(move |_| {
+                match service.set_profile(profile) {
+                    Ok(()) => get_shared_state().update_power_profile(Some(profile)),
+                    Err(e) => eprintln!("[Battery] ✗ {}", e),
+                }
+            });
+
+            self.profiles.append(&button);
+        }
     }
 
     /// Создаёт текст для tooltip
     fn create_tooltip(&self, info: &BatteryInfo) -> String {
-        let mut tooltip = format!("Battery: {}%\n", info.percentage);
+        let name = if info.kind == BatteryKind::Battery || info.model.is_empty() {
+            info.kind.name()
+        } else {
+            info.model.as_str()
+        };
+        let mut tooltip = format!("{}: {}%\n", name, info.percentage);
 
         tooltip.push_str(&format!(
             "Status: {}\n",
@@ -106,6 +275,18 @@ impl BatteryWidget {
             tooltip.push_str(&format!("Time to full: {}h {:02}m\n", hours, mins));
         }
 
+        if let Some(rate) = info.energy_rate {
+            tooltip.push_str(&format!("Power: {:.1} W\n", rate));
+        }
+
+        if let Some(health) = info.health {
+            tooltip.push_str(&format!("Health: {}%\n", health));
+        }
+
+        if info.kind == BatteryKind::Battery && !info.model.is_empty() {
+            tooltip.push_str(&format!("Model: {}\n", info.model));
+        }
+
         tooltip
     }
 
@@ -125,13 +306,54 @@ impl BatteryWidget {
         }
 
         // Добавляем класс уровня заряда
-        match info.percentage {
-            0..=10 => self.container.add_css_class("battery-critical"),
-            11..=30 => self.container.add_css_class("battery-low"),
-            31..=60 => self.container.add_css_class("battery-medium"),
-            61..=90 => self.container.add_css_class("battery-high"),
-            _ => self.container.add_css_class("battery-full"),
+        let class = match info.percentage {
+            p if p <= self.options.critical_threshold => "battery-critical",
+            p if p <= self.options.low_threshold => "battery-low",
+            0..=60 => "battery-medium",
+            61..=90 => "battery-high",
+            _ => "battery-full",
+        };
+        self.container.add_css_class(class);
+    }
+}
+
+/// Иконка батареи в зависимости от статуса и уровня заряда
+fn battery_icon(info: &BatteryInfo) -> &'static str {
+    if info.kind != BatteryKind::Battery {
+        return kind_icon(info.kind);
+    }
+
+    match info.status {
+        BatteryStatus::Charging => "󰂄", // Nerd Font: battery charging
+        BatteryStatus::Full => "󰁹", // Nerd Font: battery full
+        _ => {
+            // Выбираем иконку в зависимости от уровня заряда
+            match info.percentage {
+                90..=100 => "󰁹", // battery full
+                70..=89 => "󰂂", // battery 90%
+                50..=69 => "󰂀", // battery 70%
+                30..=49 => "󰁾", // battery 50%
+                10..=29 => "󰁼", // battery 30%
+                _ => "󰁺", // battery low/critical
+            }
         }
     }
 }
 
+/// Иконка типа устройства
+fn kind_icon(kind: BatteryKind) -> &'static str {
+    match kind {
+        BatteryKind::Battery => "󰁹",
+        BatteryKind::Ups => "󰚥",
+        BatteryKind::Mouse => "󰍽",
+        BatteryKind::Keyboard => "󰌌",
+        BatteryKind::Headset => "󰋎",
+        BatteryKind::Headphones => "󰋋",
+        BatteryKind::Phone => "󰏲",
+        BatteryKind::Tablet => "󰓶",
+        BatteryKind::GamingInput => "󰊴",
+        BatteryKind::Pen => "󰏪",
+        BatteryKind::Touchpad => "󰟸",
+        BatteryKind::Other => "󰂑",
+    }
+}
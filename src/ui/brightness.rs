@@ -2,18 +2,22 @@ use gtk4::{
     prelude::*,
     Box as GtkBox, Label, Orientation, Scale, glib, Popover,
 };
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::sync::Arc;
 use crate::domain::brightness_service::BrightnessService;
+use crate::domain::models::BrightnessDisplay;
 use crate::shared_state::get_shared_state;
 
 pub struct BrightnessWidget {
     pub container: GtkBox,
     icon_label: Label,
     percentage_label: Label,
+    monitor_name: String,
 }
 
 impl BrightnessWidget {
-    pub fn new<T: BrightnessService + 'static + ?Sized>(brightness_service: Arc<T>) -> Self {
+    pub fn new<T: BrightnessService + 'static + ?Sized>(brightness_service: Arc<T>, monitor_name: &str) -> Self {
         let container = GtkBox::new(Orientation::Horizontal, 4);
         container.set_css_classes(&["brightness-widget"]);
 
@@ -30,11 +34,10 @@ impl BrightnessWidget {
 
         // Обновляем начальное состояние из SharedState
         let shared_state = get_shared_state();
-        let brightness = shared_state.get_brightness();
-        Self::update_display(&icon_label, &percentage_label, brightness);
+        Self::update_display(&container, &icon_label, &percentage_label, monitor_name);
 
         // Создаем popover для управления яркостью
-        let popover = Self::create_brightness_popover(brightness_service.clone());
+        let popover = Self::create_brightness_popover(brightness_service.clone(), monitor_name);
         popover.set_parent(&container);
 
         // Обработчик клика
@@ -55,12 +58,13 @@ impl BrightnessWidget {
         });
 
         // Обрабатываем обновления в главном потоке GTK
+        let container_clone = container.clone();
         let icon_clone = icon_label.clone();
         let percentage_clone = percentage_label.clone();
+        let monitor = monitor_name.to_string();
         glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
             while rx.try_recv().is_ok() {
-                let brightness = get_shared_state().get_brightness();
-                Self::update_display(&icon_clone, &percentage_clone, brightness);
+                Self::update_display(&container_clone, &icon_clone, &percentage_clone, &monitor);
             }
             glib::ControlFlow::Continue
         });
@@ -70,28 +74,34 @@ impl BrightnessWidget {
             container,
             icon_label,
             percentage_label,
+            monitor_name: monitor_name.to_string(),
         }
     }
 
     pub fn update(&self) {
-        let shared_state = get_shared_state();
-        let brightness = shared_state.get_brightness();
-        Self::update_display(&self.icon_label, &self.percentage_label, brightness);
+        Self::update_display(&self.container, &self.icon_label, &self.percentage_label, &self.monitor_name);
     }
 
-    fn update_display(icon_label: &Label, percentage_label: &Label, brightness: u32) {
-        // Выбираем иконку в зависимости от уровня яркости
-        let icon = match brightness {
-            0 => "󰃚",           // nf-md-brightness_1 (минимум)
-            1..=14 => "󰃛",      // nf-md-brightness_2
-            15..=28 => "󰃜",     // nf-md-brightness_3
-            29..=42 => "󰃝",     // nf-md-brightness_4
-            43..=57 => "󰃞",     // nf-md-brightness_5
-            58..=71 => "󰃟",     // nf-md-brightness_6
-            _ => "󰃠",           // nf-md-brightness_7 (максимум)
+    /// Показывает яркость дисплея этого монитора (или основного, если у монитора её нет)
+    fn update_display(container: &GtkBox, icon_label: &Label, percentage_label: &Label, monitor_name: &str) {
+        let shared_state = get_shared_state();
+        let displays = shared_state.get_brightness_displays();
+
+        // Нечем управлять (нет подсветки и DDC/CI мониторов)
+        let Some(display) = display_for_monitor(&displays, monitor_name) else {
+            container.set_visible(false);
+            return;
+        };
+        container.set_visible(true);
+
+        // Встроенная подсветка обновляется по сигналам - берём актуальное значение
+        let brightness = if display.internal {
+            shared_state.get_brightness()
+        } else {
+            display.brightness
         };
 
-        icon_label.set_text(icon);
+        icon_label.set_text(brightness_icon(brightness));
         percentage_label.set_text(&format!("{}%", brightness));
 
         // Устанавливаем CSS класс для цветовой индикации
@@ -119,7 +129,7 @@ impl BrightnessWidget {
         }
     }
 
-    fn create_brightness_popover<T: BrightnessService + 'static + ?Sized>(brightness_service: Arc<T>) -> Popover {
+    fn create_brightness_popover<T: BrightnessService + 'static + ?Sized>(brightness_service: Arc<T>, monitor_name: &str) -> Popover {
         let popover = Popover::new();
         popover.set_css_classes(&["brightness-popover"]);
 
@@ -134,37 +144,10 @@ impl BrightnessWidget {
         title.set_css_classes(&["brightness-title"]);
         main_box.append(&title);
 
-        // Слайдер
-        let slider_box = GtkBox::new(Orientation::Horizontal, 8);
-
-        let min_icon = Label::new(Some("󰃚")); // Минимум (brightness_1)
-        min_icon.set_css_classes(&["brightness-slider-icon"]);
-
-        let slider = Scale::with_range(Orientation::Horizontal, 0.0, 100.0, 1.0);
-        slider.set_hexpand(true);
-        slider.set_css_classes(&["brightness-slider"]);
-        slider.set_draw_value(false);
-
-        if let Ok(brightness) = brightness_service.get_brightness() {
-            slider.set_value(brightness as f64);
-        }
-
-        let max_icon = Label::new(Some("󰃠")); // Максимум (brightness_7)
-        max_icon.set_css_classes(&["brightness-slider-icon"]);
-
-        slider_box.append(&min_icon);
-        slider_box.append(&slider);
-        slider_box.append(&max_icon);
-        main_box.append(&slider_box);
-
-        // Обработчик изменения слайдера
-        {
-            let brightness_service = brightness_service.clone();
-            slider.connect_value_changed(move |slider| {
-                let value = slider.value() as u32;
-                let _ = brightness_service.set_brightness(value);
-            });
-        }
+        // Слайдеры по одному на дисплей
+        let sliders = DisplaySliders::new(monitor_name);
+        main_box.append(&sliders.container);
+        sliders.update(&brightness_service);
 
         // Переключатель автоматической регулировки
         let auto_box = GtkBox::new(Orientation::Horizontal, 8);
@@ -195,19 +178,18 @@ impl BrightnessWidget {
 
         auto_box.append(&auto_label);
         auto_box.append(&auto_switch);
+        // Бэкенд без автоматической регулировки (sysfs) - переключатель не нужен
+        auto_box.set_visible(brightness_service.supports_auto_adjustment());
         main_box.append(&auto_box);
 
         popover.set_child(Some(&main_box));
 
-        // Обновляем слайдер при открытии popover
+        // Обновляем слайдеры при открытии popover
         {
             let brightness_service = brightness_service.clone();
-            let slider_clone = slider.clone();
             let auto_switch_clone = auto_switch.clone();
             popover.connect_show(move |_| {
-                if let Ok(brightness) = brightness_service.get_brightness() {
-                    slider_clone.set_value(brightness as f64);
-                }
+                sliders.update(&brightness_service);
                 if let Ok(enabled) = brightness_service.is_auto_adjustment_enabled() {
                     auto_switch_clone.set_active(enabled);
                 }
@@ -218,3 +200,128 @@ impl BrightnessWidget {
     }
 }
 
+/// Слайдеры яркости для всех дисплеев; дисплей текущего монитора - первый
+struct DisplaySliders {
+    container: GtkBox,
+    monitor_name: String,
+    /// (id дисплея, слайдер)
+    rows: RefCell<Vec<(String, Scale)>>,
+    /// Значение слайдера меняется из update(), а не пользователем
+    updating: Rc<Cell<bool>>,
+}
+
+impl DisplaySliders {
+    fn new(monitor_name: &str) -> Self {
+        Self {
+            container: GtkBox::new(Orientation::Vertical, 8),
+            monitor_name: monitor_name.to_string(),
+            rows: RefCell::new(Vec::new()),
+            updating: Rc::new(Cell::new(false)),
+        }
+    }
+
+    fn update<T: BrightnessService + 'static + ?Sized>(&self, brightness_service: &Arc<T>) {
+        let mut displays = brightness_service.list_displays();
+        // Свой монитор - первым
+        displays.sort_by_key(|d| d.connector.as_deref() != Some(self.monitor_name.as_str()));
+
+        let ids: Vec<String> = displays.iter().map(|d| d.id.clone()).collect();
+        let current: Vec<String> = self.rows.borrow().iter().map(|(id, _)| id.clone()).collect();
+        if ids != current {
+            self.rebuild(brightness_service, &displays);
+        }
+
+        self.updating.set(true);
+        for ((_, slider), display) in self.rows.borrow().iter().zip(&displays) {
+            slider.set_value(display.brightness as f64);
+        }
+        self.updating.set(false);
+    }
+
+    fn rebuild<T: BrightnessService + 'static + ?Sized>(&self, brightness_service: &Arc<T>, displays: &[BrightnessDisplay]) {
+        while let Some(child) = self.container.first_child() {
+            self.container.remove(&child);
+        }
+
+        let mut rows = Vec::new();
+        for display in displays {
+            // Подписи нужны, только когда дисплеев несколько
+            if displays.len() > 1 {
+                let name = match &display.connector {
+                    Some(connector) if *connector != display.name => format!("{} ({})", display.name, connector),
+                    _ => display.name.clone(),
+                };
+                let label = Label::new(Some(&name));
+                label.set_halign(gtk4::Align::Start);
+                label.set_css_classes(&["brightness-display-name"]);
+                self.container.append(&label);
+            }
+
+            let slider_box = GtkBox::new(Orientation::Horizontal, 8);
+
+            let min_icon = Label::new(Some("󰃚")); // Минимум (brightness_1)
+            min_icon.set_css_classes(&["brightness-slider-icon"]);
+
+            let slider = Scale::with_range(Orientation::Horizontal, 0.0, 100.0, 1.0);
+            slider.set_hexpand(true);
+            slider.set_css_classes(&["brightness-slider"]);
+            slider.set_draw_value(false);
+
+            let max_icon = Label::new(Some("󰃠")); // Максимум (brightness_7)
+            max_icon.set_css_classes(&["brightness-slider-icon"]);
+
+            slider_box.append(&min_icon);
+            slider_box.append(&slider);
+            slider_box.append(&max_icon);
+            self.container.append(&slider_box);
+
+            // Обработчик изменения слайдера
+            {
+                let brightness_service = brightness_service.clone();
+                let updating = self.updating.clone();
+                let id = display.id.clone();
+                let internal = display.internal;
+                slider.connect_value_changed(move |slider| {
+                    if updating.get() {
+                        return;
+                    }
+                    let value = slider.value() as u32;
+                    if let Err(e) = brightness_service.set_display_brightness(&id, value) {
+                        eprintln!("[Brightness] ✗ {}", e);
+                        return;
+                    }
+                    // Внешние мониторы не присылают событий - обновляем панели сами
+                    if !internal {
+                        get_shared_state().update_brightness_displays(brightness_service.list_displays());
+                    }
+                });
+            }
+
+            rows.push((display.id.clone(), slider));
+        }
+
+        *self.rows.borrow_mut() = rows;
+    }
+}
+
+/// Дисплей монитора панели; если у монитора нет регулировки - основной дисплей
+fn display_for_monitor<'a>(displays: &'a [BrightnessDisplay], monitor_name: &str) -> Option<&'a BrightnessDisplay> {
+    displays
+        .iter()
+        .find(|d| d.connector.as_deref() == Some(monitor_name))
+        .or_else(|| displays.iter().find(|d| d.internal))
+        .or_else(|| displays.first())
+}
+
+/// Иконка в зависимости от уровня яркости
+pub fn brightness_icon(brightness: u32) -> &'static str {
+    match brightness {
+        0 => "󰃚",           // nf-md-brightness_1 (минимум)
+        1..=14 => "󰃛",      // nf-md-brightness_2
+        15..=28 => "󰃜",     // nf-md-brightness_3
+        29..=42 => "󰃝",     // nf-md-brightness_4
+        43..=57 => "󰃞",     // nf-md-brightness_5
+        58..=71 => "󰃟",     // nf-md-brightness_6
+        _ => "󰃠",           // nf-md-brightness_7 (максимум)
+    }
+}
@@ -0,0 +1,471 @@
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+use crate::domain::mpris_service::MprisService;
+use crate::domain::models::{MediaPlayer, PlaybackStatus};
+
+/// Размер обложки альбома в popover
+const ART_SIZE: i32 = 96;
+
+/// Перемотка отправляется плееру, когда ползунок не двигали столько времени
+const SEEK_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Виджет медиаплеера (MPRIS)
+///
+/// Элементы создаются один раз и обновляются на месте, чтобы открытый popover
+/// не закрывался при смене трека или статуса.
+pub struct MediaWidget {
+    inner: Rc<MediaView>,
+}
+
+struct MediaView {
+    container: gtk4::Box,
+    status_icon: gtk4::Label,
+    label: gtk4::Label,
+    popover: gtk4::Popover,
+    art: gtk4::Image,
+    /// URL обложки, которая загружена (или загружается) сейчас
+    art_url: Rc<RefCell<Option<String>>>,
+    title_label: gtk4::Label,
+    artist_label: gtk4::Label,
+    album_label: gtk4::Label,
+    position: gtk4::Scale,
+    /// Отложенная перемотка, пока ползунок перетаскивают
+    pending_seek: RefCell<Option<glib::SourceId>>,
+    position_label: gtk4::Label,
+    length_label: gtk4::Label,
+    prev_button: gtk4::Button,
+    play_button: gtk4::Button,
+    next_button: gtk4::Button,
+    players_box: gtk4::Box,
+    /// (bus_name, identity) плееров, для которых созданы кнопки выбора
+    players: RefCell<Vec<(String, String)>>,
+    service: Arc<dyn MprisService + Send + Sync>,
+}
+
+impl MediaWidget {
+    pub fn new(service: Arc<dyn MprisService + Send + Sync>) -> Self {
+        let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        container.add_css_class("media-widget");
+
+        let button = gtk4::Button::new();
+        button.add_css_class("media-button");
+        button.set_has_frame(false);
+
+        let button_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+        let status_icon = gtk4::Label::new(Some("󰎆"));
+        status_icon.add_css_class("media-status-icon");
+        button_box.append(&status_icon);
+
+        let label = gtk4::Label::new(None);
+        label.add_css_class("media-label");
+        label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        label.set_max_width_chars(30);
+        button_box.append(&label);
+
+        button.set_child(Some(&button_box));
+
+        // Левый клик - воспроизведение/пауза
+        {
+            let service = Arc::clone(&service);
+            button.connect_clicked(move |_| {
+                if let Err(e) = service.play_pause() {
+                    eprintln!("[Media] ✗ {}", e);
+                }
+            });
+        }
+        container.append(&button);
+
+        // Правый клик - подробности, перемотка и выбор плеера
+        let popover = gtk4::Popover::new();
+        popover.set_parent(&button);
+        popover.set_position(gtk4::PositionType::Bottom);
+
+        let popover_box = gtk4::Box::new(gtk4::Orientation::Vertical, 8);
+        popover_box.add_css_class("media-popover");
+
+        let header = gtk4::Box::new(gtk4::Orientation::Horizontal, 12);
+
+        let art = gtk4::Image::new();
+        art.add_css_class("media-art");
+        art.set_pixel_size(ART_SIZE);
+        header.append(&art);
+
+        let text_box = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+        text_box.set_valign(gtk4::Align::Center);
+        text_box.set_hexpand(true);
+
+        let title_label = Self::create_text_label("media-title");
+        let artist_label = Self::create_text_label("media-artist");
+        let album_label = Self::create_text_label("media-album");
+        text_box.append(&title_label);
+        text_box.append(&artist_label);
+        text_box.append(&album_label);
+        header.append(&text_box);
+        popover_box.append(&header);
+
+        // Позиция трека
+        let position = gtk4::Scale::with_range(gtk4::Orientation::Horizontal, 0.0, 1.0, 1.0);
+        position.add_css_class("media-position");
+        position.set_draw_value(false);
+        position.set_width_request(260);
+        popover_box.append(&position);
+
+        let time_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+        let position_label = gtk4::Label::new(Some("0:00"));
+        position_label.add_css_class("media-time");
+        position_label.set_hexpand(true);
+        position_label.set_halign(gtk4::Align::Start);
+        let length_label = gtk4::Label::new(Some("0:00"));
+        length_label.add_css_class("media-time");
+        length_label.set_halign(gtk4::Align::End);
+        time_box.append(&position_label);
+        time_box.append(&length_label);
+        popover_box.append(&time_box);
+
+        // Кнопки управления
+        let controls = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+        controls.add_css_class("media-controls");
+        controls.set_halign(gtk4::Align::Center);
+
+        let prev_button = Self::create_control_button("󰒮");
+        let play_button = Self::create_control_button("󰐊");
+        play_button.add_css_class("media-play-button");
+        let next_button = Self::create_control_button("󰒭");
+
+        {
+            let service = Arc::clone(&service);
+            prev_button.connect_clicked(move |_| {
+                if let Err(e) = service.previous() {
+                    eprintln!("[Media] ✗ {}", e);
+                }
+            });
+        }
+        {
+            let service = Arc::clone(&service);
+            play_button.connect_clicked(move |_| {
+                if let Err(e) = service.play_pause() {
+                    eprintln!("[Media] ✗ {}", e);
+                }
+            });
+        }
+        {
+            let service = Arc::clone(&service);
+            next_button.connect_clicked(move |_| {
+                if let Err(e) = service.next() {
+                    eprintln!("[Media] ✗ {}", e);
+                }
+            });
+        }
+
+        controls.append(&prev_button);
+        controls.append(&play_button);
+        controls.append(&next_button);
+        popover_box.append(&controls);
+
+        // Выбор плеера (показывается, если плееров больше одного)
+        let players_box = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+        players_box.add_css_class("media-players");
+        players_box.set_visible(false);
+        popover_box.append(&players_box);
+
+        popover.set_child(Some(&popover_box));
+
+        let gesture = gtk4::GestureClick::new();
+        gesture.set_button(3);
+        let popover_weak = popover.downgrade();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Some(p) = popover_weak.upgrade() {
+                p.popup();
+            }
+        });
+        button.add_controller(gesture);
+
+        let inner = Rc::new(MediaView {
+            container,
+            status_icon,
+            label,
+            popover,
+            art,
+            art_url: Rc::new(RefCell::new(None)),
+            title_label,
+            artist_label,
+            album_label,
+            position,
+            pending_seek: RefCell::new(None),
+            position_label,
+            length_label,
+            prev_button,
+            play_button,
+            next_button,
+            players_box,
+            players: RefCell::new(Vec::new()),
+            service,
+        });
+
+        // Перемотка: плееру уходит только итоговое положение ползунка
+        let weak = Rc::downgrade(&inner);
+        inner.position.connect_change_value(move |_, _, value| {
+            if let Some(view) = weak.upgrade() {
+                view.schedule_seek(value);
+            }
+            glib::Propagation::Proceed
+        });
+
+        // Position не сигналится плеерами, поэтому пока popover открыт,
+        // обновляем позицию раз в секунду
+        let weak = Rc::downgrade(&inner);
+        glib::timeout_add_seconds_local(1, move || {
+            let Some(view) = weak.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if view.popover.is_visible() {
+                if let Some(player) = view.service.get_active_player() {
+                    view.update_position(&player);
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+
+        Self { inner }
+    }
+
+    pub fn widget(&self) -> &gtk4::Box {
+        &self.inner.container
+    }
+
+    pub fn update(&self) {
+        self.inner.update();
+    }
+
+    fn create_text_label(css_class: &str) -> gtk4::Label {
+        let label = gtk4::Label::new(None);
+        label.add_css_class(css_class);
+        label.set_halign(gtk4::Align::Start);
+        label.set_xalign(0.0);
+        label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        label.set_max_width_chars(28);
+        label
+    }
+
+    fn create_control_button(icon: &str) -> gtk4::Button {
+        let button = gtk4::Button::with_label(icon);
+        button.add_css_class("media-control-button");
+        button.set_has_frame(false);
+        button
+    }
+}
+
+impl MediaView {
+    fn update(self: &Rc<Self>) {
+        let players = self.service.list_players();
+        let Some(player) = self.service.get_active_player() else {
+            self.container.set_visible(false);
+            self.popover.popdown();
+            return;
+        };
+        self.container.set_visible(true);
+
+        self.status_icon.set_text(status_icon(player.status));
+        self.label.set_text(&bar_text(&player));
+        self.container.set_tooltip_text(Some(&player.identity));
+
+        if player.status == PlaybackStatus::Playing {
+            self.container.add_css_class("media-playing");
+        } else {
+            self.container.remove_css_class("media-playing");
+        }
+
+        self.title_label.set_text(player.title.as_deref().unwrap_or(&player.identity));
+        Self::set_optional_text(&self.artist_label, player.artist.as_deref());
+        Self::set_optional_text(&self.album_label, player.album.as_deref());
+
+        self.play_button.set_label(if player.status == PlaybackStatus::Playing { "󰏤" } else { "󰐊" });
+        self.prev_button.set_sensitive(player.can_go_previous);
+        self.next_button.set_sensitive(player.can_go_next);
+
+        self.update_art(player.art_url.as_deref());
+        self.update_position(&player);
+        self.update_players(&players, &player.bus_name);
+    }
+
+    fn set_optional_text(label: &gtk4::Label, text: Option<&str>) {
+        match text {
+            Some(text) if !text.is_empty() => {
+                label.set_text(text);
+                label.set_visible(true);
+            }
+            _ => label.set_visible(false),
+        }
+    }
+
+    fn update_position(&self, player: &MediaPlayer) {
+        let length = player.length_us.unwrap_or(0).max(0);
+        let seekable = player.can_seek && length > 0;
+
+        self.length_label.set_text(&if length > 0 { format_time(length) } else { "--:--".to_string() });
+
+        // Ползунок перетаскивают - не сбиваем его позицией плеера
+        if self.pending_seek.borrow().is_some() {
+            return;
+        }
+
+        self.position.set_sensitive(seekable);
+        self.position.set_range(0.0, (length as f64 / 1_000_000.0).max(1.0));
+        self.position.set_value(player.position_us as f64 / 1_000_000.0);
+
+        self.position_label.set_text(&format_time(player.position_us));
+    }
+
+    /// Откладывает перемотку до окончания перетаскивания ползунка
+    fn schedule_seek(self: &Rc<Self>, value: f64) {
+        let position_us = (value.clamp(0.0, self.position.adjustment().upper()) * 1_000_000.0) as i64;
+        self.position_label.set_text(&format_time(position_us));
+
+        if let Some(source) = self.pending_seek.borrow_mut().take() {
+            source.remove();
+        }
+
+        let weak = Rc::downgrade(self);
+        let source = glib::timeout_add_local_once(SEEK_DELAY, move || {
+            let Some(view) = weak.upgrade() else {
+                return;
+            };
+            view.pending_seek.borrow_mut().take();
+            if let Err(e) = view.service.set_position(position_us) {
+                eprintln!("[Media] ✗ {}", e);
+            }
+        });
+        *self.pending_seek.borrow_mut() = Some(source);
+    }
+
+    /// Загружает обложку, если URL изменился
+    fn update_art(&self, url: Option<&str>) {
+        if self.art_url.borrow().as_deref() == url {
+            return;
+        }
+        *self.art_url.borrow_mut() = url.map(str::to_string);
+
+        self.art.set_icon_name(Some("audio-x-generic"));
+        let Some(url) = url else {
+            return;
+        };
+
+        let file = gio::File::for_uri(url);
+        let art = self.art.downgrade();
+        let expected = url.to_string();
+        let art_url = Rc::downgrade(&self.art_url);
+        file.load_bytes_async(None::<&gio::Cancellable>, move |result| {
+            let (Some(art), Some(art_url)) = (art.upgrade(), art_url.upgrade()) else {
+                return;
+            };
+            // Пока грузилась обложка, трек уже сменился
+            if art_url.borrow().as_deref() != Some(expected.as_str()) {
+                return;
+            }
+
+            let bytes = match result {
+                Ok((bytes, _)) => bytes,
+                Err(e) => {
+                    eprintln!("[Media] ✗ Failed to load album art {}: {}", expected, e);
+                    return;
+                }
+            };
+
+            let stream = gio::MemoryInputStream::from_bytes(&bytes);
+            match gtk4::gdk_pixbuf::Pixbuf::from_stream_at_scale(
+                &stream,
+                ART_SIZE,
+                ART_SIZE,
+                true,
+                None::<&gio::Cancellable>,
+            ) {
+                Ok(pixbuf) => {
+                    let texture = gtk4::gdk::Texture::for_pixbuf(&pixbuf);
+                    art.set_paintable(Some(&texture));
+                }
+                Err(e) => eprintln!("[Media] ✗ Failed to decode album art {}: {}", expected, e),
+            }
+        });
+    }
+
+    /// Перестраивает список плееров только при его изменении
+    fn update_players(self: &Rc<Self>, players: &[MediaPlayer], active: &str) {
+        let names: Vec<(String, String)> = players
+            .iter()
+            .map(|p| (p.bus_name.clone(), p.identity.clone()))
+            .collect();
+
+        if *self.players.borrow() != names {
+            while let Some(child) = self.players_box.first_child() {
+                self.players_box.remove(&child);
+            }
+
+            for (bus_name, identity) in &names {
+                let button = gtk4::Button::with_label(identity);
+                button.add_css_class("media-player-item");
+                button.set_has_frame(false);
+                button.set_widget_name(bus_name);
+
+                let weak: Weak<MediaView> = Rc::downgrade(self);
+                let bus_name = bus_name.clone();
+                button.connect_clicked(move |_| {
+                    let Some(view) = weak.upgrade() else {
+                        return;
+                    };
+                    view.service.set_active_player(&bus_name);
+                    view.update();
+                });
+                self.players_box.append(&button);
+            }
+
+            *self.players.borrow_mut() = names;
+        }
+
+        self.players_box.set_visible(players.len() > 1);
+
+        let mut child = self.players_box.first_child();
+        while let Some(widget) = child {
+            if widget.widget_name() == active {
+                widget.add_css_class("active");
+            } else {
+                widget.remove_css_class("active");
+            }
+            child = widget.next_sibling();
+        }
+    }
+}
+
+/// Иконка статуса воспроизведения для панели
+fn status_icon(status: PlaybackStatus) -> &'static str {
+    match status {
+        PlaybackStatus::Playing => "󰐊",
+        PlaybackStatus::Paused => "󰏤",
+        PlaybackStatus::Stopped => "󰓛",
+    }
+}
+
+/// "Исполнитель - Название", либо то, что из этого известно
+fn bar_text(player: &MediaPlayer) -> String {
+    let artist = player.artist.as_deref().filter(|s| !s.is_empty());
+    let title = player.title.as_deref().filter(|s| !s.is_empty());
+
+    match (artist, title) {
+        (Some(artist), Some(title)) => format!("{} - {}", artist, title),
+        (None, Some(title)) => title.to_string(),
+        _ => player.identity.clone(),
+    }
+}
+
+/// Форматирует микросекунды как m:ss или h:mm:ss
+fn format_time(us: i64) -> String {
+    let total = (us.max(0) / 1_000_000) as u64;
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
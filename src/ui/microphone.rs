@@ -0,0 +1,185 @@
+use gtk4::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use crate::domain::volume_service::VolumeService;
+use crate::domain::models::{AudioDeviceKind, MicrophoneInfo};
+use crate::ui::volume::DevicePicker;
+
+/// Виджет микрофона
+///
+/// Элементы создаются один раз и обновляются на месте, чтобы открытый popover
+/// не закрывался при изменении громкости.
+pub struct MicrophoneWidget {
+    container: gtk4::Box,
+    button: gtk4::Button,
+    icon: gtk4::Label,
+    label: gtk4::Label,
+    in_use_indicator: gtk4::Label,
+    not_found: gtk4::Label,
+    slider: gtk4::Scale,
+    /// Значение слайдера меняется из update(), а не пользователем
+    updating_slider: Rc<Cell<bool>>,
+    picker: DevicePicker,
+    service: Arc<dyn VolumeService + Send + Sync>,
+}
+
+impl MicrophoneWidget {
+    pub fn new(service: Arc<dyn VolumeService + Send + Sync>) -> Self {
+        let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        container.add_css_class("microphone-widget");
+
+        let button = gtk4::Button::new();
+        button.add_css_class("microphone-button");
+        button.set_has_frame(false);
+
+        let icon = gtk4::Label::new(Some("󰍬"));
+        icon.add_css_class("microphone-icon");
+        button.set_child(Some(&icon));
+
+        // Левый клик - переключение mute
+        {
+            let service = Arc::clone(&service);
+            button.connect_clicked(move |_| {
+                if let Err(e) = service.toggle_microphone_mute() {
+                    eprintln!("Failed to toggle microphone mute: {}", e);
+                }
+            });
+        }
+        container.append(&button);
+
+        let label = gtk4::Label::new(None);
+        label.add_css_class("microphone-percentage");
+        container.append(&label);
+
+        let in_use_indicator = gtk4::Label::new(Some("●"));
+        in_use_indicator.add_css_class("microphone-in-use-indicator");
+        in_use_indicator.set_visible(false);
+        container.append(&in_use_indicator);
+
+        // Микрофон не найден
+        let not_found = gtk4::Label::new(Some("󰍭"));
+        not_found.add_css_class("microphone-not-found");
+        container.append(&not_found);
+
+        // Правый клик - громкость и выбор устройства ввода
+        let updating_slider = Rc::new(Cell::new(false));
+        let slider = Self::create_slider(&service, &updating_slider);
+        let picker = DevicePicker::new(Arc::clone(&service), AudioDeviceKind::Source);
+
+        let popover = gtk4::Popover::new();
+        popover.set_parent(&button);
+        popover.set_position(gtk4::PositionType::Bottom);
+
+        let popover_box = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        popover_box.add_css_class("volume-popover");
+        popover_box.append(&slider);
+        popover_box.append(picker.widget());
+        popover.set_child(Some(&popover_box));
+
+        let gesture = gtk4::GestureClick::new();
+        gesture.set_button(3);
+        let popover_weak = popover.downgrade();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Some(p) = popover_weak.upgrade() {
+                p.popup();
+            }
+        });
+        button.add_controller(gesture);
+
+        Self {
+            container,
+            button,
+            icon,
+            label,
+            in_use_indicator,
+            not_found,
+            slider,
+            updating_slider,
+            picker,
+            service,
+        }
+    }
+
+    pub fn widget(&self) -> &gtk4::Box {
+        &self.container
+    }
+
+    pub fn update(&self) {
+        let info = self.service.get_microphone_info();
+
+        self.button.set_visible(info.is_some());
+        self.label.set_visible(info.is_some());
+        self.not_found.set_visible(info.is_none());
+
+        let Some(info) = info else {
+            self.in_use_indicator.set_visible(false);
+            self.container.remove_css_class("microphone-in-use");
+            self.container.set_tooltip_text(Some("No microphone"));
+            return;
+        };
+
+        self.icon.set_text(if info.muted { "󰍭" } else { "󰍬" });
+        if info.muted {
+            self.icon.add_css_class("microphone-muted");
+        } else {
+            self.icon.remove_css_class("microphone-muted");
+        }
+
+        self.label.set_text(&format!("{}%", info.volume));
+
+        // Индикатор активного захвата
+        self.in_use_indicator.set_visible(info.in_use);
+        if info.in_use {
+            self.container.add_css_class("microphone-in-use");
+        } else {
+            self.container.remove_css_class("microphone-in-use");
+        }
+
+        if self.slider.value() as u8 != info.volume {
+            self.updating_slider.set(true);
+            self.slider.set_value(info.volume as f64);
+            self.updating_slider.set(false);
+        }
+
+        self.picker.update();
+        self.container.set_tooltip_text(Some(&Self::create_tooltip(&info)));
+    }
+
+    /// Создаёт вертикальный слайдер громкости микрофона
+    fn create_slider(
+        service: &Arc<dyn VolumeService + Send + Sync>,
+        updating: &Rc<Cell<bool>>,
+    ) -> gtk4::Scale {
+        let adjustment = gtk4::Adjustment::new(0.0, 0.0, 100.0, 1.0, 10.0, 0.0);
+
+        let slider = gtk4::Scale::new(gtk4::Orientation::Vertical, Some(&adjustment));
+        slider.set_inverted(true);
+        slider.set_draw_value(true);
+        slider.set_value_pos(gtk4::PositionType::Bottom);
+        slider.set_height_request(120);
+        slider.add_css_class("volume-slider");
+
+        let service = Arc::clone(service);
+        let updating = updating.clone();
+        slider.connect_value_changed(move |scale| {
+            if updating.get() {
+                return;
+            }
+            if let Err(e) = service.set_microphone_volume(scale.value() as u8) {
+                eprintln!("Failed to set microphone volume: {}", e);
+            }
+        });
+
+        slider
+    }
+
+    /// Создаёт текст для tooltip
+    fn create_tooltip(info: &MicrophoneInfo) -> String {
+        let status = if info.muted { "Muted" } else { "Active" };
+        let usage = if info.in_use { "\nIn use by an application" } else { "" };
+
+        format!("Microphone: {}%\nStatus: {}{}\n\nLeft click: Toggle mute\nRight click: Volume and input device",
+                info.volume, status, usage)
+    }
+}
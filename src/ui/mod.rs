@@ -1,18 +1,23 @@
 pub mod active_window;
+pub mod app_icon;
 pub mod bar;
 pub mod calendar;
 pub mod datetime;
+pub mod media;
 pub mod menu;
+pub mod microphone;
 pub mod system_tray;
 pub mod workspaces;
 pub mod battery;
 pub mod volume;
-pub mod volume_osd;
+pub mod osd;
 pub mod notifications;
 pub mod keyboard_layout;
 pub mod system_resources;
 pub mod network;
+pub mod night_light;
 pub mod brightness;
 pub mod settings;
 pub mod submap;
-
+pub mod taskbar;
+pub mod theme;
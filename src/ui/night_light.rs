@@ -0,0 +1,176 @@
+use gtk4::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use crate::config::{get_config, save_config, NightLightSchedule};
+use crate::domain::night_light_service::NightLightService;
+use crate::shared_state::get_shared_state;
+
+/// Диапазон слайдера температуры (K)
+const SLIDER_MIN: f64 = 1000.0;
+const SLIDER_MAX: f64 = 6500.0;
+
+/// Виджет ночного режима
+///
+/// Левый клик - включение/выключение, правый - температура и расписание.
+/// Состояние берётся из SharedState, поэтому все панели показывают одно и то же.
+pub struct NightLightWidget {
+    button: gtk4::Button,
+    icon: gtk4::Label,
+    slider: gtk4::Scale,
+    temperature_label: gtk4::Label,
+    schedule_label: gtk4::Label,
+    /// Значение слайдера меняется из update(), а не пользователем
+    updating_slider: Rc<Cell<bool>>,
+}
+
+impl NightLightWidget {
+    pub fn new(service: Arc<dyn NightLightService + Send + Sync>) -> Self {
+        let button = gtk4::Button::new();
+        button.add_css_class("night-light-widget");
+        button.set_has_frame(false);
+
+        let icon = gtk4::Label::new(Some("󰖔"));
+        icon.add_css_class("night-light-icon");
+        button.set_child(Some(&icon));
+
+        {
+            let service = Arc::clone(&service);
+            button.connect_clicked(move |_| {
+                let enabled = service.get_state().enabled;
+                if let Err(e) = service.set_enabled(!enabled) {
+                    eprintln!("[NightLight] ✗ {}", e);
+                }
+                get_shared_state().update_night_light(Some(service.get_state()));
+            });
+        }
+
+        // Правый клик - температура
+        let popover_box = gtk4::Box::new(gtk4::Orientation::Vertical, 8);
+        popover_box.add_css_class("night-light-popover");
+
+        let title = gtk4::Label::new(Some("Night Light"));
+        title.add_css_class("night-light-title");
+        popover_box.append(&title);
+
+        let slider_box = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
+        let slider = gtk4::Scale::with_range(gtk4::Orientation::Horizontal, SLIDER_MIN, SLIDER_MAX, 100.0);
+        slider.set_hexpand(true);
+        slider.set_draw_value(false);
+        slider.add_css_class("night-light-slider");
+        let temperature_label = gtk4::Label::new(None);
+        temperature_label.add_css_class("night-light-temperature");
+        slider_box.append(&slider);
+        slider_box.append(&temperature_label);
+        popover_box.append(&slider_box);
+
+        let schedule_label = gtk4::Label::new(None);
+        schedule_label.set_halign(gtk4::Align::Start);
+        schedule_label.add_css_class("night-light-schedule");
+        popover_box.append(&schedule_label);
+
+        let updating_slider = Rc::new(Cell::new(false));
+        {
+            let service = Arc::clone(&service);
+            let updating = updating_slider.clone();
+            let temperature_label = temperature_label.clone();
+            slider.connect_value_changed(move |slider| {
+                let kelvin = slider.value() as u32;
+                temperature_label.set_text(&format!("{}K", kelvin));
+                if updating.get() {
+                    return;
+                }
+                if let Err(e) = service.set_temperature(kelvin) {
+                    eprintln!("[NightLight] ✗ {}", e);
+                }
+                get_shared_state().update_night_light(Some(service.get_state()));
+            });
+        }
+
+        let popover = gtk4::Popover::new();
+        popover.set_parent(&button);
+        popover.set_child(Some(&popover_box));
+
+        // Температуру сохраняем в конфиг при закрытии, а не на каждое движение слайдера
+        {
+            let service = Arc::clone(&service);
+            popover.connect_closed(move |_| {
+                let temperature = service.get_state().temperature;
+                let changed = {
+                    let mut config = get_config().write().unwrap();
+                    let changed = config.night_light.temperature != temperature;
+                    config.night_light.temperature = temperature;
+                    changed
+                };
+                if changed {
+                    let _ = save_config();
+                }
+            });
+        }
+
+        let gesture = gtk4::GestureClick::new();
+        gesture.set_button(3);
+        let popover_weak = popover.downgrade();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Some(p) = popover_weak.upgrade() {
+                p.popup();
+            }
+        });
+        button.add_controller(gesture);
+
+        Self {
+            button,
+            icon,
+            slider,
+            temperature_label,
+            schedule_label,
+            updating_slider,
+        }
+    }
+
+    pub fn widget(&self) -> &gtk4::Button {
+        &self.button
+    }
+
+    pub fn update(&self) {
+        let Some(state) = get_shared_state().get_night_light() else {
+            return;
+        };
+
+        self.icon.set_text(if state.enabled { "󰖔" } else { "󰖙" });
+        if state.enabled {
+            self.button.add_css_class("night-light-enabled");
+        } else {
+            self.button.remove_css_class("night-light-enabled");
+        }
+
+        if self.slider.value() as u32 != state.temperature {
+            self.updating_slider.set(true);
+            self.slider.set_value(state.temperature as f64);
+            self.updating_slider.set(false);
+        }
+        self.temperature_label.set_text(&format!("{}K", state.temperature));
+
+        let config = get_config().read().unwrap().night_light.clone();
+        let has_location = config.latitude.is_some() && config.longitude.is_some();
+        let schedule_text = match (config.schedule, state.next_change) {
+            (NightLightSchedule::Manual, _) => "Schedule: manual".to_string(),
+            (_, Some(next)) => format!(
+                "{} at {}",
+                if state.enabled { "Turns off" } else { "Turns on" },
+                next.format("%H:%M")
+            ),
+            (NightLightSchedule::Fixed, None) => "Schedule: invalid start/end".to_string(),
+            (NightLightSchedule::Sun, None) if has_location => "Schedule: no sunset today".to_string(),
+            (NightLightSchedule::Sun, None) => "Schedule: set latitude/longitude".to_string(),
+        };
+        self.schedule_label.set_text(&schedule_text);
+
+        let status = if state.enabled {
+            format!("Night light: on ({}K)", state.temperature)
+        } else {
+            "Night light: off".to_string()
+        };
+        self.button.set_tooltip_text(Some(&format!("{}\n{}", status, schedule_text)));
+    }
+}
@@ -1,10 +1,20 @@
 use gtk4::prelude::*;
-use gtk4::glib;
+use gtk4::{gio, glib};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::{Rc, Weak};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use crate::domain::notification_service::NotificationService;
-use crate::domain::models::{Notification, NotificationUrgency};
+use crate::domain::models::{Notification, NotificationFilter, NotificationUrgency};
  use crate::shared_state::get_shared_state;
 
+/// Поиск запускается, когда ввод в строке поиска затих на это время
+const SEARCH_DELAY: Duration = Duration::from_millis(300);
+
+/// Картинки уведомлений, которые ещё не загружены: (id, виджет иконки)
+type PendingImages = Vec<(u32, glib::WeakRef<gtk4::Image>)>;
+
 pub struct NotificationWidget {
     button: gtk4::Button,
     service: Arc<dyn NotificationService + Send + Sync>,
@@ -30,6 +40,17 @@ impl NotificationWidget {
             }
         });
 
+        // Правый клик - включить/выключить «Не беспокоить»
+        let gesture = gtk4::GestureClick::new();
+        gesture.set_button(3);
+        let service_dnd = Arc::clone(&service);
+        gesture.connect_released(move |_, _, _, _| {
+            if get_shared_state().is_notification_service_available() {
+                service_dnd.set_dnd_enabled(!service_dnd.is_dnd_enabled());
+            }
+        });
+        button.add_controller(gesture);
+
         Self {
             button,
             service,
@@ -42,40 +63,7 @@ impl NotificationWidget {
     }
 
     pub fn update(&self) {
-        let shared_state = get_shared_state();
-
-        // Создаём контейнер для иконки и счётчика
-        let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
-
-        if shared_state.is_notification_service_available() {
-            let count = shared_state.get_notification_count();
-
-            // Иконка колокольчика
-            let icon = gtk4::Label::new(Some("󰂚")); // Nerd Font: bell
-            icon.add_css_class("notification-icon");
-            container.append(&icon);
-
-            // Показываем количество уведомлений, если есть
-            if count > 0 {
-                let count_label = gtk4::Label::new(Some(&count.to_string()));
-                count_label.add_css_class("notification-count");
-                container.append(&count_label);
-            }
-
-            self.button.set_tooltip_text(Some(&format!("{} notifications", count)));
-            self.button.remove_css_class("notification-disconnected");
-        } else {
-            // Сервис не подключен - показываем иконку с перечёркиванием
-            let icon = gtk4::Label::new(Some("󰂛")); // Nerd Font: bell-off
-            icon.add_css_class("notification-icon");
-            icon.add_css_class("notification-icon-disconnected");
-            container.append(&icon);
-
-            self.button.set_tooltip_text(Some("Notification service not running"));
-            self.button.add_css_class("notification-disconnected");
-        }
-
-        self.button.set_child(Some(&container));
+        Self::update_button_badge(&self.button, &self.service);
     }
 
     fn update_button_badge(button: &gtk4::Button, _service: &Arc<dyn NotificationService + Send + Sync>) {
@@ -86,9 +74,10 @@ impl NotificationWidget {
 
         if shared_state.is_notification_service_available() {
             let count = shared_state.get_notification_count();
+            let dnd = shared_state.is_notification_dnd();
 
-            // Иконка колокольчика
-            let icon = gtk4::Label::new(Some("󰂚")); // Nerd Font: bell
+            // Иконка колокольчика (в режиме DND - спящий колокольчик)
+            let icon = gtk4::Label::new(Some(if dnd { "󰂠" } else { "󰂚" })); // Nerd Font: bell-sleep / bell
             icon.add_css_class("notification-icon");
             container.append(&icon);
 
@@ -99,8 +88,18 @@ impl NotificationWidget {
                 container.append(&count_label);
             }
 
-            button.set_tooltip_text(Some(&format!("{} notifications", count)));
+            let tooltip = if dnd {
+                format!("{} notifications\nDo not disturb is on", count)
+            } else {
+                format!("{} notifications", count)
+            };
+            button.set_tooltip_text(Some(&tooltip));
             button.remove_css_class("notification-disconnected");
+            if dnd {
+                button.add_css_class("notification-dnd");
+            } else {
+                button.remove_css_class("notification-dnd");
+            }
         } else {
             // Сервис не подключен
             let icon = gtk4::Label::new(Some("󰂛")); // Nerd Font: bell-off
@@ -128,8 +127,8 @@ impl NotificationWidget {
 
         let main_box = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
         main_box.add_css_class("notification-history");
-        main_box.set_width_request(350);
-        main_box.set_height_request(400);
+        main_box.set_width_request(380);
+        main_box.set_height_request(480);
 
         // Заголовок с кнопкой очистки
         let header = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
@@ -148,6 +147,39 @@ impl NotificationWidget {
         clear_button.set_can_focus(true);
         clear_button.set_receives_default(false);
 
+        // «Не беспокоить» - ручное включение; расписание задаётся в файле правил
+        let dnd_button = gtk4::ToggleButton::with_label("󰂠 DND");
+        dnd_button.add_css_class("notification-dnd-button");
+        dnd_button.set_has_frame(false);
+        dnd_button.set_tooltip_text(Some("Do not disturb"));
+        dnd_button.set_active(service.is_dnd_enabled());
+        let service_dnd = Arc::clone(&service);
+        dnd_button.connect_toggled(move |btn| {
+            service_dnd.set_dnd_enabled(btn.is_active());
+        });
+        header.append(&dnd_button);
+        header.append(&clear_button);
+
+        // Поиск и фильтры
+        let filters = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        filters.add_css_class("notification-filters");
+
+        let search = gtk4::SearchEntry::new();
+        search.set_placeholder_text(Some("Search"));
+        search.set_hexpand(true);
+        search.add_css_class("notification-search");
+        filters.append(&search);
+
+        let urgency = gtk4::DropDown::from_strings(&["Any", "Critical", "Normal", "Low"]);
+        urgency.set_tooltip_text(Some("Urgency"));
+        urgency.add_css_class("notification-filter");
+        filters.append(&urgency);
+
+        let period = gtk4::DropDown::from_strings(&["All time", "Today", "7 days"]);
+        period.set_tooltip_text(Some("Received"));
+        period.add_css_class("notification-filter");
+        filters.append(&period);
+
         // Скроллируемая область с уведомлениями
         let scrolled = gtk4::ScrolledWindow::new();
         scrolled.set_vexpand(true);
@@ -158,103 +190,68 @@ impl NotificationWidget {
 
         scrolled.set_child(Some(&notifications_box));
 
-        let button_weak = button.downgrade();
+        let view = Rc::new(HistoryView {
+            service: Arc::clone(&service),
+            list: notifications_box,
+            search: search.clone(),
+            urgency: urgency.clone(),
+            period: period.clone(),
+            collapsed: RefCell::new(HashSet::new()),
+            pending_refresh: RefCell::new(None),
+            button_weak: button.downgrade(),
+        });
 
-        // Функция обновления списка
-        let update_list = {
-            let service = Arc::clone(&service);
-            let notifications_box = notifications_box.clone();
-            let button_weak_update = button_weak.clone();
-            move || {
-                eprintln!("[UI] update_list() called");
-                // Очищаем список
-                while let Some(child) = notifications_box.first_child() {
-                    notifications_box.remove(&child);
-                }
+        // Первоначальное заполнение
+        view.refresh();
 
-                let history = service.get_history();
-                eprintln!("[UI] Loaded {} notifications from history", history.len());
-
-                if history.is_empty() {
-                    let empty_label = gtk4::Label::new(Some("No notifications"));
-                    empty_label.add_css_class("notification-empty");
-                    notifications_box.append(&empty_label);
-                } else {
-                    for (idx, notification) in history.iter().enumerate() {
-                        eprintln!("[UI] Creating item {} for notification id={}", idx, notification.id);
-                        let item = Self::create_notification_item(
-                            &notification,
-                            service.clone(),
-                            notifications_box.clone(),
-                            button_weak_update.clone()
-                        );
-                        notifications_box.append(&item);
-                    }
-                }
-                eprintln!("[UI] update_list() completed");
+        // Фильтры перезапрашивают историю у сервиса; поиск - после паузы в наборе
+        let view_weak = Rc::downgrade(&view);
+        search.connect_search_changed(move |_| {
+            if let Some(view) = view_weak.upgrade() {
+                view.schedule_refresh();
             }
-        };
-
-        // Первоначальное заполнение
-        update_list();
+        });
+        for dropdown in [&urgency, &period] {
+            let view_weak = Rc::downgrade(&view);
+            dropdown.connect_selected_notify(move |_| {
+                if let Some(view) = view_weak.upgrade() {
+                    view.refresh();
+                }
+            });
+        }
 
         // Clear All кнопка
-        let service_clear = Arc::clone(&service);
-        let notifications_box_clear = notifications_box.clone();
-        let button_weak_clear = button_weak.clone();
+        let view_clear = Rc::downgrade(&view);
         clear_button.connect_clicked(move |_| {
             eprintln!("[UI] Clear All button clicked");
-            service_clear.clear_history();
-            // Обновляем список
-            while let Some(child) = notifications_box_clear.first_child() {
-                notifications_box_clear.remove(&child);
-            }
-            let empty_label = gtk4::Label::new(Some("No notifications"));
-            empty_label.add_css_class("notification-empty");
-            notifications_box_clear.append(&empty_label);
-            eprintln!("[UI] All notifications cleared from UI");
-
-            // Обновляем счётчик
-            if let Some(btn) = button_weak_clear.upgrade() {
-                Self::update_button_badge(&btn, &service_clear);
+            if let Some(view) = view_clear.upgrade() {
+                view.service.clear_history();
+                view.refresh();
             }
         });
 
-        // Также добавляем GestureClick
-        let gesture_clear = gtk4::GestureClick::new();
-        let service_gesture_clear = Arc::clone(&service);
-        let notifications_box_gesture_clear = notifications_box.clone();
-        let button_weak_gesture_clear = button_weak.clone();
-        gesture_clear.connect_released(move |_, _, _, _| {
-            eprintln!("[UI] Gesture click on Clear All");
-            service_gesture_clear.clear_history();
-            while let Some(child) = notifications_box_gesture_clear.first_child() {
-                notifications_box_gesture_clear.remove(&child);
-            }
-            let empty_label = gtk4::Label::new(Some("No notifications"));
-            empty_label.add_css_class("notification-empty");
-            notifications_box_gesture_clear.append(&empty_label);
-
-            // Обновляем счётчик
-            if let Some(btn) = button_weak_gesture_clear.upgrade() {
-                Self::update_button_badge(&btn, &service_gesture_clear);
-            }
-        });
-        clear_button.add_controller(gesture_clear);
-
-        header.append(&clear_button);
         main_box.append(&header);
+        main_box.append(&filters);
         main_box.append(&scrolled);
 
+        // Обработчики держат view слабо - живёт, пока открыт popover;
+        // закрытый popover отвязываем от кнопки, иначе они копятся
+        popover.connect_closed(move |popover| {
+            let _view = &view;
+            let popover = popover.clone();
+            glib::idle_add_local_once(move || popover.unparent());
+        });
+
         popover.set_child(Some(&main_box));
         popover.popup();
     }
 
+    /// Строит элемент истории; картинка уведомления не загружается сразу,
+    /// а добавляется в `pending_images`
     fn create_notification_item(
         notification: &Notification,
-        service: Arc<dyn NotificationService + Send + Sync>,
-        notifications_box: gtk4::Box,
-        button_weak: glib::WeakRef<gtk4::Button>,
+        view: &Rc<HistoryView>,
+        pending_images: &mut PendingImages,
     ) -> gtk4::Box {
         eprintln!("[UI] create_notification_item() called for id={}", notification.id);
         let item = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
@@ -291,6 +288,11 @@ impl NotificationWidget {
             img
         };
 
+        // Картинка из уведомления (аватар, обложка и т.п.) заменит иконку после загрузки
+        if notification.has_image {
+            pending_images.push((notification.id, icon.downgrade()));
+        }
+
         icon_box.append(&icon);
         item.append(&icon_box);
 
@@ -341,55 +343,24 @@ impl NotificationWidget {
         close_button.set_size_request(32, 32);
 
         let notification_id = notification.id;
-        let item_weak = item.downgrade();
-        let service_clone = Arc::clone(&service);
-        let notifications_box_clone = notifications_box.clone();
 
         eprintln!("[UI] Registering click handlers for notification id={}", notification_id);
 
         // Используем connect_clicked
-        let button_weak_click = button_weak.clone();
-        let service_update = Arc::clone(&service);
+        let item_weak = item.downgrade();
+        let view_weak = Rc::downgrade(view);
         close_button.connect_clicked(move |_| {
             eprintln!("[UI] Close button clicked for notification id={}", notification_id);
-            service_clone.remove_notification(notification_id);
-            // Удаляем элемент из UI
-            if let Some(item_widget) = item_weak.upgrade() {
-                eprintln!("[UI] Removing widget from notifications_box");
-                notifications_box_clone.remove(&item_widget);
-                eprintln!("[UI] Widget removed successfully");
-            } else {
-                eprintln!("[UI] Failed to upgrade item_weak");
-            }
-
-            // Обновляем счётчик на кнопке
-            if let Some(btn) = button_weak_click.upgrade() {
-                Self::update_button_badge(&btn, &service_update);
-            }
+            Self::remove_item(&item_weak, &view_weak, notification_id);
         });
 
         // Также добавляем GestureClick как запасной вариант
         let gesture = gtk4::GestureClick::new();
-        let notification_id_gesture = notification.id;
         let item_weak_gesture = item.downgrade();
-        let service_gesture = Arc::clone(&service);
-        let notifications_box_gesture = notifications_box.clone();
-        let button_weak_gesture = button_weak.clone();
-        let service_update_gesture = Arc::clone(&service);
-
+        let view_weak_gesture = Rc::downgrade(view);
         gesture.connect_released(move |_, _, _, _| {
-            eprintln!("[UI] Gesture click detected for notification id={}", notification_id_gesture);
-            service_gesture.remove_notification(notification_id_gesture);
-            if let Some(item_widget) = item_weak_gesture.upgrade() {
-                eprintln!("[UI] Removing widget via gesture");
-                notifications_box_gesture.remove(&item_widget);
-                eprintln!("[UI] Widget removed via gesture");
-            }
-
-            // Обновляем счётчик на кнопке
-            if let Some(btn) = button_weak_gesture.upgrade() {
-                Self::update_button_badge(&btn, &service_update_gesture);
-            }
+            eprintln!("[UI] Gesture click detected for notification id={}", notification_id);
+            Self::remove_item(&item_weak_gesture, &view_weak_gesture, notification_id);
         });
         close_button.add_controller(gesture);
 
@@ -405,9 +376,30 @@ impl NotificationWidget {
         item
     }
 
-    fn format_time(timestamp: &std::time::SystemTime) -> String {
-        use std::time::SystemTime;
+    /// Удаляет уведомление и перестраивает список (группа могла опустеть)
+    fn remove_item(item_weak: &glib::WeakRef<gtk4::Box>, view_weak: &Weak<HistoryView>, id: u32) {
+        // Обработчики clicked и GestureClick могут сработать оба - второй ничего не делает
+        let Some(item) = item_weak.upgrade() else {
+            return;
+        };
+        if item.parent().is_none() {
+            return;
+        }
+        if let Some(view) = view_weak.upgrade() {
+            view.service.remove_notification(id);
+            view.refresh();
+        }
+    }
+
+    /// Загружает PNG, полученный от сервиса уведомлений
+    fn texture_from_png(data: &[u8]) -> Option<gtk4::gdk::Texture> {
+        let loader = gtk4::gdk_pixbuf::PixbufLoader::new();
+        loader.write(data).ok()?;
+        loader.close().ok()?;
+        Some(gtk4::gdk::Texture::for_pixbuf(&loader.pixbuf()?))
+    }
 
+    fn format_time(timestamp: &std::time::SystemTime) -> String {
         let duration = SystemTime::now()
             .duration_since(*timestamp)
             .unwrap_or_default();
@@ -426,3 +418,209 @@ impl NotificationWidget {
     }
 }
 
+
+/// Открытая история: фильтры, список и свёрнутые группы
+struct HistoryView {
+    service: Arc<dyn NotificationService + Send + Sync>,
+    list: gtk4::Box,
+    search: gtk4::SearchEntry,
+    urgency: gtk4::DropDown,
+    period: gtk4::DropDown,
+    /// Приложения, группы которых свёрнуты
+    collapsed: RefCell<HashSet<String>>,
+    /// Отложенное обновление после ввода в строку поиска
+    pending_refresh: RefCell<Option<glib::SourceId>>,
+    button_weak: glib::WeakRef<gtk4::Button>,
+}
+
+impl HistoryView {
+    fn filter(&self) -> NotificationFilter {
+        let urgency = match self.urgency.selected() {
+            1 => Some(NotificationUrgency::Critical),
+            2 => Some(NotificationUrgency::Normal),
+            3 => Some(NotificationUrgency::Low),
+            _ => None,
+        };
+
+        let since = match self.period.selected() {
+            1 => chrono::Local::now()
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+                .map(|midnight| UNIX_EPOCH + Duration::from_secs(midnight.timestamp().max(0) as u64)),
+            2 => SystemTime::now().checked_sub(Duration::from_secs(7 * 24 * 60 * 60)),
+            _ => None,
+        };
+
+        NotificationFilter {
+            query: self.search.text().trim().to_string(),
+            urgency,
+            since,
+        }
+    }
+
+    /// Обновляет список, когда ввод в строку поиска затих
+    fn schedule_refresh(self: &Rc<Self>) {
+        if let Some(source) = self.pending_refresh.borrow_mut().take() {
+            source.remove();
+        }
+
+        let view_weak = Rc::downgrade(self);
+        let source = glib::timeout_add_local_once(SEARCH_DELAY, move || {
+            if let Some(view) = view_weak.upgrade() {
+                view.pending_refresh.borrow_mut().take();
+                view.refresh();
+            }
+        });
+        *self.pending_refresh.borrow_mut() = Some(source);
+    }
+
+    /// Загружает картинки уведомлений вне GTK потока и подставляет их вместо иконок
+    fn load_images(&self, images: PendingImages) {
+        if images.is_empty() {
+            return;
+        }
+
+        let service = Arc::clone(&self.service);
+        glib::spawn_future_local(async move {
+            for (id, image) in images {
+                // Список успели перестроить или закрыть
+                if image.upgrade().is_none() {
+                    continue;
+                }
+
+                let service = Arc::clone(&service);
+                let Ok(Some(data)) = gio::spawn_blocking(move || service.get_image(id)).await else {
+                    continue;
+                };
+
+                let (Some(image), Some(texture)) = (image.upgrade(), NotificationWidget::texture_from_png(&data)) else {
+                    continue;
+                };
+                image.set_paintable(Some(&texture));
+                image.set_pixel_size(32);
+                image.add_css_class("notification-image");
+            }
+        });
+    }
+
+    /// Перезапрашивает историю и строит группы по приложениям
+    fn refresh(self: &Rc<Self>) {
+        while let Some(child) = self.list.first_child() {
+            self.list.remove(&child);
+        }
+
+        let filter = self.filter();
+        let history = self.service.search_history(&filter);
+        eprintln!("[UI] Loaded {} notifications from history", history.len());
+
+        if history.is_empty() {
+            let filtered = !filter.query.is_empty() || filter.urgency.is_some() || filter.since.is_some();
+            let empty_label = gtk4::Label::new(Some(if filtered { "No matching notifications" } else { "No notifications" }));
+            empty_label.add_css_class("notification-empty");
+            self.list.append(&empty_label);
+        } else {
+            // История отсортирована по времени - группы идут по последнему уведомлению
+            let mut groups: Vec<(String, Vec<Notification>)> = Vec::new();
+            for notification in history {
+                match groups.iter_mut().find(|(app, _)| *app == notification.app_name) {
+                    Some((_, items)) => items.push(notification),
+                    None => groups.push((notification.app_name.clone(), vec![notification])),
+                }
+            }
+
+            for (app_name, notifications) in groups {
+                let group = self.create_group(&app_name, &notifications);
+                self.list.append(&group);
+            }
+        }
+
+        // Обновляем счётчик на кнопке
+        if let Some(btn) = self.button_weak.upgrade() {
+            NotificationWidget::update_button_badge(&btn, &self.service);
+        }
+    }
+
+    /// Сворачиваемая группа уведомлений одного приложения
+    fn create_group(self: &Rc<Self>, app_name: &str, notifications: &[Notification]) -> gtk4::Box {
+        let group = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        group.add_css_class("notification-group");
+
+        let header = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        header.add_css_class("notification-group-header");
+
+        let expanded = !self.collapsed.borrow().contains(app_name);
+        let name = if app_name.is_empty() { "Unknown" } else { app_name }.to_string();
+        let count = notifications.len();
+
+        let toggle = gtk4::Button::with_label(&group_label(&name, count, expanded));
+        toggle.add_css_class("notification-group-toggle");
+        toggle.set_has_frame(false);
+        toggle.set_hexpand(true);
+        if let Some(label) = toggle.child().and_downcast::<gtk4::Label>() {
+            label.set_xalign(0.0);
+            label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        }
+        header.append(&toggle);
+
+        let clear = gtk4::Button::with_label("Clear");
+        clear.add_css_class("notification-group-clear");
+        clear.set_has_frame(false);
+        clear.set_tooltip_text(Some(&format!("Clear all notifications from {}", name)));
+        header.append(&clear);
+
+        let items = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        items.set_visible(expanded);
+        let mut images = PendingImages::new();
+        for notification in notifications {
+            items.append(&NotificationWidget::create_notification_item(notification, self, &mut images));
+        }
+
+        // Картинки запрашиваются у сервиса, только когда группа развёрнута
+        let pending_images = Rc::new(RefCell::new(images));
+        if expanded {
+            self.load_images(pending_images.take());
+        }
+
+        group.append(&header);
+        group.append(&items);
+
+        let app = app_name.to_string();
+        let view_weak = Rc::downgrade(self);
+        toggle.connect_clicked(move |btn| {
+            let Some(view) = view_weak.upgrade() else {
+                return;
+            };
+            let expanded = !items.is_visible();
+            items.set_visible(expanded);
+            btn.set_label(&group_label(&name, count, expanded));
+            if expanded {
+                view.load_images(pending_images.take());
+            }
+            // Запоминаем, чтобы группа не разворачивалась при смене фильтра
+            let mut collapsed = view.collapsed.borrow_mut();
+            if expanded {
+                collapsed.remove(&app);
+            } else {
+                collapsed.insert(app.clone());
+            }
+        });
+
+        let app = app_name.to_string();
+        let view_weak = Rc::downgrade(self);
+        clear.connect_clicked(move |_| {
+            if let Some(view) = view_weak.upgrade() {
+                eprintln!("[UI] Clearing notifications from {}", app);
+                view.service.clear_app(&app);
+                view.refresh();
+            }
+        });
+
+        group
+    }
+}
+
+/// Заголовок группы: стрелка, приложение и количество
+fn group_label(name: &str, count: usize, expanded: bool) -> String {
+    format!("{} {} ({})", if expanded { "󰅀" } else { "󰅂" }, name, count)
+}
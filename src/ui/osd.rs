@@ -0,0 +1,239 @@
+use gtk4::prelude::*;
+use gtk4::{glib, Application};
+use gtk4_layer_shell::{Edge, Layer, LayerShell};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use crate::config::{self, OsdConfig};
+
+/// Очередь запросов на показ OSD (заполняется из любого потока)
+static OSD_SENDER: OnceLock<async_channel::Sender<OsdRequest>> = OnceLock::new();
+
+/// Источник OSD - по нему проверяется флаг включения в конфиге
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OsdKind {
+    Volume,
+    Brightness,
+    Microphone,
+    KeyboardLayout,
+    CapsLock,
+}
+
+impl OsdKind {
+    fn is_enabled(&self, config: &OsdConfig) -> bool {
+        match self {
+            OsdKind::Volume => config.volume,
+            OsdKind::Brightness => config.brightness,
+            OsdKind::Microphone => config.microphone,
+            OsdKind::KeyboardLayout => config.keyboard_layout,
+            OsdKind::CapsLock => config.caps_lock,
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self {
+            OsdKind::Volume => "osd-volume",
+            OsdKind::Brightness => "osd-brightness",
+            OsdKind::Microphone => "osd-microphone",
+            OsdKind::KeyboardLayout => "osd-keyboard-layout",
+            OsdKind::CapsLock => "osd-caps-lock",
+        }
+    }
+
+    fn all() -> [OsdKind; 5] {
+        [
+            OsdKind::Volume,
+            OsdKind::Brightness,
+            OsdKind::Microphone,
+            OsdKind::KeyboardLayout,
+            OsdKind::CapsLock,
+        ]
+    }
+}
+
+/// Что показывает OSD
+#[derive(Debug, Clone)]
+pub enum OsdContent {
+    /// Уровень 0-100 с вертикальной шкалой
+    Level { value: u8, muted: bool },
+    /// Короткий текст (раскладка, Caps Lock)
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct OsdRequest {
+    pub kind: OsdKind,
+    pub icon: String,
+    pub content: OsdContent,
+}
+
+/// Показывает OSD; можно вызывать из любого потока
+///
+/// До `Osd::start` и для выключенных в конфиге OSD ничего не делает.
+pub fn show_osd(kind: OsdKind, icon: &str, content: OsdContent) {
+    if let Some(sender) = OSD_SENDER.get() {
+        let _ = sender.send_blocking(OsdRequest {
+            kind,
+            icon: icon.to_string(),
+            content,
+        });
+    }
+}
+
+/// Всплывающий индикатор (On-Screen Display) поверх всех окон
+pub struct Osd {
+    window: gtk4::Window,
+    container: gtk4::Box,
+    icon: gtk4::Label,
+    progressbar: gtk4::ProgressBar,
+    value_label: gtk4::Label,
+    hide_timeout: Arc<Mutex<Option<glib::SourceId>>>,
+}
+
+impl Osd {
+    /// Создаёт окно OSD и начинает обрабатывать запросы `show_osd`
+    pub fn start(app: &Application) {
+        let osd = Self::new(app);
+        let (sender, receiver) = async_channel::unbounded::<OsdRequest>();
+        if OSD_SENDER.set(sender).is_err() {
+            eprintln!("[OSD] Already started");
+            return;
+        }
+
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            // Показываем только последний запрос из накопившихся
+            let mut last = None;
+            while let Ok(request) = receiver.try_recv() {
+                last = Some(request);
+            }
+            if let Some(request) = last {
+                osd.show(&request);
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    fn new(app: &Application) -> Self {
+        let window = gtk4::Window::new();
+        window.set_application(Some(app));
+
+        // Настройка layer shell; привязка к краям берётся из конфига при показе
+        window.init_layer_shell();
+        window.set_layer(Layer::Overlay);
+        window.set_namespace(Some("hyprline-osd"));
+
+        let container = gtk4::Box::new(gtk4::Orientation::Vertical, 8);
+        container.add_css_class("osd");
+        container.set_width_request(80);
+
+        let icon = gtk4::Label::new(None);
+        icon.add_css_class("osd-icon");
+        container.append(&icon);
+
+        // Вертикальный прогресс-бар, заполнение снизу вверх
+        let progressbar = gtk4::ProgressBar::new();
+        progressbar.set_orientation(gtk4::Orientation::Vertical);
+        progressbar.set_inverted(true);
+        progressbar.add_css_class("osd-progress");
+        progressbar.set_vexpand(true);
+        container.append(&progressbar);
+
+        // Процент для уровня или текст для текстовых OSD
+        let value_label = gtk4::Label::new(None);
+        value_label.add_css_class("osd-value");
+        container.append(&value_label);
+
+        window.set_child(Some(&container));
+        window.add_css_class("osd-window");
+
+        Self {
+            window,
+            container,
+            icon,
+            progressbar,
+            value_label,
+            hide_timeout: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn show(&self, request: &OsdRequest) {
+        let config = config::get_config().read().unwrap().osd.clone();
+        if !request.kind.is_enabled(&config) {
+            return;
+        }
+
+        // Отменяем предыдущий таймаут, если есть
+        if let Some(timeout_id) = self.hide_timeout.lock().unwrap().take() {
+            timeout_id.remove();
+        }
+
+        self.apply_position(&config);
+
+        for kind in OsdKind::all() {
+            self.container.remove_css_class(kind.css_class());
+        }
+        self.container.add_css_class(request.kind.css_class());
+
+        self.icon.set_text(&request.icon);
+
+        match &request.content {
+            OsdContent::Level { value, muted } => {
+                self.progressbar.set_visible(true);
+                self.progressbar.set_fraction(*value as f64 / 100.0);
+                self.container.set_height_request(200);
+                self.value_label.set_text(&format!("{}%", value));
+                self.value_label.remove_css_class("osd-text");
+
+                if *muted {
+                    self.container.add_css_class("osd-muted");
+                } else {
+                    self.container.remove_css_class("osd-muted");
+                }
+            }
+            OsdContent::Text(text) => {
+                self.progressbar.set_visible(false);
+                self.container.set_height_request(-1);
+                self.value_label.set_text(text);
+                self.value_label.add_css_class("osd-text");
+                self.container.remove_css_class("osd-muted");
+            }
+        }
+
+        self.window.set_visible(true);
+
+        let window = self.window.clone();
+        let hide_timeout = self.hide_timeout.clone();
+
+        let timeout_id = glib::timeout_add_local(
+            Duration::from_millis(config.timeout_ms as u64),
+            move || {
+                window.set_visible(false);
+                *hide_timeout.lock().unwrap() = None;
+                glib::ControlFlow::Break
+            },
+        );
+
+        *self.hide_timeout.lock().unwrap() = Some(timeout_id);
+    }
+
+    fn apply_position(&self, config: &OsdConfig) {
+        let (top, bottom, left, right) = config.anchor.edges();
+        self.window.set_anchor(Edge::Top, top);
+        self.window.set_anchor(Edge::Bottom, bottom);
+        self.window.set_anchor(Edge::Left, left);
+        self.window.set_anchor(Edge::Right, right);
+
+        self.window.set_margin(Edge::Top, if top { config.margin_vertical } else { 0 });
+        self.window.set_margin(Edge::Bottom, if bottom { config.margin_vertical } else { 0 });
+        self.window.set_margin(Edge::Left, if left { config.margin_horizontal } else { 0 });
+        self.window.set_margin(Edge::Right, if right { config.margin_horizontal } else { 0 });
+    }
+
+    /// Скрывает OSD немедленно
+    #[allow(dead_code)]
+    pub fn hide(&self) {
+        if let Some(timeout_id) = self.hide_timeout.lock().unwrap().take() {
+            timeout_id.remove();
+        }
+        self.window.set_visible(false);
+    }
+}
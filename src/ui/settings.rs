@@ -2,13 +2,20 @@ use gtk4::prelude::*;
 use gtk4::{
     Box as GtkBox, Button, Label, ListBox, ListBoxRow, Orientation,
     ScrolledWindow, Separator, Window, Switch, Frame, ComboBoxText, Entry,
-    glib,
+    SpinButton, TextView, glib,
 };
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::config::{WidgetType, WidgetPosition, WidgetConfig, get_config, save_config, HyprlineConfig};
+use crate::config::{
+    WidgetType, WidgetPosition, WidgetConfig, get_config, save_config, HyprlineConfig,
+    OptionKind, options_schema,
+};
 use crate::domain::workspace_service::WorkspaceService;
+use crate::ui::theme;
+
+/// Данные строки виджета в настройках: тип, enabled, options
+type WidgetRowData = (WidgetType, bool, serde_json::Value);
 
 /// Окно настроек
 pub struct SettingsWindow;
@@ -275,7 +282,10 @@ impl SettingsWindow {
         let monitors_box = GtkBox::new(Orientation::Vertical, 8);
         monitors_box.set_margin_top(16);
 
-        let monitors = workspace_service.get_monitors();
+        let monitors = workspace_service.get_monitors().unwrap_or_else(|e| {
+            eprintln!("[Settings] ✗ Failed to query monitors: {}", e);
+            Vec::new()
+        });
         let has_monitors = !monitors.is_empty();
 
         for monitor in monitors {
@@ -346,6 +356,88 @@ impl SettingsWindow {
         container
     }
 
+    pub fn create_appearance_settings() -> GtkBox {
+        let container = GtkBox::new(Orientation::Vertical, 16);
+        container.add_css_class("settings-appearance");
+        container.set_margin_start(24);
+        container.set_margin_end(24);
+        container.set_margin_top(24);
+        container.set_margin_bottom(24);
+
+        // Заголовок
+        let header = Label::new(Some("Appearance"));
+        header.add_css_class("settings-section-header");
+        header.set_halign(gtk4::Align::Start);
+        container.append(&header);
+
+        let description = Label::new(Some(&format!(
+            "Themes are CSS files in {}. Your {} is applied on top of the selected theme. \
+             Changes to these files are applied live.",
+            theme::themes_dir().display(),
+            theme::user_style_path().display(),
+        )));
+        description.add_css_class("settings-description");
+        description.set_halign(gtk4::Align::Start);
+        description.set_wrap(true);
+        container.append(&description);
+
+        // Выбор темы
+        let theme_row = GtkBox::new(Orientation::Horizontal, 12);
+        theme_row.add_css_class("monitor-row");
+        theme_row.set_margin_top(16);
+
+        let theme_label = Label::new(Some("Theme"));
+        theme_label.set_hexpand(true);
+        theme_label.set_halign(gtk4::Align::Start);
+        theme_row.append(&theme_label);
+
+        let theme_combo = ComboBoxText::new();
+        theme_combo.append(Some("__builtin__"), "Built-in");
+        for name in theme::list_themes() {
+            theme_combo.append(Some(&name), &name);
+        }
+
+        {
+            let config = get_config().read().unwrap();
+            let active = config.theme.as_deref().unwrap_or("__builtin__");
+            if !theme_combo.set_active_id(Some(active)) {
+                // Тема выбрана, но файла нет - показываем её, чтобы не потерять выбор
+                theme_combo.append(Some(active), &format!("{} (missing)", active));
+                theme_combo.set_active_id(Some(active));
+            }
+        }
+
+        theme_combo.connect_changed(|combo| {
+            if let Some(id) = combo.active_id() {
+                let theme = (id != "__builtin__").then(|| id.to_string());
+                let mut config = get_config().write().unwrap();
+                if config.theme == theme {
+                    return;
+                }
+                config.theme = theme;
+                drop(config);
+                let _ = save_config();
+            }
+        });
+
+        theme_row.append(&theme_combo);
+        container.append(&theme_row);
+
+        // Открыть каталог с темами в файловом менеджере
+        let open_btn = Button::with_label("Open Themes Folder");
+        open_btn.add_css_class("settings-button");
+        open_btn.set_halign(gtk4::Align::Start);
+        open_btn.connect_clicked(|_| {
+            let uri = format!("file://{}", theme::themes_dir().display());
+            if let Err(e) = gtk4::gio::AppInfo::launch_default_for_uri(&uri, None::<&gtk4::gio::AppLaunchContext>) {
+                eprintln!("[Settings] ✗ Failed to open {}: {}", uri, e);
+            }
+        });
+        container.append(&open_btn);
+
+        container
+    }
+
     pub fn create_widgets_settings() -> GtkBox {
         let container = GtkBox::new(Orientation::Vertical, 8);
         container.add_css_class("settings-widgets");
@@ -374,34 +466,32 @@ impl SettingsWindow {
         container.append(&header_box);
 
         // Загружаем данные из конфигурации
-        let left_widgets: Rc<RefCell<Vec<(WidgetType, bool)>>> = Rc::new(RefCell::new(Vec::new()));
-        let center_widgets: Rc<RefCell<Vec<(WidgetType, bool)>>> = Rc::new(RefCell::new(Vec::new()));
-        let right_widgets: Rc<RefCell<Vec<(WidgetType, bool)>>> = Rc::new(RefCell::new(Vec::new()));
+        let left_widgets: Rc<RefCell<Vec<WidgetRowData>>> = Rc::new(RefCell::new(Vec::new()));
+        let center_widgets: Rc<RefCell<Vec<WidgetRowData>>> = Rc::new(RefCell::new(Vec::new()));
+        let right_widgets: Rc<RefCell<Vec<WidgetRowData>>> = Rc::new(RefCell::new(Vec::new()));
 
         {
             let config = get_config().read().unwrap();
             let profile = config.get_active_profile();
 
-            let mut left_vec: Vec<_> = profile.widgets.iter()
-                .filter(|w| w.position == WidgetPosition::Left)
-                .map(|w| (w.widget_type, w.enabled, w.order))
-                .collect();
-            let mut center_vec: Vec<_> = profile.widgets.iter()
-                .filter(|w| w.position == WidgetPosition::Center)
-                .map(|w| (w.widget_type, w.enabled, w.order))
-                .collect();
-            let mut right_vec: Vec<_> = profile.widgets.iter()
-                .filter(|w| w.position == WidgetPosition::Right)
-                .map(|w| (w.widget_type, w.enabled, w.order))
-                .collect();
-
-            left_vec.sort_by_key(|(_, _, order)| *order);
-            center_vec.sort_by_key(|(_, _, order)| *order);
-            right_vec.sort_by_key(|(_, _, order)| *order);
-
-            *left_widgets.borrow_mut() = left_vec.iter().map(|(t, e, _)| (*t, *e)).collect();
-            *center_widgets.borrow_mut() = center_vec.iter().map(|(t, e, _)| (*t, *e)).collect();
-            *right_widgets.borrow_mut() = right_vec.iter().map(|(t, e, _)| (*t, *e)).collect();
+            let zone = |position: WidgetPosition| -> Vec<WidgetRowData> {
+                let mut widgets: Vec<_> = profile.widgets.iter()
+                    .filter(|w| w.position == position)
+                    .collect();
+                widgets.sort_by_key(|w| w.order);
+                widgets.iter().map(|w| (w.widget_type, w.enabled, w.options.clone())).collect()
+            };
+
+            *left_widgets.borrow_mut() = zone(WidgetPosition::Left);
+            *center_widgets.borrow_mut() = zone(WidgetPosition::Center);
+            *right_widgets.borrow_mut() = zone(WidgetPosition::Right);
+
+            // Виджеты, которых нет в профиле (например, добавленные в новой версии) - выключенными справа
+            for widget_type in WidgetType::all() {
+                if !profile.widgets.iter().any(|w| w.widget_type == widget_type) {
+                    right_widgets.borrow_mut().push((widget_type, false, serde_json::Value::Null));
+                }
+            }
         }
 
         // Создаём Box для каждой зоны с уникальными CSS классами
@@ -433,7 +523,7 @@ impl SettingsWindow {
         fn create_zone_column(
             title: &str,
             list_box: &Rc<GtkBox>,
-            widgets: &Rc<RefCell<Vec<(WidgetType, bool)>>>,
+            widgets: &Rc<RefCell<Vec<WidgetRowData>>>,
             left_list: Rc<GtkBox>,
             center_list: Rc<GtkBox>,
             right_list: Rc<GtkBox>,
@@ -455,10 +545,11 @@ impl SettingsWindow {
             list_box.set_vexpand(true);
 
             // Заполняем список
-            for (widget_type, enabled) in widgets.borrow().iter() {
+            for (widget_type, enabled, options) in widgets.borrow().iter() {
                 let row = create_widget_row(
                     *widget_type,
                     *enabled,
+                    options.clone(),
                     left_list.clone(),
                     center_list.clone(),
                     right_list.clone(),
@@ -550,12 +641,9 @@ impl SettingsWindow {
                 let mut order = 0i32;
                 while let Some(widget) = child {
                     if let Some((widget_type, enabled)) = get_widget_from_box_child(&widget) {
-                        new_widgets.push(WidgetConfig {
-                            widget_type,
-                            enabled,
-                            position,
-                            order,
-                        });
+                        let mut widget_config = WidgetConfig::new(widget_type, enabled, position, order);
+                        widget_config.options = get_widget_options_from_box_child(&widget);
+                        new_widgets.push(widget_config);
                         order += 1;
                     }
                     child = widget.next_sibling();
@@ -622,6 +710,7 @@ fn create_input_dialog(parent: Option<&Window>, title: &str, label: &str, defaul
 fn create_widget_row(
     widget_type: WidgetType,
     enabled: bool,
+    options: serde_json::Value,
     left_list: Rc<GtkBox>,
     center_list: Rc<GtkBox>,
     right_list: Rc<GtkBox>,
@@ -637,6 +726,7 @@ fn create_widget_row(
     unsafe {
         row.set_data("widget_type", widget_type as i32);
         row.set_data("enabled", enabled as i32);
+        row.set_data("options", options.to_string());
     }
 
     // Верхняя строка: кнопки вверх/вниз, иконка и название
@@ -695,6 +785,23 @@ fn create_widget_row(
     name.set_hexpand(true);
     top_row.append(&name);
 
+    // Кнопка настроек виджета (только для виджетов с options)
+    if has_widget_options(widget_type) {
+        let options_btn = Button::new();
+        options_btn.set_label("󰒓");
+        options_btn.add_css_class("settings-move-btn");
+        options_btn.set_tooltip_text(Some("Widget options"));
+
+        let row_weak = row.downgrade();
+        options_btn.connect_clicked(move |btn| {
+            if let Some(row) = row_weak.upgrade() {
+                let parent = btn.root().and_then(|r| r.downcast::<Window>().ok());
+                show_widget_options_dialog(parent.as_ref(), widget_type, &row);
+            }
+        });
+        top_row.append(&options_btn);
+    }
+
     row.append(&top_row);
 
     // Нижняя строка: кнопки зон и переключатель
@@ -801,6 +908,10 @@ fn get_widget_from_box_child(widget: &gtk4::Widget) -> Option<(WidgetType, bool)
                 10 => WidgetType::Notifications,
                 11 => WidgetType::DateTime,
                 12 => WidgetType::Submap,
+                13 => WidgetType::Taskbar,
+                14 => WidgetType::Microphone,
+                15 => WidgetType::Media,
+                16 => WidgetType::NightLight,
                 _ => return None,
             };
             return Some((widget_type, en != 0));
@@ -809,6 +920,226 @@ fn get_widget_from_box_child(widget: &gtk4::Widget) -> Option<(WidgetType, bool)
     None
 }
 
+/// Получает options виджета из GtkBox child
+fn get_widget_options_from_box_child(widget: &gtk4::Widget) -> serde_json::Value {
+    unsafe {
+        widget
+            .data::<String>("options")
+            .and_then(|p| serde_json::from_str(p.as_ref()).ok())
+            .unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Есть ли у виджета настраиваемые options
+fn has_widget_options(widget_type: WidgetType) -> bool {
+    options_schema(widget_type).is_some()
+}
+
+/// Контрол редактора одного поля options
+enum OptionEditor {
+    Bool(Switch),
+    Int(SpinButton),
+    Text(Entry),
+    Choice(ComboBoxText),
+    Pairs {
+        view: TextView,
+        first: &'static str,
+        second: &'static str,
+    },
+}
+
+impl OptionEditor {
+    /// Создаёт контрол для поля и заполняет его текущим значением
+    fn new(kind: &OptionKind, value: &serde_json::Value) -> Self {
+        match *kind {
+            OptionKind::Bool => {
+                let switch = Switch::new();
+                switch.set_active(value.as_bool().unwrap_or(false));
+                switch.set_valign(gtk4::Align::Center);
+                OptionEditor::Bool(switch)
+            }
+            OptionKind::Int { min, max } => {
+                let spin = SpinButton::with_range(min as f64, max as f64, 1.0);
+                spin.set_value(value.as_i64().unwrap_or(min) as f64);
+                OptionEditor::Int(spin)
+            }
+            OptionKind::Text => {
+                let entry = Entry::new();
+                entry.set_text(value.as_str().unwrap_or_default());
+                OptionEditor::Text(entry)
+            }
+            OptionKind::Choice(choices) => {
+                let combo = ComboBoxText::new();
+                combo.add_css_class("settings-position-combo");
+                for &(id, label) in choices {
+                    combo.append(Some(id), label);
+                }
+                if !combo.set_active_id(value.as_str()) {
+                    combo.set_active(Some(0));
+                }
+                OptionEditor::Choice(combo)
+            }
+            OptionKind::Pairs { first, second } => {
+                let text = value
+                    .as_array()
+                    .map(|items| {
+                        items
+                            .iter()
+                            .map(|item| {
+                                format!(
+                                    "{} => {}",
+                                    item[first].as_str().unwrap_or_default(),
+                                    item[second].as_str().unwrap_or_default()
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    })
+                    .unwrap_or_default();
+
+                let view = TextView::new();
+                view.set_monospace(true);
+                view.buffer().set_text(&text);
+                OptionEditor::Pairs { view, first, second }
+            }
+        }
+    }
+
+    /// Добавляет контрол с подписью в контейнер диалога
+    fn attach(&self, content: &GtkBox, label: &str) {
+        let label = Label::new(Some(label));
+        label.set_halign(gtk4::Align::Start);
+
+        if let OptionEditor::Pairs { view, .. } = self {
+            content.append(&label);
+            let scroll = ScrolledWindow::new();
+            scroll.set_min_content_height(100);
+            scroll.set_child(Some(view));
+            content.append(&scroll);
+            return;
+        }
+
+        let row = GtkBox::new(Orientation::Horizontal, 12);
+        label.set_hexpand(true);
+        row.append(&label);
+        match self {
+            OptionEditor::Bool(switch) => row.append(switch),
+            OptionEditor::Int(spin) => row.append(spin),
+            OptionEditor::Text(entry) => row.append(entry),
+            OptionEditor::Choice(combo) => row.append(combo),
+            OptionEditor::Pairs { .. } => {}
+        }
+        content.append(&row);
+    }
+
+    /// Текущее значение контрола в JSON
+    fn value(&self) -> serde_json::Value {
+        match self {
+            OptionEditor::Bool(switch) => switch.is_active().into(),
+            OptionEditor::Int(spin) => (spin.value() as i64).into(),
+            OptionEditor::Text(entry) => entry.text().to_string().into(),
+            OptionEditor::Choice(combo) => combo
+                .active_id()
+                .map(|id| serde_json::Value::from(id.to_string()))
+                .unwrap_or(serde_json::Value::Null),
+            OptionEditor::Pairs { view, first, second } => {
+                let buffer = view.buffer();
+                let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+                let items = text
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| {
+                        let (a, b) = line.split_once(" => ").unwrap_or((line, ""));
+                        let mut item = serde_json::Map::new();
+                        item.insert(first.to_string(), a.into());
+                        item.insert(second.to_string(), b.into());
+                        serde_json::Value::Object(item)
+                    })
+                    .collect();
+                serde_json::Value::Array(items)
+            }
+        }
+    }
+}
+
+/// Диалог настроек виджета, генерируется по схеме options;
+/// результат сохраняется в data строки и применяется по Apply
+fn show_widget_options_dialog(parent: Option<&Window>, widget_type: WidgetType, row: &GtkBox) {
+    let Some(schema) = options_schema(widget_type) else {
+        return;
+    };
+
+    let dialog = gtk4::Dialog::with_buttons(
+        Some(&format!("{} Options", widget_type.name())),
+        parent,
+        gtk4::DialogFlags::MODAL | gtk4::DialogFlags::DESTROY_WITH_PARENT,
+        &[("Cancel", gtk4::ResponseType::Cancel), ("OK", gtk4::ResponseType::Ok)],
+    );
+
+    let content = dialog.content_area();
+    content.set_margin_start(16);
+    content.set_margin_end(16);
+    content.set_margin_top(16);
+    content.set_margin_bottom(16);
+    content.set_spacing(8);
+
+    // Текущие настройки в нормализованном виде (с версией и всеми полями)
+    let current = (schema.normalize)(&get_widget_options_from_box_child(row.upcast_ref()))
+        .unwrap_or_else(|e| {
+            eprintln!("[Settings] ✗ Invalid options for {:?}, using defaults: {}", widget_type, e);
+            schema.defaults()
+        });
+
+    let editors: Vec<(&'static str, OptionEditor)> = schema
+        .fields
+        .iter()
+        .map(|field| {
+            let editor = OptionEditor::new(&field.kind, &current[field.key]);
+            editor.attach(&content, field.label);
+            (field.key, editor)
+        })
+        .collect();
+
+    let error_label = Label::new(None);
+    error_label.add_css_class("settings-options-error");
+    error_label.set_halign(gtk4::Align::Start);
+    error_label.set_wrap(true);
+    error_label.set_visible(false);
+    content.append(&error_label);
+
+    let normalize = schema.normalize;
+    let row_weak = row.downgrade();
+    dialog.connect_response(move |dialog, response| {
+        if response != gtk4::ResponseType::Ok {
+            dialog.close();
+            return;
+        }
+
+        let mut value = current.clone();
+        for (key, editor) in &editors {
+            value[*key] = editor.value();
+        }
+
+        match normalize(&value) {
+            Ok(value) => {
+                if let Some(row) = row_weak.upgrade() {
+                    unsafe {
+                        row.set_data("options", value.to_string());
+                    }
+                }
+                dialog.close();
+            }
+            Err(e) => {
+                // Диалог остаётся открытым, пока значения не исправлены
+                error_label.set_text(&e);
+                error_label.set_visible(true);
+            }
+        }
+    });
+
+    dialog.present();
+}
+
 /// Показать окно настроек
 pub fn show_settings(app: &gtk4::Application) {
     use crate::infrastructure::hyprland_ipc::HyprlandIpc;
@@ -860,6 +1191,10 @@ pub fn show_settings(app: &gtk4::Application) {
     unsafe { widgets_item.set_data("page", "widgets"); }
     menu_list.append(&widgets_item);
 
+    let appearance_item = SettingsWindow::create_menu_item("󰏘", "Appearance");
+    unsafe { appearance_item.set_data("page", "appearance"); }
+    menu_list.append(&appearance_item);
+
     menu_box.append(&menu_list);
     main_box.append(&menu_box);
 
@@ -916,6 +1251,9 @@ pub fn show_settings(app: &gtk4::Application) {
                 Some("widgets") => {
                     content.append(&SettingsWindow::create_widgets_settings());
                 }
+                Some("appearance") => {
+                    content.append(&SettingsWindow::create_appearance_settings());
+                }
                 _ => {}
             }
         }
@@ -1,5 +1,6 @@
 use gtk4::prelude::*;
 use std::sync::Arc;
+use crate::config::SystemResourcesOptions;
 use crate::domain::system_resources_service::SystemResourcesService;
 use crate::shared_state::get_shared_state;
 
@@ -7,16 +8,18 @@ pub struct SystemResourcesWidget {
     container: gtk4::Box,
     #[allow(dead_code)]
     service: Arc<dyn SystemResourcesService + Send + Sync>,
+    options: SystemResourcesOptions,
 }
 
 impl SystemResourcesWidget {
-    pub fn new(service: Arc<dyn SystemResourcesService + Send + Sync>) -> Self {
+    pub fn new(service: Arc<dyn SystemResourcesService + Send + Sync>, options: SystemResourcesOptions) -> Self {
         let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 8);
         container.add_css_class("system-resources-widget");
 
         Self {
             container,
             service,
+            options,
         }
     }
 
@@ -44,21 +47,23 @@ impl SystemResourcesWidget {
             cpu_label.add_css_class("cpu-value");
             self.container.append(&cpu_label);
 
-            // Разделитель
-            let separator = gtk4::Label::new(Some("│"));
-            separator.add_css_class("system-resources-separator");
-            self.container.append(&separator);
+            if self.options.show_memory {
+                // Разделитель
+                let separator = gtk4::Label::new(Some("│"));
+                separator.add_css_class("system-resources-separator");
+                self.container.append(&separator);
 
-            // RAM иконка и использование
-            let ram_icon = gtk4::Label::new(Some("󰍛")); // Nerd Font: nf-md-memory
-            ram_icon.add_css_class("system-resources-icon");
-            ram_icon.add_css_class("ram-icon");
-            self.container.append(&ram_icon);
+                // RAM иконка и использование
+                let ram_icon = gtk4::Label::new(Some("󰍛")); // Nerd Font: nf-md-memory
+                ram_icon.add_css_class("system-resources-icon");
+                ram_icon.add_css_class("ram-icon");
+                self.container.append(&ram_icon);
 
-            let ram_label = gtk4::Label::new(Some(&format!("{:.1}G", resources.memory_used_gb)));
-            ram_label.add_css_class("system-resources-value");
-            ram_label.add_css_class("ram-value");
-            self.container.append(&ram_label);
+                let ram_label = gtk4::Label::new(Some(&format!("{:.1}G", resources.memory_used_gb)));
+                ram_label.add_css_class("system-resources-value");
+                ram_label.add_css_class("ram-value");
+                self.container.append(&ram_label);
+            }
 
             // Tooltip с подробной информацией
             let tooltip = format!(
@@ -77,23 +82,22 @@ impl SystemResourcesWidget {
 
     /// Применяет CSS классы в зависимости от уровня нагрузки
     fn apply_usage_classes(&self, resources: &crate::domain::models::SystemResources) {
-        // CPU классы
-        if resources.cpu_usage >= 80.0 {
-            self.container.add_css_class("cpu-high");
-        } else if resources.cpu_usage >= 50.0 {
-            self.container.add_css_class("cpu-medium");
-        } else {
-            self.container.add_css_class("cpu-low");
+        for class in ["cpu-high", "cpu-medium", "cpu-low", "ram-high", "ram-medium", "ram-low"] {
+            self.container.remove_css_class(class);
         }
 
-        // RAM классы
-        if resources.memory_usage >= 80.0 {
-            self.container.add_css_class("ram-high");
-        } else if resources.memory_usage >= 50.0 {
-            self.container.add_css_class("ram-medium");
-        } else {
-            self.container.add_css_class("ram-low");
-        }
+        let level = |usage: f64| {
+            if usage >= self.options.high_threshold as f64 {
+                "high"
+            } else if usage >= self.options.medium_threshold as f64 {
+                "medium"
+            } else {
+                "low"
+            }
+        };
+
+        self.container.add_css_class(&format!("cpu-{}", level(resources.cpu_usage as f64)));
+        self.container.add_css_class(&format!("ram-{}", level(resources.memory_usage as f64)));
     }
 }
 
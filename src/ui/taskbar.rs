@@ -0,0 +1,193 @@
+use crate::config::{TaskbarOptions, TaskbarScope};
+use crate::domain::models::WindowInfo;
+use crate::domain::workspace_service::WorkspaceService;
+use crate::infrastructure::hyprland_client::HyprlandError;
+use crate::infrastructure::hyprland_events::HyprlandEvent;
+use crate::ui::app_icon::create_app_icon;
+use gtk4::prelude::*;
+use gtk4::gdk;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Максимальная длина заголовка окна на кнопке
+const MAX_TITLE_CHARS: i32 = 20;
+
+/// Список окон (taskbar)
+pub struct TaskbarWidget {
+    container: gtk4::Box,
+    monitor_name: String,
+    service: Arc<dyn WorkspaceService + Send + Sync>,
+    options: TaskbarOptions,
+    /// Адреса окон (`0x...`), запросивших внимание
+    urgent: RefCell<HashSet<String>>,
+}
+
+impl TaskbarWidget {
+    pub fn new(
+        monitor_name: String,
+        service: Arc<dyn WorkspaceService + Send + Sync>,
+        options: TaskbarOptions,
+    ) -> Self {
+        let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 2);
+        container.add_css_class("taskbar");
+
+        Self {
+            container,
+            monitor_name,
+            service,
+            options,
+            urgent: RefCell::new(HashSet::new()),
+        }
+    }
+
+    pub fn widget(&self) -> &gtk4::Box {
+        &self.container
+    }
+
+    /// Обрабатывает событие Hyprland, возвращает true если нужна перерисовка
+    pub fn handle_event(&self, event: &HyprlandEvent) -> bool {
+        match event {
+            HyprlandEvent::Urgent { address } => {
+                self.urgent.borrow_mut().insert(format!("0x{}", address));
+                true
+            }
+            HyprlandEvent::ActiveWindowV2 { address } | HyprlandEvent::CloseWindow { address } => {
+                // Окно получило фокус или закрылось - внимание больше не требуется
+                self.urgent.borrow_mut().remove(&format!("0x{}", address));
+                true
+            }
+            HyprlandEvent::WindowTitleV2 { .. } | HyprlandEvent::ChangeFloatingMode { .. } => true,
+            _ => event.affects_workspaces(),
+        }
+    }
+
+    pub fn update(&self) {
+        // При ошибке IPC оставляем последний отрисованный список
+        let windows = match self.visible_windows() {
+            Ok(windows) => windows,
+            Err(e) => {
+                eprintln!("[Taskbar] ✗ Failed to query windows: {}", e);
+                return;
+            }
+        };
+
+        // Очищаем существующие элементы
+        while let Some(child) = self.container.first_child() {
+            self.container.remove(&child);
+        }
+
+        if self.options.group_by_class {
+            let mut groups: Vec<(String, Vec<WindowInfo>)> = Vec::new();
+            for window in windows {
+                match groups.iter_mut().find(|(class, _)| *class == window.class) {
+                    Some((_, group)) => group.push(window),
+                    None => groups.push((window.class.clone(), vec![window])),
+                }
+            }
+
+            for (_, group) in groups {
+                let button = self.create_button(group);
+                self.container.append(&button);
+            }
+        } else {
+            for window in windows {
+                let button = self.create_button(vec![window]);
+                self.container.append(&button);
+            }
+        }
+    }
+
+    /// Окна, которые нужно показать на этом мониторе
+    fn visible_windows(&self) -> Result<Vec<WindowInfo>, HyprlandError> {
+        let snapshot = self.service.get_snapshot(&self.monitor_name)?;
+        let monitor_id = snapshot.monitor_id;
+
+        let active_workspace = match self.options.scope {
+            TaskbarScope::Workspace => snapshot.monitor_active_workspace,
+            TaskbarScope::Monitor => None,
+        };
+
+        let mut windows: Vec<WindowInfo> = snapshot
+            .clients
+            .into_iter()
+            .filter(|w| w.mapped && !w.hidden)
+            .filter(|w| Some(w.monitor) == monitor_id)
+            .filter(|w| match self.options.scope {
+                TaskbarScope::Workspace => Some(w.workspace.id) == active_workspace,
+                TaskbarScope::Monitor => true,
+            })
+            .collect();
+
+        // Порядок: по workspace, затем по положению на экране
+        windows.sort_by_key(|w| (w.workspace.id, w.at.0, w.at.1));
+        Ok(windows)
+    }
+
+    /// Создаёт кнопку для окна или группы окон одного класса
+    fn create_button(&self, group: Vec<WindowInfo>) -> gtk4::Box {
+        let first = &group[0];
+
+        let button = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        button.add_css_class("taskbar-item");
+        button.append(&create_app_icon(&first.class, 16));
+
+        if self.options.show_titles {
+            let text = if group.len() > 1 {
+                first.class.clone()
+            } else {
+                first.title.clone()
+            };
+            let title = gtk4::Label::new(Some(&text));
+            title.add_css_class("taskbar-title");
+            title.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+            title.set_max_width_chars(MAX_TITLE_CHARS);
+            button.append(&title);
+        }
+
+        if group.len() > 1 {
+            let count = gtk4::Label::new(Some(&group.len().to_string()));
+            count.add_css_class("taskbar-count");
+            button.append(&count);
+        }
+
+        let tooltip = group.iter().map(|w| w.title.as_str()).collect::<Vec<_>>().join("\n");
+        button.set_tooltip_text(Some(&tooltip));
+
+        // focusHistoryID = 0 у окна с фокусом
+        if group.iter().any(|w| w.focus_history_id == 0) {
+            button.add_css_class("active");
+        }
+        let urgent = self.urgent.borrow();
+        if group.iter().any(|w| urgent.contains(&w.address)) {
+            button.add_css_class("urgent");
+        }
+
+        // Левый клик - фокус (для группы: следующее окно после текущего), средний - закрыть
+        let addresses: Vec<String> = group.iter().map(|w| w.address.clone()).collect();
+        let focused_index = group.iter().position(|w| w.focus_history_id == 0);
+        let service = self.service.clone();
+        let click = gtk4::GestureClick::new();
+        click.set_button(0);
+        click.connect_pressed(move |gesture, _, _, _| {
+            let result = match gesture.current_button() {
+                gdk::BUTTON_PRIMARY => {
+                    let index = focused_index.map(|i| (i + 1) % addresses.len()).unwrap_or(0);
+                    service.focus_window(&addresses[index])
+                }
+                gdk::BUTTON_MIDDLE => {
+                    let index = focused_index.unwrap_or(0);
+                    service.close_window(&addresses[index])
+                }
+                _ => Ok(()),
+            };
+
+            if let Err(e) = result {
+                eprintln!("[Taskbar] ✗ Window action failed: {}", e);
+            }
+        });
+        button.add_controller(click);
+
+        button
+    }
+}
@@ -0,0 +1,160 @@
+use crate::config::{get_config, subscribe_config_changes, HyprlineConfig};
+use gtk4::{gdk, glib};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::Duration;
+
+/// Каталог конфигурации hyprline
+fn config_dir() -> PathBuf {
+    let config_path = HyprlineConfig::config_path();
+    config_path.parent().map(Path::to_path_buf).unwrap_or(config_path)
+}
+
+/// Пользовательский стиль, применяется поверх темы
+pub fn user_style_path() -> PathBuf {
+    config_dir().join("style.css")
+}
+
+/// Каталог с темами (`<имя>.css`)
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+fn theme_path(name: &str) -> PathBuf {
+    themes_dir().join(format!("{}.css", name))
+}
+
+/// Имена доступных тем, по алфавиту
+pub fn list_themes() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(themes_dir()) else {
+        return Vec::new();
+    };
+
+    let mut themes: Vec<String> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("css"))
+        .filter_map(|p| p.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+        .collect();
+    themes.sort();
+    themes
+}
+
+/// Подключает тему и `style.css` поверх встроенных стилей и перезагружает их при изменении
+///
+/// Приоритеты: встроенный styles.css < тема < style.css пользователя.
+pub fn init_user_styles(display: &gdk::Display) {
+    let theme_provider = gtk4::CssProvider::new();
+    let user_provider = gtk4::CssProvider::new();
+
+    for provider in [&theme_provider, &user_provider] {
+        provider.connect_parsing_error(|_, section, error| {
+            eprintln!("[Theme] ✗ {}: {}", section.to_str(), error);
+        });
+    }
+
+    gtk4::style_context_add_provider_for_display(
+        display,
+        &theme_provider,
+        gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+    );
+    gtk4::style_context_add_provider_for_display(
+        display,
+        &user_provider,
+        gtk4::STYLE_PROVIDER_PRIORITY_USER,
+    );
+
+    let reload = move || {
+        let theme = get_config().read().unwrap().theme.clone();
+        load_css(&theme_provider, theme.as_deref().map(theme_path).as_deref());
+        load_css(&user_provider, Some(&user_style_path()));
+    };
+    reload();
+
+    // Перезагрузка при смене темы в конфигурации и при изменении css файлов
+    let (tx, rx) = async_channel::unbounded::<()>();
+    {
+        let tx = tx.clone();
+        subscribe_config_changes(move || {
+            let _ = tx.send_blocking(());
+        });
+    }
+    start_watching(tx);
+
+    glib::timeout_add_local(Duration::from_millis(100), move || {
+        let mut changed = false;
+        while rx.try_recv().is_ok() {
+            changed = true;
+        }
+        if changed {
+            reload();
+        }
+        glib::ControlFlow::Continue
+    });
+}
+
+/// Загружает css файл в provider; отсутствующий файл - пустой стиль
+fn load_css(provider: &gtk4::CssProvider, path: Option<&Path>) {
+    match path {
+        Some(path) if path.exists() => {
+            provider.load_from_path(path);
+            eprintln!("[Theme] ✓ Loaded {:?}", path);
+        }
+        Some(path) => {
+            if path != user_style_path() {
+                eprintln!("[Theme] ✗ Theme file not found: {:?}", path);
+            }
+            provider.load_from_data("");
+        }
+        None => provider.load_from_data(""),
+    }
+}
+
+/// Следит за css файлами в каталоге конфигурации и `themes/`
+fn start_watching(tx: async_channel::Sender<()>) {
+    let dir = config_dir();
+
+    thread::spawn(move || {
+        let (notify_tx, notify_rx) = channel::<Result<Event, notify::Error>>();
+
+        let mut watcher = match notify::recommended_watcher(notify_tx) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("[Theme] ✗ Failed to create watcher: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = std::fs::create_dir_all(themes_dir()) {
+            eprintln!("[Theme] ✗ Failed to create themes directory: {}", e);
+        }
+        if let Err(e) = watcher.watch(&dir, RecursiveMode::Recursive) {
+            eprintln!("[Theme] ✗ Failed to watch {:?}: {}", dir, e);
+            return;
+        }
+
+        eprintln!("[Theme] ✓ Watching styles in {:?}", dir);
+
+        while let Ok(res) = notify_rx.recv() {
+            let Ok(event) = res else {
+                continue;
+            };
+
+            let is_css = event
+                .paths
+                .iter()
+                .any(|p| p.extension().and_then(|e| e.to_str()) == Some("css"));
+            if !is_css || matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+
+            // Редакторы пишут файл в несколько приёмов - схлопываем пачку событий
+            thread::sleep(Duration::from_millis(100));
+            while notify_rx.try_recv().is_ok() {}
+
+            let _ = tx.send_blocking(());
+        }
+    });
+}
@@ -1,11 +1,27 @@
 use gtk4::prelude::*;
 use std::sync::Arc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
 use crate::domain::volume_service::VolumeService;
-use crate::domain::models::VolumeInfo;
+use crate::domain::models::{AudioDevice, AudioDeviceKind, AudioStream, VolumeInfo};
+use crate::ui::app_icon::create_app_icon;
 
+/// Виджет громкости
+///
+/// Элементы создаются один раз и обновляются на месте, чтобы открытый popover
+/// не закрывался при изменении громкости и списка потоков.
 pub struct VolumeWidget {
     container: gtk4::Box,
+    button: gtk4::Button,
+    icon: gtk4::Label,
+    label: gtk4::Label,
+    not_found: gtk4::Label,
+    slider: gtk4::Scale,
+    /// Значение слайдера меняется из update(), а не пользователем
+    updating_slider: Rc<Cell<bool>>,
+    picker: DevicePicker,
+    mixer: StreamMixer,
     service: Arc<dyn VolumeService + Send + Sync>,
 }
 
@@ -14,8 +30,74 @@ impl VolumeWidget {
         let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
         container.add_css_class("volume-widget");
 
+        // Кнопка с иконкой
+        let button = gtk4::Button::new();
+        button.add_css_class("volume-button");
+        button.set_has_frame(false);
+
+        let icon = gtk4::Label::new(None);
+        icon.add_css_class("volume-icon");
+        button.set_child(Some(&icon));
+
+        // Обработчик клика - переключение mute
+        {
+            let service = Arc::clone(&service);
+            button.connect_clicked(move |_| {
+                if let Err(e) = service.toggle_mute() {
+                    eprintln!("Failed to toggle mute: {}", e);
+                }
+                // Обновление произойдёт автоматически через событие PipeWire
+            });
+        }
+        container.append(&button);
+
+        // Лейбл с процентами
+        let label = gtk4::Label::new(None);
+        label.add_css_class("volume-percentage");
+        container.append(&label);
+
+        // Громкость не доступна
+        let not_found = gtk4::Label::new(Some("󰝟"));
+        not_found.add_css_class("volume-not-found");
+        container.append(&not_found);
+
+        let updating_slider = Rc::new(Cell::new(false));
+        let slider = Self::create_volume_slider(&service, &updating_slider);
+        let picker = DevicePicker::new(Arc::clone(&service), AudioDeviceKind::Sink);
+        let mixer = StreamMixer::new(Arc::clone(&service));
+
+        let popover = gtk4::Popover::new();
+        popover.set_parent(&button);
+        popover.set_position(gtk4::PositionType::Bottom);
+
+        let popover_box = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        popover_box.add_css_class("volume-popover");
+        popover_box.append(&slider);
+        popover_box.append(picker.widget());
+        popover_box.append(mixer.widget());
+        popover.set_child(Some(&popover_box));
+
+        // При правом клике показываем слайдер, устройства и микшер
+        let gesture = gtk4::GestureClick::new();
+        gesture.set_button(3); // Правая кнопка мыши
+        let popover_weak = popover.downgrade();
+        gesture.connect_released(move |_, _, _, _| {
+            if let Some(p) = popover_weak.upgrade() {
+                p.popup();
+            }
+        });
+        button.add_controller(gesture);
+
         Self {
             container,
+            button,
+            icon,
+            label,
+            not_found,
+            slider,
+            updating_slider,
+            picker,
+            mixer,
             service,
         }
     }
@@ -25,101 +107,48 @@ impl VolumeWidget {
     }
 
     pub fn update(&self) {
-        // Очищаем контейнер
-        while let Some(child) = self.container.first_child() {
-            self.container.remove(&child);
-        }
-
         // Получаем информацию о громкости
-        if let Some(volume_info) = self.service.get_volume_info() {
-            // Создаём кнопку с иконкой
-            let button = gtk4::Button::new();
-            button.add_css_class("volume-button");
-            button.set_has_frame(false);
-
-            let icon = self.create_volume_icon(&volume_info);
-            button.set_child(Some(&icon));
-
-            // Обработчик клика - переключение mute
-            let service = Arc::clone(&self.service);
-            button.connect_clicked(move |_| {
-                if let Err(e) = service.toggle_mute() {
-                    eprintln!("Failed to toggle mute: {}", e);
-                }
-                // Обновление произойдёт автоматически через событие PipeWire
-            });
+        let volume_info = self.service.get_volume_info();
 
-            self.container.append(&button);
+        self.button.set_visible(volume_info.is_some());
+        self.label.set_visible(volume_info.is_some());
+        self.not_found.set_visible(volume_info.is_none());
 
-            // Создаём лейбл с процентами
-            let label = gtk4::Label::new(Some(&format!("{}%", volume_info.volume)));
-            label.add_css_class("volume-percentage");
-            self.container.append(&label);
-
-            // Создаём слайдер для регулировки громкости
-            let slider = self.create_volume_slider(&volume_info);
-
-            // Показываем слайдер при наведении
-            let popover = gtk4::Popover::new();
-            popover.set_parent(&button);
-            popover.set_position(gtk4::PositionType::Bottom);
-
-            let slider_box = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
-            slider_box.add_css_class("volume-popover");
-            slider_box.set_height_request(120);
-            slider_box.append(&slider);
-
-            popover.set_child(Some(&slider_box));
-
-            // При правом клике показываем слайдер
-            let gesture = gtk4::GestureClick::new();
-            gesture.set_button(3); // Правая кнопка мыши
-            let popover_weak = popover.downgrade();
-            gesture.connect_released(move |_, _, _, _| {
-                if let Some(p) = popover_weak.upgrade() {
-                    p.popup();
-                }
-            });
-            button.add_controller(gesture);
+        let Some(volume_info) = volume_info else {
+            self.container.set_tooltip_text(None);
+            return;
+        };
 
-            // Устанавливаем tooltip
-            let tooltip = self.create_tooltip(&volume_info);
-            self.container.set_tooltip_text(Some(&tooltip));
+        self.icon.set_text(volume_icon(volume_info.volume, volume_info.muted));
+        if volume_info.muted {
+            self.icon.add_css_class("volume-muted");
         } else {
-            // Громкость не доступна
-            let label = gtk4::Label::new(Some("󰝟"));
-            label.add_css_class("volume-not-found");
-            self.container.append(&label);
+            self.icon.remove_css_class("volume-muted");
         }
-    }
 
-    /// Создаёт иконку громкости в зависимости от уровня и состояния mute
-    fn create_volume_icon(&self, info: &VolumeInfo) -> gtk4::Label {
-        let icon_text = if info.muted {
-            "󰝟" // Nerd Font: volume muted
-        } else {
-            match info.volume {
-                0 => "󰝟", // volume off (same as muted visually)
-                1..=33 => "󰕿", // volume low
-                34..=66 => "󰖀", // volume medium
-                _ => "󰕾", // volume high
-            }
-        };
+        self.label.set_text(&format!("{}%", volume_info.volume));
 
-        let icon = gtk4::Label::new(Some(icon_text));
-        icon.add_css_class("volume-icon");
-
-        if info.muted {
-            icon.add_css_class("volume-muted");
+        if self.slider.value() as u8 != volume_info.volume {
+            self.updating_slider.set(true);
+            self.slider.set_value(volume_info.volume as f64);
+            self.updating_slider.set(false);
         }
 
-        icon
+        self.picker.update();
+        self.mixer.update(&self.service.list_streams());
+
+        // Устанавливаем tooltip
+        let tooltip = self.create_tooltip(&volume_info);
+        self.container.set_tooltip_text(Some(&tooltip));
     }
 
     /// Создаёт вертикальный слайдер для регулировки громкости
-    fn create_volume_slider(&self, info: &VolumeInfo) -> gtk4::Scale {
+    fn create_volume_slider(
+        service: &Arc<dyn VolumeService + Send + Sync>,
+        updating: &Rc<Cell<bool>>,
+    ) -> gtk4::Scale {
         let adjustment = gtk4::Adjustment::new(
-            info.volume as f64,
+            0.0,
             0.0,
             100.0,
             1.0,
@@ -131,25 +160,24 @@ impl VolumeWidget {
         slider.set_inverted(true); // Верх - больше, низ - меньше
         slider.set_draw_value(true);
         slider.set_value_pos(gtk4::PositionType::Bottom);
+        slider.set_height_request(120);
         slider.add_css_class("volume-slider");
 
         // Обработчик изменения значения
-        let service = Arc::clone(&self.service);
-        let is_changing = RefCell::new(false);
+        let service = Arc::clone(service);
+        let updating = updating.clone();
 
         slider.connect_value_changed(move |scale| {
             // Предотвращаем циклическое обновление
-            if *is_changing.borrow() {
+            if updating.get() {
                 return;
             }
 
-            *is_changing.borrow_mut() = true;
             let volume = scale.value() as u8;
             if let Err(e) = service.set_volume(volume) {
                 eprintln!("Failed to set volume: {}", e);
             }
             // Обновление произойдёт автоматически через событие PipeWire
-            *is_changing.borrow_mut() = false;
         });
 
         slider
@@ -163,8 +191,273 @@ impl VolumeWidget {
             "Active"
         };
 
-        format!("Volume: {}%\nStatus: {}\n\nLeft click: Toggle mute\nRight click: Adjust volume",
+        format!("Volume: {}%\nStatus: {}\n\nLeft click: Toggle mute\nRight click: Volume, devices and mixer",
                 info.volume, status)
     }
 }
 
+/// Иконка громкости в зависимости от уровня и состояния mute
+pub fn volume_icon(volume: u8, muted: bool) -> &'static str {
+    if muted {
+        return "󰝟"; // Nerd Font: volume muted
+    }
+
+    match volume {
+        0 => "󰝟", // volume off (same as muted visually)
+        1..=33 => "󰕿", // volume low
+        34..=66 => "󰖀", // volume medium
+        _ => "󰕾", // volume high
+    }
+}
+
+/// Список устройств для выбора устройства по умолчанию
+///
+/// Кнопки пересоздаются только при изменении списка устройств.
+pub struct DevicePicker {
+    container: gtk4::Box,
+    service: Arc<dyn VolumeService + Send + Sync>,
+    kind: AudioDeviceKind,
+    devices: RefCell<Vec<AudioDevice>>,
+}
+
+impl DevicePicker {
+    pub fn new(service: Arc<dyn VolumeService + Send + Sync>, kind: AudioDeviceKind) -> Self {
+        let container = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+        container.add_css_class("audio-device-picker");
+        container.set_visible(false);
+
+        Self {
+            container,
+            service,
+            kind,
+            devices: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn widget(&self) -> &gtk4::Box {
+        &self.container
+    }
+
+    pub fn update(&self) {
+        let devices = self.service.list_devices(self.kind);
+        if *self.devices.borrow() == devices {
+            return;
+        }
+
+        while let Some(child) = self.container.first_child() {
+            self.container.remove(&child);
+        }
+
+        // Выбирать не из чего
+        self.container.set_visible(devices.len() > 1);
+
+        for device in &devices {
+            let button = gtk4::Button::with_label(&device.description);
+            button.add_css_class("audio-device");
+            button.set_has_frame(false);
+            button.set_tooltip_text(Some(&device.name));
+            if let Some(label) = button.child().and_downcast::<gtk4::Label>() {
+                label.set_xalign(0.0);
+                label.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+                label.set_max_width_chars(32);
+            }
+
+            if device.is_default {
+                button.add_css_class("audio-device-default");
+            }
+
+            let service = Arc::clone(&self.service);
+            let kind = self.kind;
+            let name = device.name.clone();
+            button.connect_clicked(move |_| {
+                if let Err(e) = service.set_default_device(kind, &name) {
+                    eprintln!("Failed to set default device: {}", e);
+                }
+            });
+
+            self.container.append(&button);
+        }
+
+        *self.devices.borrow_mut() = devices;
+    }
+}
+
+/// Микшер: громкость и mute каждого потока воспроизведения
+struct StreamMixer {
+    container: gtk4::Box,
+    list: gtk4::Box,
+    rows: RefCell<HashMap<u32, MixerRow>>,
+    service: Arc<dyn VolumeService + Send + Sync>,
+}
+
+impl StreamMixer {
+    fn new(service: Arc<dyn VolumeService + Send + Sync>) -> Self {
+        let container = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        container.add_css_class("volume-mixer");
+        container.set_visible(false);
+
+        let header = gtk4::Label::new(Some("Applications"));
+        header.add_css_class("volume-mixer-header");
+        header.set_xalign(0.0);
+        container.append(&header);
+
+        let list = gtk4::Box::new(gtk4::Orientation::Vertical, 4);
+        container.append(&list);
+
+        Self {
+            container,
+            list,
+            rows: RefCell::new(HashMap::new()),
+            service,
+        }
+    }
+
+    fn widget(&self) -> &gtk4::Box {
+        &self.container
+    }
+
+    /// Добавляет строки новых потоков, удаляет исчезнувшие, обновляет остальные на месте
+    fn update(&self, streams: &[AudioStream]) {
+        let mut rows = self.rows.borrow_mut();
+
+        rows.retain(|id, row| {
+            let keep = streams.iter().any(|s| s.id == *id);
+            if !keep {
+                self.list.remove(&row.root);
+            }
+            keep
+        });
+
+        for stream in streams {
+            let row = rows.entry(stream.id).or_insert_with(|| {
+                let row = MixerRow::new(stream.id, Arc::clone(&self.service));
+                self.list.append(&row.root);
+                row
+            });
+            row.update(stream);
+        }
+
+        self.container.set_visible(!streams.is_empty());
+    }
+}
+
+/// Строка микшера для одного потока
+struct MixerRow {
+    root: gtk4::Box,
+    icon_slot: gtk4::Box,
+    icon_name: RefCell<Option<String>>,
+    name: gtk4::Label,
+    title: gtk4::Label,
+    slider: gtk4::Scale,
+    mute_button: gtk4::Button,
+    muted: Rc<Cell<bool>>,
+    updating: Rc<Cell<bool>>,
+}
+
+impl MixerRow {
+    fn new(id: u32, service: Arc<dyn VolumeService + Send + Sync>) -> Self {
+        let root = gtk4::Box::new(gtk4::Orientation::Horizontal, 6);
+        root.add_css_class("volume-mixer-row");
+
+        let icon_slot = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
+        icon_slot.set_valign(gtk4::Align::Center);
+        root.append(&icon_slot);
+
+        let info = gtk4::Box::new(gtk4::Orientation::Vertical, 0);
+        info.set_hexpand(true);
+
+        let name = gtk4::Label::new(None);
+        name.add_css_class("volume-mixer-app");
+        name.set_xalign(0.0);
+        name.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        name.set_max_width_chars(28);
+        info.append(&name);
+
+        let title = gtk4::Label::new(None);
+        title.add_css_class("volume-mixer-title");
+        title.set_xalign(0.0);
+        title.set_ellipsize(gtk4::pango::EllipsizeMode::End);
+        title.set_max_width_chars(28);
+        info.append(&title);
+
+        let updating = Rc::new(Cell::new(false));
+        let slider = gtk4::Scale::with_range(gtk4::Orientation::Horizontal, 0.0, 100.0, 1.0);
+        slider.add_css_class("volume-mixer-slider");
+        slider.set_draw_value(false);
+        slider.set_width_request(160);
+        {
+            let service = Arc::clone(&service);
+            let updating = updating.clone();
+            slider.connect_value_changed(move |scale| {
+                if updating.get() {
+                    return;
+                }
+                if let Err(e) = service.set_stream_volume(id, scale.value() as u8) {
+                    eprintln!("Failed to set stream volume: {}", e);
+                }
+            });
+        }
+        info.append(&slider);
+        root.append(&info);
+
+        let muted = Rc::new(Cell::new(false));
+        let mute_button = gtk4::Button::new();
+        mute_button.add_css_class("volume-mixer-mute");
+        mute_button.set_has_frame(false);
+        mute_button.set_valign(gtk4::Align::Center);
+        {
+            let muted = muted.clone();
+            mute_button.connect_clicked(move |_| {
+                if let Err(e) = service.set_stream_mute(id, !muted.get()) {
+                    eprintln!("Failed to toggle stream mute: {}", e);
+                }
+            });
+        }
+        root.append(&mute_button);
+
+        Self {
+            root,
+            icon_slot,
+            icon_name: RefCell::new(None),
+            name,
+            title,
+            slider,
+            mute_button,
+            muted,
+            updating,
+        }
+    }
+
+    fn update(&self, stream: &AudioStream) {
+        // Иконка пересоздаётся только при смене приложения
+        let icon = stream.icon_name.clone().unwrap_or_else(|| stream.app_name.clone());
+        if self.icon_name.borrow().as_deref() != Some(icon.as_str()) {
+            while let Some(child) = self.icon_slot.first_child() {
+                self.icon_slot.remove(&child);
+            }
+            self.icon_slot.append(&create_app_icon(&icon, 24));
+            *self.icon_name.borrow_mut() = Some(icon);
+        }
+
+        self.name.set_text(&stream.app_name);
+
+        let title = stream.media_title.as_deref().unwrap_or_default();
+        self.title.set_text(title);
+        self.title.set_visible(!title.is_empty() && title != stream.app_name);
+        self.root.set_tooltip_text(Some(title).filter(|t| !t.is_empty()));
+
+        if self.slider.value() as u8 != stream.volume {
+            self.updating.set(true);
+            self.slider.set_value(stream.volume as f64);
+            self.updating.set(false);
+        }
+
+        self.muted.set(stream.muted);
+        self.mute_button.set_label(volume_icon(stream.volume, stream.muted));
+        if stream.muted {
+            self.mute_button.add_css_class("volume-muted");
+        } else {
+            self.mute_button.remove_css_class("volume-muted");
+        }
+    }
+}
@@ -1,14 +1,24 @@
+use crate::config::WorkspacesOptions;
+use crate::domain::models::{WindowInfo, Workspace, WorkspaceSnapshot};
 use crate::domain::workspace_service::WorkspaceService;
+use crate::ui::app_icon::create_app_icon;
 use gtk4::prelude::*;
 use gtk4::{gdk, glib};
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Максимум иконок приложений на один workspace
+const MAX_ICONS_PER_WORKSPACE: usize = 4;
+
+/// Префикс данных drag-and-drop, чтобы не принимать посторонний текст
+const DRAG_PREFIX: &str = "hyprline-workspace:";
+
 pub struct WorkspacesWidget {
     container: gtk4::Box,
     workspace_keys: HashMap<i32, String>,
     monitor_name: String,
     service: Arc<dyn WorkspaceService + Send + Sync>,
+    options: WorkspacesOptions,
 }
 
 impl WorkspacesWidget {
@@ -16,16 +26,66 @@ impl WorkspacesWidget {
         monitor_name: String,
         workspace_keys: HashMap<i32, String>,
         service: Arc<dyn WorkspaceService + Send + Sync>,
+        options: WorkspacesOptions,
     ) -> Self {
         let container = gtk4::Box::new(gtk4::Orientation::Horizontal, 0);
         container.add_css_class("workspaces");
         container.set_margin_start(10);
 
+        // Прокрутка колесом - переключение между открытыми workspaces
+        let scroll = gtk4::EventControllerScroll::new(
+            gtk4::EventControllerScrollFlags::VERTICAL | gtk4::EventControllerScrollFlags::DISCRETE,
+        );
+        let service_for_scroll = service.clone();
+        scroll.connect_scroll(move |_, _, dy| {
+            let delta = if dy > 0.0 { 1 } else if dy < 0.0 { -1 } else { 0 };
+            if delta != 0 {
+                if let Err(e) = service_for_scroll.cycle_workspace(delta) {
+                    eprintln!("[Workspaces] ✗ Failed to cycle workspace: {}", e);
+                }
+            }
+            glib::Propagation::Stop
+        });
+        container.add_controller(scroll);
+
+        // Перетаскивание workspace с панели другого монитора - перенос workspace на этот монитор
+        let drop_target = gtk4::DropTarget::new(glib::Type::STRING, gdk::DragAction::MOVE);
+        let service_for_drop = service.clone();
+        let monitor_for_drop = monitor_name.clone();
+        drop_target.connect_drop(move |_, value, _, _| {
+            let Ok(data) = value.get::<String>() else {
+                return false;
+            };
+            let Some(payload) = data.strip_prefix(DRAG_PREFIX) else {
+                return false;
+            };
+            let Some((source_monitor, target)) = payload.split_once('|') else {
+                return false;
+            };
+
+            if source_monitor == monitor_for_drop {
+                return false;
+            }
+
+            match service_for_drop.move_workspace_to_monitor(target, &monitor_for_drop) {
+                Ok(()) => {
+                    eprintln!("[Workspaces] ✓ Moved workspace {} to {}", target, monitor_for_drop);
+                    true
+                }
+                Err(e) => {
+                    eprintln!("[Workspaces] ✗ Failed to move workspace: {}", e);
+                    false
+                }
+            }
+        });
+        container.add_controller(drop_target);
+
         Self {
             container,
             workspace_keys,
             monitor_name,
             service,
+            options,
         }
     }
 
@@ -34,71 +94,179 @@ impl WorkspacesWidget {
     }
 
     pub fn update(&self) {
-        let workspaces = self.service.get_workspaces();
-        let global_active_id = self.service.get_active_workspace();
-        let active_monitor = self.service.get_active_monitor();
-        let monitor_active_id = self.service.get_active_workspace_for_monitor(&self.monitor_name);
+        // При ошибке IPC оставляем последнее отрисованное состояние
+        let snapshot = match self.service.get_snapshot(&self.monitor_name) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                eprintln!("[Workspaces] ✗ Failed to query workspaces: {}", e);
+                return;
+            }
+        };
+        let WorkspaceSnapshot {
+            workspaces,
+            active_workspace: global_active_id,
+            active_monitor,
+            monitor_active_workspace: monitor_active_id,
+            active_special,
+            clients,
+            ..
+        } = snapshot;
+        let active_special = if self.options.show_special { active_special } else { None };
+        let clients = if self.options.show_icons { clients } else { Vec::new() };
 
         // Очищаем существующие элементы
         while let Some(child) = self.container.first_child() {
             self.container.remove(&child);
         }
 
-        // Фильтруем workspaces для текущего монитора
-        let monitor_workspaces: Vec<_> = workspaces
+        // Фильтруем workspaces для текущего монитора (если включено)
+        let visible: Vec<&Workspace> = workspaces
             .iter()
-            .filter(|w| w.monitor == self.monitor_name)
+            .filter(|w| !self.options.monitor_only || w.monitor == self.monitor_name)
             .collect();
 
-        // Собираем ID workspaces для отображения
-        let mut workspace_ids: Vec<i32> = monitor_workspaces
+        // Обычные workspaces: с окнами или активные
+        let mut regular: Vec<&Workspace> = visible
             .iter()
-            .filter(|w| w.windows > 0)
-            .map(|w| w.id)
+            .copied()
+            .filter(|w| !w.is_special())
+            .filter(|w| w.windows > 0 || Some(w.id) == monitor_active_id || w.id == global_active_id)
             .collect();
 
-        // Добавляем активный workspace для этого монитора
-        if let Some(active_id) = monitor_active_id {
-            if !workspace_ids.contains(&active_id) {
-                workspace_ids.push(active_id);
-            }
-        }
+        // Нумерованные по id, именованные (отрицательный id) - после них по имени
+        regular.sort_by(|a, b| {
+            (a.id <= 0, a.id.max(0), &a.name).cmp(&(b.id <= 0, b.id.max(0), &b.name))
+        });
 
-        workspace_ids.sort();
+        for workspace in regular {
+            let is_active = workspace.id == global_active_id && active_monitor == self.monitor_name;
+            let is_monitor_active = Some(workspace.id) == monitor_active_id;
 
-        // Создаём метки для workspaces
-        for ws_id in workspace_ids {
             let text = self
                 .workspace_keys
-                .get(&ws_id)
-                .map(|k| k.to_string())
-                .unwrap_or_else(|| ws_id.to_string());
+                .get(&workspace.id)
+                .cloned()
+                .unwrap_or_else(|| workspace.name.clone());
 
-            let label = gtk4::Label::new(Some(&text));
-            label.add_css_class("workspace-label");
+            let item = self.create_item(workspace, &text, &clients);
+            if is_active || is_monitor_active {
+                item.add_css_class("active");
+            } else if workspace.windows > 0 {
+                item.add_css_class("occupied");
+            }
+            self.container.append(&item);
+        }
 
-            let is_active = ws_id == global_active_id && active_monitor == self.monitor_name;
-            let is_monitor_active = Some(ws_id) == monitor_active_id;
+        // Special workspaces: с окнами или открытые на этом мониторе
+        if self.options.show_special {
+            let mut special: Vec<&Workspace> = visible
+                .iter()
+                .copied()
+                .filter(|w| w.is_special())
+                .filter(|w| w.windows > 0 || active_special.as_deref() == Some(w.name.as_str()))
+                .collect();
+            special.sort_by(|a, b| a.name.cmp(&b.name));
 
-            if is_active || is_monitor_active {
-                label.add_css_class("active");
-            } else if monitor_workspaces.iter().any(|w| w.id == ws_id && w.windows > 0) {
-                label.add_css_class("occupied");
+            for workspace in special {
+                let text = match workspace.special_name() {
+                    "" => "󰐃".to_string(),
+                    name => name.to_string(),
+                };
+
+                let item = self.create_item(workspace, &text, &clients);
+                item.add_css_class("special");
+                if active_special.as_deref() == Some(workspace.name.as_str()) {
+                    item.add_css_class("active");
+                } else if workspace.windows > 0 {
+                    item.add_css_class("occupied");
+                }
+                self.container.append(&item);
             }
+        }
+    }
+
+    /// Создаёт элемент workspace: подпись и иконки приложений
+    fn create_item(&self, workspace: &Workspace, text: &str, clients: &[WindowInfo]) -> gtk4::Box {
+        let item = gtk4::Box::new(gtk4::Orientation::Horizontal, 4);
+        item.add_css_class("workspace-label");
 
-            // Обработчик кликов
-            let service = self.service.clone();
-            let event_controller = gtk4::EventControllerLegacy::new();
-            event_controller.connect_event(move |_, event| {
-                if event.event_type() == gdk::EventType::ButtonPress {
-                    service.switch_workspace(ws_id);
+        let label = gtk4::Label::new(Some(text));
+        label.add_css_class("workspace-name");
+        item.append(&label);
+
+        let windows: Vec<&WindowInfo> = clients
+            .iter()
+            .filter(|c| c.workspace.id == workspace.id && c.mapped)
+            .collect();
+
+        if !windows.is_empty() {
+            // Одна иконка на класс приложения
+            let mut classes: Vec<&str> = Vec::new();
+            for window in &windows {
+                if !classes.contains(&window.class.as_str()) {
+                    classes.push(&window.class);
                 }
-                glib::Propagation::Proceed
-            });
-            label.add_controller(event_controller);
+            }
 
-            self.container.append(&label);
+            for class in classes.into_iter().take(MAX_ICONS_PER_WORKSPACE) {
+                let icon = create_app_icon(class, 14);
+                icon.add_css_class("workspace-icon");
+                item.append(&icon);
+            }
+
+            let tooltip = windows
+                .iter()
+                .map(|w| w.title.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            item.set_tooltip_text(Some(&tooltip));
         }
+
+        // Обработчик кликов: левая кнопка - переключение, средняя - перенос активного окна
+        let service = self.service.clone();
+        let click_workspace = workspace.clone();
+        let click = gtk4::GestureClick::new();
+        click.set_button(0);
+        click.connect_pressed(move |gesture, _, _, _| {
+            match gesture.current_button() {
+                gdk::BUTTON_PRIMARY => {
+                    let result = if click_workspace.is_special() {
+                        service.toggle_special_workspace(click_workspace.special_name())
+                    } else if click_workspace.id > 0 {
+                        service.switch_workspace(click_workspace.id)
+                    } else {
+                        service.switch_workspace_by_name(&click_workspace.name)
+                    };
+                    if let Err(e) = result {
+                        eprintln!("[Workspaces] ✗ Failed to switch workspace: {}", e);
+                    }
+                }
+                gdk::BUTTON_MIDDLE => {
+                    if let Err(e) = service.move_focused_window_to_workspace(&click_workspace.dispatch_target()) {
+                        eprintln!("[Workspaces] ✗ Failed to move window: {}", e);
+                    }
+                }
+                _ => {}
+            }
+        });
+        item.add_controller(click);
+
+        // Источник drag-and-drop: "<монитор>|<workspace>"
+        let drag_source = gtk4::DragSource::new();
+        drag_source.set_actions(gdk::DragAction::MOVE);
+        let drag_data = format!("{}{}|{}", DRAG_PREFIX, workspace.monitor, workspace.dispatch_target());
+        drag_source.connect_prepare(move |_, _, _| {
+            Some(gdk::ContentProvider::for_value(&drag_data.to_value()))
+        });
+        let drag_label = text.to_string();
+        drag_source.connect_drag_begin(move |_, drag| {
+            let icon = gtk4::Label::new(Some(&drag_label));
+            icon.add_css_class("workspace-drag-icon");
+            let drag_icon = gtk4::DragIcon::for_drag(drag);
+            drag_icon.set_child(Some(&icon));
+        });
+        item.add_controller(drag_source);
+
+        item
     }
 }
-